@@ -2,9 +2,9 @@ use std::env::current_dir;
 use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
-use farm_20_nv::msg::{
-    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, StakerInfoResponse,
-    StateResponse,
+
+use farm_campaign::msg::{
+    ConfigResponse, Cw20HookMsg, EpochResponse, ExecuteMsg, InstantiateMsg, ParticipantResponse, QueryMsg,
 };
 
 fn main() {
@@ -18,6 +18,6 @@ fn main() {
     export_schema(&schema_for!(Cw20HookMsg), &out_dir);
     export_schema(&schema_for!(QueryMsg), &out_dir);
     export_schema(&schema_for!(ConfigResponse), &out_dir);
-    export_schema(&schema_for!(StakerInfoResponse), &out_dir);
-    export_schema(&schema_for!(StateResponse), &out_dir);
+    export_schema(&schema_for!(ParticipantResponse), &out_dir);
+    export_schema(&schema_for!(EpochResponse), &out_dir);
 }
@@ -0,0 +1,108 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+
+use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult};
+use cw2::set_contract_version;
+use cyber_std::particle::check_particle;
+use farm::contract::validate_rewards;
+
+use crate::{
+    error::ContractError,
+    execute::{
+        execute_add_distribution_periods_native, execute_receive, execute_reclaim_epoch, execute_register,
+        execute_submit_cyberlinks, execute_update_weight, execute_withdraw,
+    },
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    query::{query_config, query_epoch, query_participant},
+    state::{Config, CONFIG},
+};
+
+const CONTRACT_NAME: &str = "crates.io:farm-campaign";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const DEFAULT_MAX_LINKS: u32 = 30;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    if check_particle(msg.particle.clone()).is_err() {
+        return Err(ContractError::InvalidParticle { particle: msg.particle });
+    }
+    if msg.epoch_duration == 0 {
+        return Err(ContractError::InvalidEpochDuration {});
+    }
+    let time = env.block.time.seconds();
+    let rewards = validate_rewards(deps.api, &info, time, msg.rewards)?;
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            distribution_account: deps.api.addr_validate(&msg.distribution_account)?,
+            particle: msg.particle.clone(),
+            rewards,
+            start_time: time,
+            epoch_duration: msg.epoch_duration,
+            link_weight: msg.link_weight,
+            rank_multiplier: msg.rank_multiplier,
+            max_links: msg.max_links.unwrap_or(DEFAULT_MAX_LINKS),
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("particle", msg.particle))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Register {} => execute_register(deps, env, info),
+        ExecuteMsg::SubmitCyberlinks { particles } => execute_submit_cyberlinks(deps, env, info, particles),
+        ExecuteMsg::UpdateWeight {} => execute_update_weight(deps, env, info),
+        ExecuteMsg::Withdraw {} => execute_withdraw(deps, env, info),
+        ExecuteMsg::Receive(msg) => execute_receive(deps, env, info, msg),
+        ExecuteMsg::AddDistributionPeriods { reward_asset, periods } => {
+            execute_add_distribution_periods_native(deps, env, info, reward_asset, periods)
+        }
+        ExecuteMsg::ReclaimEpoch { epoch } => execute_reclaim_epoch(deps, env, info, epoch),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(
+    deps: Deps,
+    env: Env,
+    msg: QueryMsg,
+) -> StdResult<Binary> {
+    let time = env.block.time.seconds();
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Participant { address } => to_binary(&query_participant(deps, address, time)?),
+        QueryMsg::Epoch { epoch } => {
+            let config = CONFIG.load(deps.storage)?;
+            to_binary(&query_epoch(deps, epoch.unwrap_or_else(|| config.epoch(time)))?)
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    deps: DepsMut,
+    _env: Env,
+    _msg: Empty,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::default())
+}
@@ -0,0 +1,42 @@
+use cosmwasm_std::StdError;
+use farm::error::ContractError as FarmContractError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Farm(#[from] FarmContractError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid particle {particle}")]
+    InvalidParticle { particle: String },
+
+    #[error("Epoch duration must not be zero")]
+    InvalidEpochDuration {},
+
+    #[error("Already registered")]
+    AlreadyRegistered {},
+
+    #[error("Not registered")]
+    NotRegistered {},
+
+    #[error("Cyberlink from {from} to the campaign particle not found")]
+    CyberlinkNotFound { from: String },
+
+    #[error("Cyberlink from {from} already submitted")]
+    DuplicateCyberlink { from: String },
+
+    #[error("More than {max} cyberlinks")]
+    TooManyCyberlinks { max: u32 },
+
+    #[error("Epoch {epoch} is not finished")]
+    EpochNotFinished { epoch: u64 },
+
+    #[error("Epoch {epoch} has participants or is reclaimed")]
+    CannotReclaimEpoch { epoch: u64 },
+}
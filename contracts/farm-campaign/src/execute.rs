@@ -0,0 +1,282 @@
+use cosmwasm_std::{
+    from_binary, Addr, CosmosMsg, DepsMut, Env, MessageInfo, Order, QuerierWrapper, Response, StdResult, Storage,
+    Uint128,
+};
+use cw20::Cw20ReceiveMsg;
+use cw_storage_plus::Bound;
+use cyber_std::particle::check_particle;
+use cyber_std::{CyberQuerier, CyberQueryWrapper};
+use farm::asset::AssetInfo;
+use farm::error::ContractError as FarmContractError;
+use farm::execute::{compute_distributed_amount, validate_distribution_periods};
+use farm::state::RewardAsset;
+
+use crate::{
+    error::ContractError,
+    msg::Cw20HookMsg,
+    state::{Config, Participant, CONFIG, EPOCHS, PARTICIPANTS, PARTICIPANT_WEIGHTS},
+};
+
+pub fn execute_register(deps: DepsMut, _env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    if PARTICIPANTS.has(deps.storage, &info.sender) {
+        return Err(ContractError::AlreadyRegistered {});
+    }
+    PARTICIPANTS.save(deps.storage, &info.sender, &Participant { links: vec![] })?;
+
+    Ok(Response::new().add_attributes(vec![("action", "register"), ("participant", info.sender.as_str())]))
+}
+
+// particles have to be cyberlinked by the sender to the campaign particle
+pub fn execute_submit_cyberlinks(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    particles: Vec<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut participant = PARTICIPANTS
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::NotRegistered {})?;
+
+    let querier = QuerierWrapper::<CyberQueryWrapper>::new(&*deps.querier);
+    let cyber_querier = CyberQuerier::new(&querier);
+    for particle in particles {
+        if check_particle(particle.clone()).is_err() || particle == config.particle {
+            return Err(ContractError::InvalidParticle { particle });
+        }
+        if participant.links.contains(&particle) {
+            return Err(ContractError::DuplicateCyberlink { from: particle });
+        }
+        let link = cyber_querier.query_cyberlink_exist(particle.as_str(), config.particle.as_str(), info.sender.as_str())?;
+        if !link.exist {
+            return Err(ContractError::CyberlinkNotFound { from: particle });
+        }
+        participant.links.push(particle);
+    }
+    if participant.links.len() > config.max_links as usize {
+        return Err(ContractError::TooManyCyberlinks { max: config.max_links });
+    }
+    PARTICIPANTS.save(deps.storage, &info.sender, &participant)?;
+
+    let weight = update_weight(deps.storage, &cyber_querier, &config, &info.sender, &participant, env.block.time.seconds())?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "submit_cyberlinks"),
+        ("participant", info.sender.as_str()),
+        ("links", participant.links.len().to_string().as_str()),
+        ("weight", weight.to_string().as_str()),
+    ]))
+}
+
+pub fn execute_update_weight(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let participant = PARTICIPANTS
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::NotRegistered {})?;
+
+    let querier = QuerierWrapper::<CyberQueryWrapper>::new(&*deps.querier);
+    let cyber_querier = CyberQuerier::new(&querier);
+    let weight = update_weight(deps.storage, &cyber_querier, &config, &info.sender, &participant, env.block.time.seconds())?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "update_weight"),
+        ("participant", info.sender.as_str()),
+        ("weight", weight.to_string().as_str()),
+    ]))
+}
+
+// weight of the participant in the current epoch from its cyberlinks and the current ranks of the linked particles
+fn update_weight(
+    storage: &mut dyn Storage,
+    querier: &CyberQuerier,
+    config: &Config,
+    address: &Addr,
+    participant: &Participant,
+    time: u64,
+) -> StdResult<Uint128> {
+    let mut weight = Uint128::zero();
+    for particle in participant.links.iter() {
+        let rank = querier.query_particle_rank(particle.as_str())?.rank;
+        weight += config.link_weight + Uint128::from(rank) * config.rank_multiplier;
+    }
+
+    let epoch = config.epoch(time);
+    let previous = PARTICIPANT_WEIGHTS.may_load(storage, (address, epoch))?.unwrap_or_default();
+    let mut epoch_info = EPOCHS.may_load(storage, epoch)?.unwrap_or_default();
+    epoch_info.total_weight = epoch_info.total_weight.checked_sub(previous)? + weight;
+    EPOCHS.save(storage, epoch, &epoch_info)?;
+    if weight.is_zero() {
+        PARTICIPANT_WEIGHTS.remove(storage, (address, epoch));
+    } else {
+        PARTICIPANT_WEIGHTS.save(storage, (address, epoch), &weight)?;
+    }
+    Ok(weight)
+}
+
+// reward of the asset distributed during the epoch
+pub fn epoch_reward(config: &Config, reward: &RewardAsset, epoch: u64) -> Uint128 {
+    compute_distributed_amount(&reward.distribution_schedule, config.epoch_start(epoch), config.epoch_start(epoch + 1))
+}
+
+// finished epochs the participant has weight in and its rewards of each reward asset in them
+pub fn pending_rewards(
+    storage: &dyn Storage,
+    config: &Config,
+    address: &Addr,
+    time: u64,
+) -> StdResult<(Vec<u64>, Vec<Uint128>)> {
+    let current_epoch = config.epoch(time);
+    let weights = PARTICIPANT_WEIGHTS
+        .prefix(address)
+        .range(storage, None, Some(Bound::exclusive(current_epoch)), Order::Ascending)
+        .collect::<StdResult<Vec<(u64, Uint128)>>>()?;
+
+    let mut amounts = vec![Uint128::zero(); config.rewards.len()];
+    for &(epoch, weight) in weights.iter() {
+        let total_weight = EPOCHS.load(storage, epoch)?.total_weight;
+        for (reward, amount) in config.rewards.iter().zip(amounts.iter_mut()) {
+            *amount += epoch_reward(config, reward, epoch).multiply_ratio(weight, total_weight);
+        }
+    }
+    Ok((weights.into_iter().map(|(epoch, _)| epoch).collect(), amounts))
+}
+
+pub fn execute_withdraw(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let (epochs, amounts) = pending_rewards(deps.storage, &config, &info.sender, env.block.time.seconds())?;
+    for epoch in epochs {
+        PARTICIPANT_WEIGHTS.remove(deps.storage, (&info.sender, epoch));
+    }
+
+    let rewards: Vec<(&AssetInfo, Uint128)> = config
+        .rewards
+        .iter()
+        .zip(amounts)
+        .filter(|(_, amount)| !amount.is_zero())
+        .map(|(reward, amount)| (&reward.asset, amount))
+        .collect();
+    let messages = rewards
+        .iter()
+        .map(|(asset, amount)| asset.transfer_msg(info.sender.as_str(), *amount))
+        .collect::<StdResult<Vec<CosmosMsg>>>()?;
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "withdraw"),
+        ("participant", info.sender.as_str()),
+        ("amount", amounts_attribute(&rewards).as_str()),
+    ]))
+}
+
+pub fn execute_reclaim_epoch(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    epoch: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.distribution_account {
+        return Err(ContractError::Unauthorized {});
+    }
+    if epoch >= config.epoch(env.block.time.seconds()) {
+        return Err(ContractError::EpochNotFinished { epoch });
+    }
+    let mut epoch_info = EPOCHS.may_load(deps.storage, epoch)?.unwrap_or_default();
+    if !epoch_info.total_weight.is_zero() || epoch_info.reclaimed {
+        return Err(ContractError::CannotReclaimEpoch { epoch });
+    }
+    epoch_info.reclaimed = true;
+    EPOCHS.save(deps.storage, epoch, &epoch_info)?;
+
+    let rewards: Vec<(&AssetInfo, Uint128)> = config
+        .rewards
+        .iter()
+        .map(|reward| (&reward.asset, epoch_reward(&config, reward, epoch)))
+        .filter(|(_, amount)| !amount.is_zero())
+        .collect();
+    let messages = rewards
+        .iter()
+        .map(|(asset, amount)| asset.transfer_msg(config.distribution_account.as_str(), *amount))
+        .collect::<StdResult<Vec<CosmosMsg>>>()?;
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "reclaim_epoch"),
+        ("epoch", epoch.to_string().as_str()),
+        ("amount", amounts_attribute(&rewards).as_str()),
+    ]))
+}
+
+pub fn execute_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    match from_binary(&cw20_msg.msg) {
+        Ok(Cw20HookMsg::AddDistributionPeriods { periods }) => {
+            let cw20_sender = deps.api.addr_validate(&cw20_msg.sender)?;
+            let reward_asset = AssetInfo::Cw20 { address: info.sender };
+            execute_add_distribution_periods(deps, env, cw20_sender, reward_asset, periods, cw20_msg.amount)
+        }
+        Err(_) => Err(ContractError::Unauthorized {}),
+    }
+}
+
+// add distribution periods of native reward asset funded by attached funds
+pub fn execute_add_distribution_periods_native(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    reward_asset: AssetInfo,
+    periods: Vec<(u64, u64, Uint128)>,
+) -> Result<Response, ContractError> {
+    let reward_asset = reward_asset.validate(deps.api)?;
+    let amount = reward_asset.amount_in(&info.funds);
+
+    execute_add_distribution_periods(deps, env, info.sender, reward_asset, periods, amount)
+}
+
+// periods added to the current epoch share its rewards among all its weights
+pub fn execute_add_distribution_periods(
+    deps: DepsMut,
+    env: Env,
+    sender_addr: Addr,
+    reward_asset: AssetInfo,
+    periods: Vec<(u64, u64, Uint128)>,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if sender_addr != config.distribution_account {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let committed: Uint128 = periods.iter().map(|period| period.2).sum();
+    if amount != committed {
+        return Err(FarmContractError::InvalidRewardFunds { expected: committed, received: amount }.into());
+    }
+
+    let position = match config.rewards.iter().position(|reward| reward.asset == reward_asset) {
+        Some(position) => position,
+        None => {
+            config.rewards.push(RewardAsset { asset: reward_asset.clone(), distribution_schedule: vec![] });
+            config.rewards.len() - 1
+        }
+    };
+    let reward = &mut config.rewards[position];
+    validate_distribution_periods(&reward.distribution_schedule, &periods, env.block.time.seconds())?;
+    reward.distribution_schedule.extend(periods);
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "add_distribution_periods"),
+        ("reward_asset", reward_asset.to_string().as_str()),
+        ("amount", amount.to_string().as_str()),
+    ]))
+}
+
+fn amounts_attribute(amounts: &[(&AssetInfo, Uint128)]) -> String {
+    amounts
+        .iter()
+        .map(|(asset, amount)| format!("{}{}", amount, asset))
+        .collect::<Vec<String>>()
+        .join(",")
+}
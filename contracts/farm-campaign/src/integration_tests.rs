@@ -0,0 +1,243 @@
+#![cfg(test)]
+
+use std::marker::PhantomData;
+
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+use cosmwasm_std::{
+    coins, from_binary, to_binary, BankMsg, ContractResult, Decimal, Deps, DepsMut, Env, OwnedDeps, QuerierWrapper,
+    Response, SubMsg, SystemError, SystemResult, Uint128,
+};
+use cyber_std::particle::prepare_particle;
+use cyber_std::{CyberQuery, CyberQueryWrapper, CyberlinkExistResponse, ParticleRankResponse};
+use farm::asset::AssetInfo;
+use farm::error::ContractError as FarmContractError;
+use farm::state::RewardAsset;
+
+use crate::contract::{execute, instantiate, query};
+use crate::error::ContractError;
+use crate::msg::{EpochResponse, ExecuteMsg, InstantiateMsg, ParticipantResponse, QueryMsg};
+
+const DISTRIBUTION_ACCOUNT: &str = "distribution";
+const REWARD_DENOM: &str = "boot";
+
+type CyberDeps = OwnedDeps<MockStorage, MockApi, MockQuerier<CyberQueryWrapper>, CyberQueryWrapper>;
+
+fn particle(content: &str) -> String {
+    prepare_particle(content.to_string()).unwrap().to_string()
+}
+
+// alice links "first" and "second", bob links "third" to the campaign particle
+fn cyber_dependencies() -> CyberDeps {
+    let querier = MockQuerier::<CyberQueryWrapper>::new(&[]).with_custom_handler(|query| {
+        let res = match &query.query_data {
+            CyberQuery::CyberlinkExist { from, to, address } => {
+                let links = [("first", "alice"), ("second", "alice"), ("third", "bob")];
+                let exist = *to == particle("campaign")
+                    && links.iter().any(|(content, neuron)| *from == particle(content) && address == neuron);
+                to_binary(&CyberlinkExistResponse { exist })
+            }
+            CyberQuery::ParticleRank { particle: linked } => {
+                let ranks = [("first", 10), ("second", 0), ("third", 30)];
+                let rank = ranks.iter().find(|(content, _)| *linked == particle(content)).map_or(0, |r| r.1);
+                to_binary(&ParticleRankResponse { rank })
+            }
+            _ => return SystemResult::Err(SystemError::UnsupportedRequest { kind: "cyber".to_string() }),
+        };
+        SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+    });
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier,
+        custom_query_type: PhantomData::<CyberQueryWrapper>,
+    }
+}
+
+fn exec(deps: &mut CyberDeps, env: &Env, sender: &str, msg: ExecuteMsg) -> Result<Response, ContractError> {
+    let deps_mut = DepsMut { storage: &mut deps.storage, api: &deps.api, querier: QuerierWrapper::new(&deps.querier) };
+    execute(deps_mut, env.clone(), mock_info(sender, &[]), msg)
+}
+
+fn participant(deps: &CyberDeps, env: &Env, address: &str) -> ParticipantResponse {
+    let deps = Deps { storage: &deps.storage, api: &deps.api, querier: QuerierWrapper::new(&deps.querier) };
+    from_binary(&query(deps, env.clone(), QueryMsg::Participant { address: address.to_string() }).unwrap()).unwrap()
+}
+
+fn epoch(deps: &CyberDeps, env: &Env, epoch: Option<u64>) -> EpochResponse {
+    let deps = Deps { storage: &deps.storage, api: &deps.api, querier: QuerierWrapper::new(&deps.querier) };
+    from_binary(&query(deps, env.clone(), QueryMsg::Epoch { epoch }).unwrap()).unwrap()
+}
+
+// campaign of two epochs of 100 seconds rewarding 500 each
+fn setup() -> (CyberDeps, Env) {
+    let mut deps = cyber_dependencies();
+    let env = mock_env();
+    let time = env.block.time.seconds();
+    let msg = InstantiateMsg {
+        distribution_account: DISTRIBUTION_ACCOUNT.to_string(),
+        particle: particle("campaign"),
+        rewards: vec![RewardAsset {
+            asset: AssetInfo::Native { denom: REWARD_DENOM.to_string() },
+            distribution_schedule: vec![(time, time + 200, Uint128::new(1000))],
+        }],
+        epoch_duration: 100,
+        link_weight: Uint128::new(10),
+        rank_multiplier: Decimal::one(),
+        max_links: Some(2),
+    };
+    let deps_mut = DepsMut { storage: &mut deps.storage, api: &deps.api, querier: QuerierWrapper::new(&deps.querier) };
+    instantiate(deps_mut, env.clone(), mock_info(DISTRIBUTION_ACCOUNT, &coins(1000, REWARD_DENOM)), msg).unwrap();
+    (deps, env)
+}
+
+#[test]
+fn instantiate_validates_campaign() {
+    let mut deps = cyber_dependencies();
+    let msg = InstantiateMsg {
+        distribution_account: DISTRIBUTION_ACCOUNT.to_string(),
+        particle: "campaign".to_string(),
+        rewards: vec![],
+        epoch_duration: 100,
+        link_weight: Uint128::new(10),
+        rank_multiplier: Decimal::zero(),
+        max_links: None,
+    };
+    let mut instantiate_campaign = |msg: InstantiateMsg| {
+        let deps_mut =
+            DepsMut { storage: &mut deps.storage, api: &deps.api, querier: QuerierWrapper::new(&deps.querier) };
+        instantiate(deps_mut, mock_env(), mock_info(DISTRIBUTION_ACCOUNT, &[]), msg)
+    };
+
+    let err = instantiate_campaign(msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::InvalidParticle { particle: "campaign".to_string() });
+    let err = instantiate_campaign(InstantiateMsg { particle: particle("campaign"), epoch_duration: 0, ..msg.clone() })
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidEpochDuration {});
+    instantiate_campaign(InstantiateMsg { particle: particle("campaign"), ..msg }).unwrap();
+}
+
+#[test]
+fn cyberlinks_are_verified() {
+    let (mut deps, env) = setup();
+
+    let submit = |particles: &[&str]| ExecuteMsg::SubmitCyberlinks {
+        particles: particles.iter().map(|content| particle(content)).collect(),
+    };
+    let err = exec(&mut deps, &env, "alice", submit(&["first"])).unwrap_err();
+    assert_eq!(err, ContractError::NotRegistered {});
+    exec(&mut deps, &env, "alice", ExecuteMsg::Register {}).unwrap();
+    let err = exec(&mut deps, &env, "alice", ExecuteMsg::Register {}).unwrap_err();
+    assert_eq!(err, ContractError::AlreadyRegistered {});
+
+    let err = exec(&mut deps, &env, "alice", submit(&["third"])).unwrap_err();
+    assert_eq!(err, ContractError::CyberlinkNotFound { from: particle("third") });
+    let err = exec(&mut deps, &env, "alice", ExecuteMsg::SubmitCyberlinks { particles: vec!["first".to_string()] })
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidParticle { particle: "first".to_string() });
+    let err = exec(&mut deps, &env, "alice", submit(&["first", "first"])).unwrap_err();
+    assert_eq!(err, ContractError::DuplicateCyberlink { from: particle("first") });
+
+    exec(&mut deps, &env, "alice", submit(&["first"])).unwrap();
+    let err = exec(&mut deps, &env, "alice", submit(&["first"])).unwrap_err();
+    assert_eq!(err, ContractError::DuplicateCyberlink { from: particle("first") });
+    let err = exec(&mut deps, &env, "alice", submit(&["second", "campaign"])).unwrap_err();
+    assert_eq!(err, ContractError::InvalidParticle { particle: particle("campaign") });
+
+    // link weight of 10 and rank of 10 of the first particle
+    let info = participant(&deps, &env, "alice");
+    assert_eq!(info.links, vec![particle("first")]);
+    assert_eq!(info.weight, Uint128::new(20));
+}
+
+#[test]
+fn epoch_rewards_are_shared_by_weight() {
+    let (mut deps, mut env) = setup();
+    for (neuron, particles) in [("alice", vec!["first", "second"]), ("bob", vec!["third"])] {
+        exec(&mut deps, &env, neuron, ExecuteMsg::Register {}).unwrap();
+        let particles = particles.iter().map(|content| particle(content)).collect();
+        exec(&mut deps, &env, neuron, ExecuteMsg::SubmitCyberlinks { particles }).unwrap();
+    }
+    assert_eq!(participant(&deps, &env, "alice").weight, Uint128::new(30));
+    assert_eq!(participant(&deps, &env, "bob").weight, Uint128::new(40));
+    let current = epoch(&deps, &env, None);
+    assert_eq!((current.epoch, current.total_weight), (0, Uint128::new(70)));
+    assert_eq!(current.rewards[0].amount, Uint128::new(500));
+
+    // rewards of the epoch are paid after it finishes, weight is updated for every epoch
+    let res = exec(&mut deps, &env, "alice", ExecuteMsg::Withdraw {}).unwrap();
+    assert!(res.messages.is_empty());
+    env.block.time = env.block.time.plus_seconds(100);
+    assert_eq!(participant(&deps, &env, "alice").weight, Uint128::zero());
+    exec(&mut deps, &env, "alice", ExecuteMsg::UpdateWeight {}).unwrap();
+
+    let res = exec(&mut deps, &env, "alice", ExecuteMsg::Withdraw {}).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send { to_address: "alice".to_string(), amount: coins(214, REWARD_DENOM) })]
+    );
+    assert_eq!(participant(&deps, &env, "alice").pending_rewards[0].amount, Uint128::zero());
+
+    // alice alone has weight in the second epoch
+    env.block.time = env.block.time.plus_seconds(100);
+    assert_eq!(participant(&deps, &env, "alice").pending_rewards[0].amount, Uint128::new(500));
+    let res = exec(&mut deps, &env, "bob", ExecuteMsg::Withdraw {}).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send { to_address: "bob".to_string(), amount: coins(285, REWARD_DENOM) })]
+    );
+}
+
+#[test]
+fn empty_epoch_is_reclaimed() {
+    let (mut deps, mut env) = setup();
+    exec(&mut deps, &env, "alice", ExecuteMsg::Register {}).unwrap();
+    exec(&mut deps, &env, "alice", ExecuteMsg::SubmitCyberlinks { particles: vec![particle("first")] }).unwrap();
+    env.block.time = env.block.time.plus_seconds(100);
+
+    let err = exec(&mut deps, &env, DISTRIBUTION_ACCOUNT, ExecuteMsg::ReclaimEpoch { epoch: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::EpochNotFinished { epoch: 1 });
+    env.block.time = env.block.time.plus_seconds(100);
+    let err = exec(&mut deps, &env, "alice", ExecuteMsg::ReclaimEpoch { epoch: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = exec(&mut deps, &env, DISTRIBUTION_ACCOUNT, ExecuteMsg::ReclaimEpoch { epoch: 0 }).unwrap_err();
+    assert_eq!(err, ContractError::CannotReclaimEpoch { epoch: 0 });
+
+    let res = exec(&mut deps, &env, DISTRIBUTION_ACCOUNT, ExecuteMsg::ReclaimEpoch { epoch: 1 }).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send { to_address: DISTRIBUTION_ACCOUNT.to_string(), amount: coins(500, REWARD_DENOM) })]
+    );
+    assert!(epoch(&deps, &env, Some(1)).reclaimed);
+    let err = exec(&mut deps, &env, DISTRIBUTION_ACCOUNT, ExecuteMsg::ReclaimEpoch { epoch: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::CannotReclaimEpoch { epoch: 1 });
+}
+
+#[test]
+fn distribution_periods_are_added_by_distribution_account() {
+    let (mut deps, env) = setup();
+    let time = env.block.time.seconds();
+    let msg = ExecuteMsg::AddDistributionPeriods {
+        reward_asset: AssetInfo::Native { denom: REWARD_DENOM.to_string() },
+        periods: vec![(time + 200, time + 300, Uint128::new(300))],
+    };
+    let add = |deps: &mut CyberDeps, sender: &str, amount: u128| {
+        let deps_mut =
+            DepsMut { storage: &mut deps.storage, api: &deps.api, querier: QuerierWrapper::new(&deps.querier) };
+        execute(deps_mut, env.clone(), mock_info(sender, &coins(amount, REWARD_DENOM)), msg.clone())
+    };
+
+    assert_eq!(add(&mut deps, "alice", 300).unwrap_err(), ContractError::Unauthorized {});
+    assert_eq!(
+        add(&mut deps, DISTRIBUTION_ACCOUNT, 200).unwrap_err(),
+        ContractError::Farm(FarmContractError::InvalidRewardFunds {
+            expected: Uint128::new(300),
+            received: Uint128::new(200),
+        })
+    );
+    add(&mut deps, DISTRIBUTION_ACCOUNT, 300).unwrap();
+    assert_eq!(epoch(&deps, &env, Some(2)).rewards[0].amount, Uint128::new(300));
+    assert_eq!(
+        add(&mut deps, DISTRIBUTION_ACCOUNT, 300).unwrap_err(),
+        ContractError::Farm(FarmContractError::OverlappingDistributionPeriod { start: time + 200, end: time + 300 })
+    );
+}
@@ -0,0 +1,110 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
+use farm::asset::AssetInfo;
+use farm::state::RewardAsset;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub distribution_account: String,
+    /// Particle the participants cyberlink their particles to
+    pub particle: String,
+    pub rewards: Vec<RewardAsset>,
+    /// Epochs of the duration in seconds start at the instantiation
+    pub epoch_duration: u64,
+    /// Weight of every verified cyberlink
+    pub link_weight: Uint128,
+    /// Weight added by every unit of the rank of the linked particle
+    pub rank_multiplier: Decimal,
+    /// Cyberlinks counted per participant, 30 by default
+    pub max_links: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    Register {},
+    /// Submit particles cyberlinked by the sender to the campaign particle,
+    /// the weight of the sender in the current epoch is updated
+    SubmitCyberlinks {
+        particles: Vec<String>,
+    },
+    /// Update the weight of the sender in the current epoch with the current ranks
+    UpdateWeight {},
+    /// Withdraw rewards of the finished epochs the sender has weight in
+    Withdraw {},
+    Receive(Cw20ReceiveMsg),
+    /// Add a list of distribution periods of the native reward asset funded by attached funds,
+    /// sent by the distribution account
+    AddDistributionPeriods {
+        reward_asset: AssetInfo,
+        periods: Vec<(u64, u64, Uint128)>,
+    },
+    /// Refund rewards of the finished epoch without weight to the distribution account
+    ReclaimEpoch {
+        epoch: u64,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Add a list of distribution periods of the cw20 reward asset funded by the sent amount,
+    /// sent by the reward token on behalf of the distribution account
+    AddDistributionPeriods {
+        periods: Vec<(u64, u64, Uint128)>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Config {},
+    /// Cyberlinks, current weight and rewards of the finished epochs of the participant
+    Participant {
+        address: String,
+    },
+    /// Total weight and rewards of the epoch, current epoch by default
+    Epoch {
+        epoch: Option<u64>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub distribution_account: String,
+    pub particle: String,
+    pub rewards: Vec<RewardAsset>,
+    pub start_time: u64,
+    pub epoch_duration: u64,
+    pub link_weight: Uint128,
+    pub rank_multiplier: Decimal,
+    pub max_links: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ParticipantResponse {
+    pub address: String,
+    pub links: Vec<String>,
+    /// Weight in the current epoch
+    pub weight: Uint128,
+    pub pending_rewards: Vec<RewardResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EpochResponse {
+    pub epoch: u64,
+    pub start: u64,
+    pub end: u64,
+    pub total_weight: Uint128,
+    pub reclaimed: bool,
+    pub rewards: Vec<RewardResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardResponse {
+    pub asset: AssetInfo,
+    pub amount: Uint128,
+}
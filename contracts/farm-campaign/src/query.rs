@@ -0,0 +1,61 @@
+use cosmwasm_std::{Deps, StdResult};
+
+use crate::{
+    execute::{epoch_reward, pending_rewards},
+    msg::{ConfigResponse, EpochResponse, ParticipantResponse, RewardResponse},
+    state::{CONFIG, EPOCHS, PARTICIPANTS, PARTICIPANT_WEIGHTS},
+};
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        distribution_account: config.distribution_account.to_string(),
+        particle: config.particle,
+        rewards: config.rewards,
+        start_time: config.start_time,
+        epoch_duration: config.epoch_duration,
+        link_weight: config.link_weight,
+        rank_multiplier: config.rank_multiplier,
+        max_links: config.max_links,
+    })
+}
+
+pub fn query_participant(deps: Deps, address: String, time: u64) -> StdResult<ParticipantResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let address = deps.api.addr_validate(&address)?;
+    let participant = PARTICIPANTS.load(deps.storage, &address)?;
+    let weight = PARTICIPANT_WEIGHTS
+        .may_load(deps.storage, (&address, config.epoch(time)))?
+        .unwrap_or_default();
+    let (_, amounts) = pending_rewards(deps.storage, &config, &address, time)?;
+
+    Ok(ParticipantResponse {
+        address: address.to_string(),
+        links: participant.links,
+        weight,
+        pending_rewards: config
+            .rewards
+            .iter()
+            .zip(amounts)
+            .map(|(reward, amount)| RewardResponse { asset: reward.asset.clone(), amount })
+            .collect(),
+    })
+}
+
+pub fn query_epoch(deps: Deps, epoch: u64) -> StdResult<EpochResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let epoch_info = EPOCHS.may_load(deps.storage, epoch)?.unwrap_or_default();
+
+    Ok(EpochResponse {
+        epoch,
+        start: config.epoch_start(epoch),
+        end: config.epoch_start(epoch + 1),
+        total_weight: epoch_info.total_weight,
+        reclaimed: epoch_info.reclaimed,
+        rewards: config
+            .rewards
+            .iter()
+            .map(|reward| RewardResponse { asset: reward.asset.clone(), amount: epoch_reward(&config, reward, epoch) })
+            .collect(),
+    })
+}
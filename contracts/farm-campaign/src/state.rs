@@ -0,0 +1,58 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw_storage_plus::{Item, Map};
+use farm::state::RewardAsset;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub distribution_account: Addr,
+    /// Particle the participants cyberlink their particles to
+    pub particle: String,
+    pub rewards: Vec<RewardAsset>,
+    /// Start of the first epoch, unix time in seconds
+    pub start_time: u64,
+    pub epoch_duration: u64,
+    /// Weight of every verified cyberlink
+    pub link_weight: Uint128,
+    /// Weight added by every unit of the rank of the linked particle
+    pub rank_multiplier: Decimal,
+    /// Cyberlinks counted per participant
+    pub max_links: u32,
+}
+
+impl Config {
+    // epoch of the time, the first epoch is zero
+    pub fn epoch(&self, time: u64) -> u64 {
+        time.saturating_sub(self.start_time) / self.epoch_duration
+    }
+
+    // start time of the epoch, end of the previous one
+    pub fn epoch_start(&self, epoch: u64) -> u64 {
+        self.start_time + epoch * self.epoch_duration
+    }
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// Particles cyberlinked by the participant to the campaign particle
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Participant {
+    pub links: Vec<String>,
+}
+
+pub const PARTICIPANTS: Map<&Addr, Participant> = Map::new("participants");
+
+/// Rewards of an epoch are shared by the weights participants updated during the epoch,
+/// rewards of a finished epoch without weight are reclaimed by the distribution account
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct Epoch {
+    pub total_weight: Uint128,
+    pub reclaimed: bool,
+}
+
+pub const EPOCHS: Map<u64, Epoch> = Map::new("epochs");
+
+/// Weights of the participant in the epochs not withdrawn yet
+pub const PARTICIPANT_WEIGHTS: Map<(&Addr, u64), Uint128> = Map::new("participant_weights");
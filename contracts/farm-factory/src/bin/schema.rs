@@ -2,10 +2,7 @@ use std::env::current_dir;
 use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
-use farm_nv_20::msg::{
-    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, StakerInfoResponse,
-    StateResponse,
-};
+use farm_factory::msg::{ConfigResponse, ExecuteMsg, FarmResponse, FarmsResponse, InstantiateMsg, QueryMsg};
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -15,9 +12,8 @@ fn main() {
 
     export_schema(&schema_for!(InstantiateMsg), &out_dir);
     export_schema(&schema_for!(ExecuteMsg), &out_dir);
-    export_schema(&schema_for!(Cw20HookMsg), &out_dir);
     export_schema(&schema_for!(QueryMsg), &out_dir);
     export_schema(&schema_for!(ConfigResponse), &out_dir);
-    export_schema(&schema_for!(StakerInfoResponse), &out_dir);
-    export_schema(&schema_for!(StateResponse), &out_dir);
+    export_schema(&schema_for!(FarmResponse), &out_dir);
+    export_schema(&schema_for!(FarmsResponse), &out_dir);
 }
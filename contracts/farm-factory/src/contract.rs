@@ -0,0 +1,156 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+
+use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Reply, Response, StdError, StdResult};
+use cw2::set_contract_version;
+use cw_utils::parse_reply_instantiate_data;
+use farm::msg::InstantiateMsg as FarmInstantiateMsg;
+
+use crate::{
+    error::ContractError,
+    execute::{execute_create_farm, execute_remove_farm, execute_sync_farm, execute_update_config, register_farm},
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    query::{query_config, query_farm, query_farms, query_farms_by_reward_asset, query_farms_by_staking_asset},
+    state::{Config, CONFIG},
+};
+
+const CONTRACT_NAME: &str = "crates.io:farm-factory";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+pub const MSG_REPLY_ID_FARM_INSTANT: u64 = 1;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            owner: owner.clone(),
+            farm_code_id: msg.farm_code_id,
+            token_code_id: msg.token_code_id,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "instantiate")
+        .add_attribute("owner", owner))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::CreateFarm {
+            admin,
+            distribution_account,
+            staking_asset,
+            pool_id,
+            rewards,
+            lockup_tiers,
+            unbonding_period,
+            auto_compound,
+            pool_name,
+            treasury,
+            withdraw_fee,
+            unbond_fee,
+            unbond_fee_decay,
+        } => execute_create_farm(
+            deps,
+            env,
+            info,
+            FarmInstantiateMsg {
+                admin,
+                distribution_account,
+                staking_asset,
+                pool_id,
+                rewards,
+                lockup_tiers,
+                unbonding_period,
+                auto_compound,
+                migrate_from: None,
+                token_code_id: 0,
+                pool_name,
+                treasury,
+                withdraw_fee,
+                unbond_fee,
+                unbond_fee_decay,
+            },
+        ),
+        ExecuteMsg::RemoveFarm { address } => execute_remove_farm(deps, env, info, address),
+        ExecuteMsg::SyncFarm { address } => execute_sync_farm(deps, env, info, address),
+        ExecuteMsg::UpdateConfig {
+            owner,
+            farm_code_id,
+            token_code_id,
+        } => execute_update_config(deps, env, info, owner, farm_code_id, token_code_id),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(
+    deps: DepsMut,
+    _env: Env,
+    msg: Reply,
+) -> Result<Response, ContractError> {
+    match msg.id {
+        MSG_REPLY_ID_FARM_INSTANT => {
+            let res = parse_reply_instantiate_data(msg)
+                .map_err(|err| StdError::generic_err(err.to_string()))?;
+            let address = deps.api.addr_validate(&res.contract_address)?;
+            register_farm(deps.storage, &address)?;
+
+            Ok(Response::new()
+                .add_attribute("reply", "farm_instant")
+                .add_attribute("farm_address", address))
+        }
+        _ => Err(ContractError::InvalidReplyId {}),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(
+    deps: Deps,
+    _env: Env,
+    msg: QueryMsg,
+) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Farm { address } => to_binary(&query_farm(deps, address)?),
+        QueryMsg::Farms { start_after, limit } => to_binary(&query_farms(deps, start_after, limit)?),
+        QueryMsg::FarmsByStakingAsset {
+            asset,
+            start_after,
+            limit,
+        } => to_binary(&query_farms_by_staking_asset(deps, asset, start_after, limit)?),
+        QueryMsg::FarmsByRewardAsset {
+            asset,
+            start_after,
+            limit,
+        } => to_binary(&query_farms_by_reward_asset(deps, asset, start_after, limit)?),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    deps: DepsMut,
+    _env: Env,
+    _msg: Empty,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::default())
+}
@@ -0,0 +1,147 @@
+use cosmwasm_std::{to_binary, Addr, DepsMut, Empty, Env, MessageInfo, Response, StdResult, Storage, SubMsg, WasmMsg};
+use farm::asset::AssetInfo;
+use farm::msg::{ConfigResponse as FarmConfigResponse, InstantiateMsg as FarmInstantiateMsg, QueryMsg as FarmQueryMsg};
+
+use crate::{
+    contract::MSG_REPLY_ID_FARM_INSTANT,
+    error::ContractError,
+    state::{asset_key, FarmInfo, CONFIG, FARMS, FARMS_BY_REWARD_ASSET, FARMS_BY_STAKING_ASSET, PENDING_FARM},
+};
+
+// instantiate the farm with the token code id of the factory, the farm is registered on the reply
+pub fn execute_create_farm(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    mut msg: FarmInstantiateMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    msg.token_code_id = config.token_code_id;
+    PENDING_FARM.save(
+        deps.storage,
+        &FarmInfo {
+            staking_asset: msg.staking_asset.clone(),
+            reward_assets: msg.rewards.iter().map(|reward| reward.asset.clone()).collect(),
+            pool_name: msg.pool_name.clone(),
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_submessage(SubMsg::reply_on_success(
+            WasmMsg::Instantiate {
+                admin: Some(config.owner.to_string()),
+                code_id: config.farm_code_id,
+                label: format!("CyberFarm - {}", msg.pool_name),
+                msg: to_binary(&msg)?,
+                funds: info.funds,
+            },
+            MSG_REPLY_ID_FARM_INSTANT,
+        ))
+        .add_attributes(vec![
+            ("action", "create_farm"),
+            ("pool_name", msg.pool_name.as_str()),
+        ]))
+}
+
+pub fn register_farm(storage: &mut dyn Storage, address: &Addr) -> StdResult<()> {
+    let farm = PENDING_FARM.load(storage)?;
+    PENDING_FARM.remove(storage);
+
+    FARMS_BY_STAKING_ASSET.save(storage, (&asset_key(&farm.staking_asset), address), &Empty {})?;
+    for asset in farm.reward_assets.iter() {
+        FARMS_BY_REWARD_ASSET.save(storage, (&asset_key(asset), address), &Empty {})?;
+    }
+    FARMS.save(storage, address, &farm)
+}
+
+pub fn execute_remove_farm(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let address = deps.api.addr_validate(&address)?;
+    let farm = FARMS
+        .may_load(deps.storage, &address)?
+        .ok_or_else(|| ContractError::FarmNotFound { address: address.to_string() })?;
+
+    FARMS_BY_STAKING_ASSET.remove(deps.storage, (&asset_key(&farm.staking_asset), &address));
+    for asset in farm.reward_assets.iter() {
+        FARMS_BY_REWARD_ASSET.remove(deps.storage, (&asset_key(asset), &address));
+    }
+    FARMS.remove(deps.storage, &address);
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "remove_farm"),
+        ("farm_address", address.as_str()),
+    ]))
+}
+
+// register the reward assets of the farm config, reward assets are only added to the farm
+pub fn execute_sync_farm(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let address = deps.api.addr_validate(&address)?;
+    let mut farm = FARMS
+        .may_load(deps.storage, &address)?
+        .ok_or_else(|| ContractError::FarmNotFound { address: address.to_string() })?;
+
+    let config: FarmConfigResponse = deps
+        .querier
+        .query_wasm_smart(&address, &FarmQueryMsg::Config {})?;
+    let added: Vec<AssetInfo> = config
+        .rewards
+        .into_iter()
+        .map(|reward| reward.asset)
+        .filter(|asset| !farm.reward_assets.contains(asset))
+        .collect();
+    for asset in added.iter() {
+        FARMS_BY_REWARD_ASSET.save(deps.storage, (&asset_key(asset), &address), &Empty {})?;
+    }
+    farm.reward_assets.extend(added);
+    FARMS.save(deps.storage, &address, &farm)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "sync_farm"),
+        ("farm_address", address.as_str()),
+    ]))
+}
+
+pub fn execute_update_config(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    owner: Option<String>,
+    farm_code_id: Option<u64>,
+    token_code_id: Option<u64>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_validate(&owner)?;
+    }
+    if let Some(farm_code_id) = farm_code_id {
+        config.farm_code_id = farm_code_id;
+    }
+    if let Some(token_code_id) = token_code_id {
+        config.token_code_id = token_code_id;
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
@@ -0,0 +1,379 @@
+#![cfg(test)]
+
+use cosmwasm_std::{coins, Addr, Binary, Deps, Empty, Env, StdError, StdResult, Uint128};
+use cw_multi_test::{Contract, ContractWrapper, Executor};
+use cyber_std::CyberMsgWrapper;
+use cyber_std_test::CyberApp;
+
+use farm::asset::AssetInfo;
+use farm::msg::{ConfigResponse as FarmConfigResponse, ExecuteMsg as FarmExecuteMsg, QueryMsg as FarmQueryMsg};
+use farm::state::RewardAsset;
+
+use crate::contract::{execute, instantiate, query, reply};
+use crate::error::ContractError;
+use crate::msg::{ConfigResponse, ExecuteMsg, FarmResponse, FarmsResponse, InstantiateMsg, QueryMsg};
+
+const OWNER: &str = "owner";
+const DISTRIBUTION_ACCOUNT: &str = "distribution";
+
+pub fn contract_factory() -> Box<dyn Contract<CyberMsgWrapper, Empty>> {
+    let contract = ContractWrapper::new_with_empty(execute, instantiate, query).with_reply_empty(reply);
+    Box::new(contract)
+}
+
+pub fn contract_farm() -> Box<dyn Contract<CyberMsgWrapper, Empty>> {
+    let contract = ContractWrapper::new(farm::contract::execute, farm::contract::instantiate, farm::contract::query)
+        .with_reply(farm::contract::reply);
+    Box::new(contract)
+}
+
+// farm failing to report its state
+fn query_failing_state(deps: Deps, env: Env, msg: FarmQueryMsg) -> StdResult<Binary> {
+    match msg {
+        FarmQueryMsg::State { .. } => Err(StdError::generic_err("state is not available")),
+        _ => farm::contract::query(deps, env, msg),
+    }
+}
+
+pub fn contract_failing_farm() -> Box<dyn Contract<CyberMsgWrapper, Empty>> {
+    let contract = ContractWrapper::new(farm::contract::execute, farm::contract::instantiate, query_failing_state)
+        .with_reply(farm::contract::reply);
+    Box::new(contract)
+}
+
+pub fn contract_cw20() -> Box<dyn Contract<CyberMsgWrapper, Empty>> {
+    let contract = ContractWrapper::new_with_empty(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn native(denom: &str) -> AssetInfo {
+    AssetInfo::Native { denom: denom.to_string() }
+}
+
+fn setup() -> (CyberApp, Addr) {
+    let mut app = CyberApp::new();
+    app.init_modules(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &Addr::unchecked(OWNER), coins(1_000_000, "boot"))
+            .unwrap();
+        router
+            .bank
+            .init_balance(storage, &Addr::unchecked(DISTRIBUTION_ACCOUNT), coins(1_000_000, "milk"))
+            .unwrap();
+    });
+    let farm_code_id = app.store_code(contract_farm());
+    let token_code_id = app.store_code(contract_cw20());
+    let factory_code_id = app.store_code(contract_factory());
+    let factory = app
+        .instantiate_contract(
+            factory_code_id,
+            Addr::unchecked(OWNER),
+            &InstantiateMsg { owner: None, farm_code_id, token_code_id },
+            &[],
+            "factory",
+            None,
+        )
+        .unwrap();
+    (app, factory)
+}
+
+fn create_farm(app: &mut CyberApp, factory: &Addr, staking: &str, reward: &str, amount: u128) -> Addr {
+    let time = app.block_info().time.seconds();
+    let distribution_schedule = match amount {
+        0 => vec![],
+        _ => vec![(time, time + 100, Uint128::new(amount))],
+    };
+    let funds = match amount {
+        0 => vec![],
+        _ => coins(amount, reward),
+    };
+    let res = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            factory.clone(),
+            &ExecuteMsg::CreateFarm {
+                admin: None,
+                distribution_account: DISTRIBUTION_ACCOUNT.to_string(),
+                staking_asset: native(staking),
+                pool_id: None,
+                rewards: vec![RewardAsset { asset: native(reward), distribution_schedule }],
+                lockup_tiers: None,
+                unbonding_period: None,
+                auto_compound: None,
+                pool_name: staking.to_uppercase(),
+                treasury: None,
+                withdraw_fee: None,
+                unbond_fee: None,
+                unbond_fee_decay: None,
+            },
+            &funds,
+        )
+        .unwrap();
+    let address = res
+        .events
+        .iter()
+        .flat_map(|event| event.attributes.iter())
+        .find(|attr| attr.key == "farm_address")
+        .unwrap()
+        .value
+        .clone();
+    Addr::unchecked(address)
+}
+
+// query of a page of farms
+type PageQuery = fn(Option<String>, Option<u32>) -> QueryMsg;
+
+// addresses of the listed farms
+fn farms(app: &CyberApp, factory: &Addr, msg: QueryMsg) -> Vec<Addr> {
+    let res: FarmsResponse = app.wrap().query_wasm_smart(factory, &msg).unwrap();
+    res.farms.into_iter().map(|farm| Addr::unchecked(farm.address)).collect()
+}
+
+fn sorted(mut farms: Vec<Addr>) -> Vec<Addr> {
+    farms.sort();
+    farms
+}
+
+#[test]
+fn farms_are_created_and_registered() {
+    let (mut app, factory) = setup();
+    let first = create_farm(&mut app, &factory, "pool", "boot", 1000);
+    let second = create_farm(&mut app, &factory, "pool", "milk", 0);
+    let third = create_farm(&mut app, &factory, "lp", "boot", 0);
+
+    // funds are forwarded to the farm and the LP token is instantiated by the farm
+    assert_eq!(app.wrap().query_balance(&first, "boot").unwrap().amount, Uint128::new(1000));
+    let config: FarmConfigResponse = app.wrap().query_wasm_smart(&first, &FarmQueryMsg::Config {}).unwrap();
+    assert_eq!(config.distribution_account, DISTRIBUTION_ACCOUNT);
+    assert!(!config.lp_token.is_empty());
+
+    let farm: FarmResponse = app
+        .wrap()
+        .query_wasm_smart(&factory, &QueryMsg::Farm { address: first.to_string() })
+        .unwrap();
+    assert_eq!(farm.pool_name, "POOL");
+    assert_eq!(farm.reward_assets, vec![native("boot")]);
+    assert_eq!(farm.state.unwrap().total_bond_amount, Uint128::zero());
+
+    assert_eq!(
+        farms(&app, &factory, QueryMsg::Farms { start_after: None, limit: None }),
+        sorted(vec![first.clone(), second.clone(), third.clone()])
+    );
+    assert_eq!(
+        farms(&app, &factory, QueryMsg::FarmsByStakingAsset { asset: native("pool"), start_after: None, limit: None }),
+        sorted(vec![first.clone(), second.clone()])
+    );
+    assert_eq!(
+        farms(&app, &factory, QueryMsg::FarmsByStakingAsset { asset: native("lp"), start_after: None, limit: None }),
+        vec![third.clone()]
+    );
+    assert_eq!(
+        farms(&app, &factory, QueryMsg::FarmsByRewardAsset { asset: native("boot"), start_after: None, limit: None }),
+        sorted(vec![first, third])
+    );
+    assert_eq!(
+        farms(&app, &factory, QueryMsg::FarmsByRewardAsset { asset: native("milk"), start_after: None, limit: None }),
+        vec![second]
+    );
+    assert!(farms(&app, &factory, QueryMsg::FarmsByRewardAsset { asset: native("pool"), start_after: None, limit: None })
+        .is_empty());
+}
+
+#[test]
+fn farms_are_paginated() {
+    let (mut app, factory) = setup();
+    let all = sorted((0..5).map(|_| create_farm(&mut app, &factory, "pool", "boot", 0)).collect());
+    create_farm(&mut app, &factory, "lp", "milk", 0);
+
+    let queries: [PageQuery; 3] = [
+        |start_after, limit| QueryMsg::Farms { start_after, limit },
+        |start_after, limit| QueryMsg::FarmsByStakingAsset { asset: native("pool"), start_after, limit },
+        |start_after, limit| QueryMsg::FarmsByRewardAsset { asset: native("boot"), start_after, limit },
+    ];
+    for query in queries {
+        let mut listed = vec![];
+        let mut start_after = None;
+        loop {
+            let page = farms(&app, &factory, query(start_after, Some(2)));
+            assert!(page.len() <= 2);
+            match page.last() {
+                Some(last) => start_after = Some(last.to_string()),
+                None => break,
+            }
+            listed.extend(page);
+        }
+        let listed: Vec<Addr> = listed.into_iter().filter(|farm| all.contains(farm)).collect();
+        assert_eq!(listed, all);
+    }
+    assert_eq!(
+        farms(&app, &factory, QueryMsg::Farms { start_after: Some(all[0].to_string()), limit: Some(1) }).len(),
+        1
+    );
+    assert_eq!(farms(&app, &factory, QueryMsg::Farms { start_after: None, limit: None }).len(), 6);
+}
+
+#[test]
+fn removed_farm_is_dropped_from_indexes() {
+    let (mut app, factory) = setup();
+    let first = create_farm(&mut app, &factory, "pool", "boot", 0);
+    let second = create_farm(&mut app, &factory, "pool", "boot", 0);
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(DISTRIBUTION_ACCOUNT),
+            factory.clone(),
+            &ExecuteMsg::RemoveFarm { address: first.to_string() },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        factory.clone(),
+        &ExecuteMsg::RemoveFarm { address: first.to_string() },
+        &[],
+    )
+    .unwrap();
+    assert_eq!(farms(&app, &factory, QueryMsg::Farms { start_after: None, limit: None }), vec![second.clone()]);
+    assert_eq!(
+        farms(&app, &factory, QueryMsg::FarmsByStakingAsset { asset: native("pool"), start_after: None, limit: None }),
+        vec![second.clone()]
+    );
+    assert_eq!(
+        farms(&app, &factory, QueryMsg::FarmsByRewardAsset { asset: native("boot"), start_after: None, limit: None }),
+        vec![second]
+    );
+    app.wrap()
+        .query_wasm_smart::<FarmResponse>(&factory, &QueryMsg::Farm { address: first.to_string() })
+        .unwrap_err();
+
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            factory,
+            &ExecuteMsg::RemoveFarm { address: first.to_string() },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::FarmNotFound { address: first.to_string() }, err.downcast().unwrap());
+}
+
+#[test]
+fn config_is_updated_by_owner() {
+    let (mut app, factory) = setup();
+    let update = |app: &mut CyberApp, sender: &str, owner: Option<String>, farm_code_id: Option<u64>| {
+        app.execute_contract(
+            Addr::unchecked(sender),
+            factory.clone(),
+            &ExecuteMsg::UpdateConfig { owner, farm_code_id, token_code_id: None },
+            &[],
+        )
+    };
+
+    let err = update(&mut app, DISTRIBUTION_ACCOUNT, None, Some(100)).unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    // fields not given are kept
+    let before: ConfigResponse = app.wrap().query_wasm_smart(&factory, &QueryMsg::Config {}).unwrap();
+    update(&mut app, OWNER, Some("new_owner".to_string()), None).unwrap();
+    let config: ConfigResponse = app.wrap().query_wasm_smart(&factory, &QueryMsg::Config {}).unwrap();
+    assert_eq!(config, ConfigResponse { owner: "new_owner".to_string(), ..before.clone() });
+
+    // the former owner loses the access
+    let err = update(&mut app, OWNER, None, Some(100)).unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    update(&mut app, "new_owner", None, Some(100)).unwrap();
+    let config: ConfigResponse = app.wrap().query_wasm_smart(&factory, &QueryMsg::Config {}).unwrap();
+    assert_eq!(config, ConfigResponse { owner: "new_owner".to_string(), farm_code_id: 100, ..before });
+}
+
+#[test]
+fn reward_assets_added_later_are_synced() {
+    let (mut app, factory) = setup();
+    let farm = create_farm(&mut app, &factory, "pool", "boot", 0);
+    let time = app.block_info().time.seconds();
+    app.execute_contract(
+        Addr::unchecked(DISTRIBUTION_ACCOUNT),
+        farm.clone(),
+        &FarmExecuteMsg::AddDistributionPeriods {
+            reward_asset: native("milk"),
+            periods: vec![(time, time + 100, Uint128::new(100))],
+        },
+        &coins(100, "milk"),
+    )
+    .unwrap();
+    let by_milk = QueryMsg::FarmsByRewardAsset { asset: native("milk"), start_after: None, limit: None };
+    assert!(farms(&app, &factory, by_milk.clone()).is_empty());
+
+    // anyone syncs the farm, syncing again changes nothing
+    for _ in 0..2 {
+        app.execute_contract(
+            Addr::unchecked(DISTRIBUTION_ACCOUNT),
+            factory.clone(),
+            &ExecuteMsg::SyncFarm { address: farm.to_string() },
+            &[],
+        )
+        .unwrap();
+    }
+    assert_eq!(farms(&app, &factory, by_milk), vec![farm.clone()]);
+    let res: FarmResponse = app
+        .wrap()
+        .query_wasm_smart(&factory, &QueryMsg::Farm { address: farm.to_string() })
+        .unwrap();
+    assert_eq!(res.reward_assets, vec![native("boot"), native("milk")]);
+
+    // synced reward assets are dropped with the farm
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        factory.clone(),
+        &ExecuteMsg::RemoveFarm { address: farm.to_string() },
+        &[],
+    )
+    .unwrap();
+    assert!(farms(&app, &factory, QueryMsg::FarmsByRewardAsset { asset: native("milk"), start_after: None, limit: None })
+        .is_empty());
+    let err = app
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            factory,
+            &ExecuteMsg::SyncFarm { address: farm.to_string() },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::FarmNotFound { address: farm.to_string() }, err.downcast().unwrap());
+}
+
+#[test]
+fn farm_failing_state_query_is_listed_without_state() {
+    let (mut app, factory) = setup();
+    let farm = create_farm(&mut app, &factory, "pool", "boot", 0);
+    let failing_code_id = app.store_code(contract_failing_farm());
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        factory.clone(),
+        &ExecuteMsg::UpdateConfig { owner: None, farm_code_id: Some(failing_code_id), token_code_id: None },
+        &[],
+    )
+    .unwrap();
+    let failing = create_farm(&mut app, &factory, "pool", "boot", 0);
+
+    let res: FarmsResponse = app
+        .wrap()
+        .query_wasm_smart(&factory, &QueryMsg::Farms { start_after: None, limit: None })
+        .unwrap();
+    assert_eq!(res.farms.len(), 2);
+    for listed in res.farms {
+        assert_eq!(listed.state.is_some(), listed.address == farm.as_str());
+    }
+    let res: FarmResponse = app
+        .wrap()
+        .query_wasm_smart(&factory, &QueryMsg::Farm { address: failing.to_string() })
+        .unwrap();
+    assert_eq!(res.state, None);
+}
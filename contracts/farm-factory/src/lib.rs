@@ -4,3 +4,5 @@ pub mod execute;
 pub mod msg;
 pub mod query;
 pub mod error;
+#[cfg(test)]
+mod integration_tests;
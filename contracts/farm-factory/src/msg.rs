@@ -0,0 +1,102 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::Decimal;
+
+use farm::asset::AssetInfo;
+use farm::msg::StateResponse;
+use farm::state::{LockupTier, RewardAsset};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// Account that creates and removes farms, the sender by default
+    pub owner: Option<String>,
+    pub farm_code_id: u64,
+    pub token_code_id: u64,
+}
+
+// messages are deserialized once per call, the size of the variants does not matter
+#[allow(clippy::large_enum_variant)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Owner operation to instantiate the farm with the token code id of the factory,
+    /// attached funds fund the native reward schedules
+    CreateFarm {
+        admin: Option<String>,
+        distribution_account: String,
+        staking_asset: AssetInfo,
+        pool_id: Option<u64>,
+        rewards: Vec<RewardAsset>,
+        lockup_tiers: Option<Vec<LockupTier>>,
+        unbonding_period: Option<u64>,
+        auto_compound: Option<bool>,
+        pool_name: String,
+        treasury: Option<String>,
+        withdraw_fee: Option<Decimal>,
+        unbond_fee: Option<Decimal>,
+        unbond_fee_decay: Option<u64>,
+    },
+    /// Owner operation to remove the farm from the registry
+    RemoveFarm {
+        address: String,
+    },
+    /// Register the reward assets of the farm added after its creation, anyone may sync the farm
+    SyncFarm {
+        address: String,
+    },
+    /// Owner operation to update the config, fields not given are kept
+    UpdateConfig {
+        owner: Option<String>,
+        farm_code_id: Option<u64>,
+        token_code_id: Option<u64>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Config {},
+    Farm {
+        address: String,
+    },
+    /// Farms in the order of their addresses
+    Farms {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Farms staking the asset in the order of their addresses
+    FarmsByStakingAsset {
+        asset: AssetInfo,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Farms rewarding in the asset in the order of their addresses
+    FarmsByRewardAsset {
+        asset: AssetInfo,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: String,
+    pub farm_code_id: u64,
+    pub token_code_id: u64,
+}
+
+/// Registered farm with its current state, none if the farm fails to report it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FarmResponse {
+    pub address: String,
+    pub staking_asset: AssetInfo,
+    pub reward_assets: Vec<AssetInfo>,
+    pub pool_name: String,
+    pub state: Option<StateResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FarmsResponse {
+    pub farms: Vec<FarmResponse>,
+}
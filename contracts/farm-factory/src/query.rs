@@ -0,0 +1,108 @@
+use cosmwasm_std::{Addr, Deps, Empty, Order, StdResult};
+use cw_storage_plus::{Bound, Map};
+
+use farm::asset::AssetInfo;
+use farm::msg::{QueryMsg as FarmQueryMsg, StateResponse};
+
+use crate::{
+    msg::{ConfigResponse, FarmResponse, FarmsResponse},
+    state::{asset_key, FarmInfo, CONFIG, FARMS, FARMS_BY_REWARD_ASSET, FARMS_BY_STAKING_ASSET},
+};
+
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+
+    Ok(ConfigResponse {
+        owner: config.owner.to_string(),
+        farm_code_id: config.farm_code_id,
+        token_code_id: config.token_code_id,
+    })
+}
+
+pub fn query_farm(deps: Deps, address: String) -> StdResult<FarmResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let farm = FARMS.load(deps.storage, &address)?;
+
+    farm_response(deps, address, farm)
+}
+
+pub fn query_farms(deps: Deps, start_after: Option<String>, limit: Option<u32>) -> StdResult<FarmsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after.map(|addr| deps.api.addr_validate(&addr)).transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+    let farms = FARMS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(FarmsResponse {
+        farms: farms
+            .into_iter()
+            .map(|(address, farm)| farm_response(deps, address, farm))
+            .collect::<StdResult<_>>()?,
+    })
+}
+
+pub fn query_farms_by_staking_asset(
+    deps: Deps,
+    asset: AssetInfo,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<FarmsResponse> {
+    query_farms_by_asset(deps, FARMS_BY_STAKING_ASSET, asset, start_after, limit)
+}
+
+pub fn query_farms_by_reward_asset(
+    deps: Deps,
+    asset: AssetInfo,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<FarmsResponse> {
+    query_farms_by_asset(deps, FARMS_BY_REWARD_ASSET, asset, start_after, limit)
+}
+
+fn query_farms_by_asset(
+    deps: Deps,
+    index: Map<(&str, &Addr), Empty>,
+    asset: AssetInfo,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<FarmsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after.map(|addr| deps.api.addr_validate(&addr)).transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+    let addresses = index
+        .prefix(&asset_key(&asset))
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<Addr>>>()?;
+
+    Ok(FarmsResponse {
+        farms: addresses
+            .into_iter()
+            .map(|address| {
+                let farm = FARMS.load(deps.storage, &address)?;
+                farm_response(deps, address, farm)
+            })
+            .collect::<StdResult<_>>()?,
+    })
+}
+
+// registered farm with the state queried from the farm, a failing farm does not fail the listing
+fn farm_response(deps: Deps, address: Addr, farm: FarmInfo) -> StdResult<FarmResponse> {
+    let state: Option<StateResponse> = deps
+        .querier
+        .query_wasm_smart(&address, &FarmQueryMsg::State { time: None })
+        .ok();
+
+    Ok(FarmResponse {
+        address: address.to_string(),
+        staking_asset: farm.staking_asset,
+        reward_assets: farm.reward_assets,
+        pool_name: farm.pool_name,
+        state,
+    })
+}
@@ -0,0 +1,39 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Empty};
+use cw_storage_plus::{Item, Map};
+
+use farm::asset::AssetInfo;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub owner: Addr,
+    pub farm_code_id: u64,
+    pub token_code_id: u64,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// Assets of the farm, reward assets added after the creation are registered by the sync of the farm
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FarmInfo {
+    pub staking_asset: AssetInfo,
+    pub reward_assets: Vec<AssetInfo>,
+    pub pool_name: String,
+}
+
+pub const FARMS: Map<&Addr, FarmInfo> = Map::new("farms");
+pub const FARMS_BY_STAKING_ASSET: Map<(&str, &Addr), Empty> = Map::new("farms_by_staking_asset");
+pub const FARMS_BY_REWARD_ASSET: Map<(&str, &Addr), Empty> = Map::new("farms_by_reward_asset");
+
+/// Farm being instantiated, registered on the reply with its address
+pub const PENDING_FARM: Item<FarmInfo> = Item::new("pending_farm");
+
+// native denoms and cw20 addresses are kept apart in the asset indexes
+pub fn asset_key(asset: &AssetInfo) -> String {
+    match asset {
+        AssetInfo::Native { denom } => format!("native:{}", denom),
+        AssetInfo::Cw20 { address } => format!("cw20:{}", address),
+    }
+}
@@ -1,6 +0,0 @@
-pub mod contract;
-pub mod state;
-pub mod execute;
-pub mod msg;
-pub mod query;
-pub mod error;
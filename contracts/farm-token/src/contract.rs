@@ -0,0 +1,97 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+
+use cosmwasm_std::{to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult, SubMsg, Uint128, WasmMsg};
+use cw2::set_contract_version;
+use cw20_base::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use cw20_base::state::TOKEN_INFO;
+use cw20_base::ContractError;
+use farm::msg::ExecuteMsg as FarmExecuteMsg;
+
+const CONTRACT_NAME: &str = "crates.io:farm-token";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let response = cw20_base::contract::instantiate(deps.branch(), env, info, msg)?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(response)
+}
+
+// cw20 execute, tokens moved between holders move the farm position of the minter with them,
+// the transfer reverts when the tokens are not backed by a position of the sender,
+// tokens are burned only by the farm on unbond and holders cannot burn them
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    let minter = TOKEN_INFO.load(deps.storage)?.mint.map(|mint| mint.minter);
+    if matches!(msg, ExecuteMsg::Burn { .. } | ExecuteMsg::BurnFrom { .. }) && minter.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let transfer = match &msg {
+        ExecuteMsg::Transfer { recipient, amount } | ExecuteMsg::Send { contract: recipient, amount, .. } => {
+            Some((info.sender.to_string(), recipient.clone(), *amount))
+        }
+        ExecuteMsg::TransferFrom { owner, recipient, amount }
+        | ExecuteMsg::SendFrom { owner, contract: recipient, amount, .. } => {
+            Some((owner.clone(), recipient.clone(), *amount))
+        }
+        _ => None,
+    };
+
+    let mut response = cw20_base::contract::execute(deps.branch(), env, info, msg)?;
+
+    if let Some((from, to, amount)) = transfer {
+        if let Some(farm) = minter {
+            // position is moved before the recipient contract handles the received tokens
+            if let Some(msg) = transfer_position_msg(&farm, from, to, amount)? {
+                response.messages.insert(0, msg);
+            }
+        }
+    }
+
+    Ok(response)
+}
+
+// tokens sent to the farm are unbonded by the farm itself
+fn transfer_position_msg(farm: &Addr, from: String, to: String, amount: Uint128) -> StdResult<Option<SubMsg>> {
+    if farm.as_str() == from || farm.as_str() == to {
+        return Ok(None);
+    }
+    Ok(Some(SubMsg::new(WasmMsg::Execute {
+        contract_addr: farm.to_string(),
+        msg: to_binary(&FarmExecuteMsg::TransferPosition { from, to, amount })?,
+        funds: vec![],
+    })))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(
+    deps: Deps,
+    env: Env,
+    msg: QueryMsg,
+) -> StdResult<Binary> {
+    cw20_base::contract::query(deps, env, msg)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    deps: DepsMut,
+    _env: Env,
+    _msg: Empty,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::default())
+}
@@ -0,0 +1,390 @@
+#![cfg(test)]
+
+use cosmwasm_std::{coins, to_binary, Addr, Decimal, Empty, Uint128};
+use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg, MinterResponse};
+use cw20_base::ContractError;
+use cw_multi_test::{AppResponse, Contract, ContractWrapper, Executor};
+use cyber_std::CyberMsgWrapper;
+use cyber_std_test::CyberApp;
+
+use farm::asset::AssetInfo;
+use farm::error::ContractError as FarmContractError;
+use farm::msg::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg as FarmExecuteMsg, InstantiateMsg as FarmInstantiateMsg,
+    QueryMsg as FarmQueryMsg, StakerInfoResponse,
+};
+use farm::state::{LockupTier, RewardAsset};
+
+use crate::contract::{execute, instantiate, query};
+
+const DISTRIBUTION_ACCOUNT: &str = "distribution";
+const STAKING_DENOM: &str = "pool";
+const REWARD_DENOM: &str = "boot";
+
+pub fn contract_farm() -> Box<dyn Contract<CyberMsgWrapper, Empty>> {
+    let contract = ContractWrapper::new(farm::contract::execute, farm::contract::instantiate, farm::contract::query)
+        .with_reply(farm::contract::reply);
+    Box::new(contract)
+}
+
+pub fn contract_farm_token() -> Box<dyn Contract<CyberMsgWrapper, Empty>> {
+    let contract = ContractWrapper::new_with_empty(execute, instantiate, query);
+    Box::new(contract)
+}
+
+struct Setup {
+    app: CyberApp,
+    farm_code_id: u64,
+    farm: Addr,
+    lp_token: Addr,
+}
+
+impl Setup {
+    fn new() -> Self {
+        let stakers = [Addr::unchecked("staker0"), Addr::unchecked("staker1")];
+        let mut app = CyberApp::new();
+        app.init_modules(|router, _, storage| {
+            router
+                .bank
+                .init_balance(storage, &Addr::unchecked(DISTRIBUTION_ACCOUNT), coins(1_000_000, REWARD_DENOM))
+                .unwrap();
+            for staker in stakers.iter() {
+                router.bank.init_balance(storage, staker, coins(1_000_000, STAKING_DENOM)).unwrap();
+            }
+        });
+        let farm_code_id = app.store_code(contract_farm());
+        let token_code_id = app.store_code(contract_farm_token());
+        let time = app.block_info().time.seconds();
+        let farm = app
+            .instantiate_contract(
+                farm_code_id,
+                Addr::unchecked(DISTRIBUTION_ACCOUNT),
+                &FarmInstantiateMsg {
+                    admin: None,
+                    distribution_account: DISTRIBUTION_ACCOUNT.to_string(),
+                    staking_asset: AssetInfo::Native { denom: STAKING_DENOM.to_string() },
+                    pool_id: None,
+                    rewards: vec![RewardAsset {
+                        asset: AssetInfo::Native { denom: REWARD_DENOM.to_string() },
+                        distribution_schedule: vec![(time, time + 100, Uint128::new(1000))],
+                    }],
+                    lockup_tiers: Some(vec![
+                        LockupTier { duration: 0, multiplier: Decimal::one() },
+                        LockupTier { duration: 100, multiplier: Decimal::percent(200) },
+                    ]),
+                    unbonding_period: None,
+                    auto_compound: None,
+                    migrate_from: None,
+                    token_code_id,
+                    pool_name: "POOL".to_string(),
+                    treasury: None,
+                    withdraw_fee: None,
+                    unbond_fee: None,
+                    unbond_fee_decay: None,
+                },
+                &coins(1000, REWARD_DENOM),
+                "farm",
+                None,
+            )
+            .unwrap();
+        let config: ConfigResponse = app.wrap().query_wasm_smart(&farm, &FarmQueryMsg::Config {}).unwrap();
+        let lp_token = Addr::unchecked(config.lp_token);
+        Setup { app, farm_code_id, farm, lp_token }
+    }
+
+    // migrate the farm to a new farm taking over the LP token, the old farm is returned
+    fn migrate(&mut self) -> Addr {
+        let new_farm = self
+            .app
+            .instantiate_contract(
+                self.farm_code_id,
+                Addr::unchecked(DISTRIBUTION_ACCOUNT),
+                &FarmInstantiateMsg {
+                    admin: None,
+                    distribution_account: DISTRIBUTION_ACCOUNT.to_string(),
+                    staking_asset: AssetInfo::Native { denom: STAKING_DENOM.to_string() },
+                    pool_id: None,
+                    rewards: vec![],
+                    lockup_tiers: None,
+                    unbonding_period: None,
+                    auto_compound: None,
+                    migrate_from: Some(self.farm.to_string()),
+                    token_code_id: 0,
+                    pool_name: "POOL".to_string(),
+                    treasury: None,
+                    withdraw_fee: None,
+                    unbond_fee: None,
+                    unbond_fee_decay: None,
+                },
+                &[],
+                "farm",
+                None,
+            )
+            .unwrap();
+        self.app
+            .execute_contract(
+                Addr::unchecked(DISTRIBUTION_ACCOUNT),
+                self.farm.clone(),
+                &FarmExecuteMsg::MigrateStaking { new_staking_contract: new_farm.to_string() },
+                &[],
+            )
+            .unwrap();
+        std::mem::replace(&mut self.farm, new_farm)
+    }
+
+    fn bond(&mut self, staker: &str, amount: u128, lock_duration: Option<u64>) {
+        self.app
+            .execute_contract(
+                Addr::unchecked(staker),
+                self.farm.clone(),
+                &FarmExecuteMsg::Bond { lock_duration, recipient: None },
+                &coins(amount, STAKING_DENOM),
+            )
+            .unwrap();
+    }
+
+    fn transfer(&mut self, from: &str, to: &str, amount: u128) -> anyhow::Result<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(from),
+            self.lp_token.clone(),
+            &Cw20ExecuteMsg::Transfer { recipient: to.to_string(), amount: Uint128::new(amount) },
+            &[],
+        )
+    }
+
+    fn unbond(&mut self, staker: &str, amount: u128) -> anyhow::Result<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(staker),
+            self.lp_token.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: self.farm.to_string(),
+                amount: Uint128::new(amount),
+                msg: to_binary(&Cw20HookMsg::Unbond { lock_id: None }).unwrap(),
+            },
+            &[],
+        )
+    }
+
+    fn staker_info(&self, staker: &str) -> StakerInfoResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                &self.farm,
+                &FarmQueryMsg::StakerInfo {
+                    staker: staker.to_string(),
+                    time: Some(self.app.block_info().time.seconds()),
+                },
+            )
+            .unwrap()
+    }
+
+    fn lp_balance(&self, address: &str) -> Uint128 {
+        let res: BalanceResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(&self.lp_token, &Cw20QueryMsg::Balance { address: address.to_string() })
+            .unwrap();
+        res.balance
+    }
+}
+
+#[test]
+fn position_follows_transferred_tokens() {
+    let mut setup = Setup::new();
+    setup.bond("staker0", 150, None);
+    setup.bond("staker0", 50, Some(100));
+    setup.app.update_block(|block| block.time = block.time.plus_seconds(50));
+
+    // unlocked bond is moved first, then the part of the lock keeping its unlock time
+    setup.transfer("staker0", "staker1", 175).unwrap();
+    assert_eq!(setup.lp_balance("staker1"), Uint128::new(175));
+    let sender = setup.staker_info("staker0");
+    assert_eq!(sender.bond_amount, Uint128::new(25));
+    assert_eq!(sender.rewards[0].pending_reward, Uint128::new(500));
+    let recipient = setup.staker_info("staker1");
+    assert_eq!(recipient.bond_amount, Uint128::new(175));
+    assert_eq!(recipient.rewards[0].pending_reward, Uint128::zero());
+    assert_eq!(recipient.locks.len(), 2);
+
+    // rewards follow the weight of the moved bond, 50 for the sender and 200 for the recipient
+    setup.app.update_block(|block| block.time = block.time.plus_seconds(50));
+    assert_eq!(setup.staker_info("staker0").rewards[0].pending_reward, Uint128::new(600));
+    assert_eq!(setup.staker_info("staker1").rewards[0].pending_reward, Uint128::new(400));
+
+    // holder of transferred tokens unbonds them
+    setup.unbond("staker1", 175).unwrap();
+    assert_eq!(setup.staker_info("staker1").bond_amount, Uint128::zero());
+    assert_eq!(setup.lp_balance("staker1"), Uint128::zero());
+    assert_eq!(
+        setup.app.wrap().query_balance("staker1", STAKING_DENOM).unwrap().amount,
+        Uint128::new(1_000_175),
+    );
+}
+
+#[test]
+fn locked_position_is_moved_with_its_unlock_time() {
+    let mut setup = Setup::new();
+    setup.bond("staker0", 100, Some(100));
+    setup.transfer("staker0", "staker1", 40).unwrap();
+
+    let err = setup.unbond("staker1", 40).unwrap_err();
+    assert_eq!(FarmContractError::LockedBond {}, err.downcast().unwrap());
+    assert_eq!(setup.staker_info("staker1").weight, Uint128::new(80));
+
+    // boost of the moved lock ends at its unlock time
+    setup.app.update_block(|block| block.time = block.time.plus_seconds(100));
+    assert_eq!(setup.staker_info("staker1").weight, Uint128::new(40));
+    setup.transfer("staker0", "staker1", 10).unwrap();
+    assert_eq!(setup.staker_info("staker1").weight, Uint128::new(50));
+    setup.unbond("staker1", 50).unwrap();
+    assert_eq!(setup.staker_info("staker0").bond_amount, Uint128::new(50));
+    assert_eq!(setup.staker_info("staker0").weight, Uint128::new(50));
+}
+
+#[test]
+fn only_lp_token_transfers_position() {
+    let mut setup = Setup::new();
+    setup.bond("staker0", 100, None);
+
+    let err = setup
+        .app
+        .execute_contract(
+            Addr::unchecked("staker0"),
+            setup.farm.clone(),
+            &FarmExecuteMsg::TransferPosition {
+                from: "staker0".to_string(),
+                to: "staker1".to_string(),
+                amount: Uint128::new(100),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(FarmContractError::Unauthorized {}, err.downcast().unwrap());
+}
+
+#[test]
+fn position_is_transferred_while_paused() {
+    let mut setup = Setup::new();
+    setup.bond("staker0", 100, None);
+    setup
+        .app
+        .execute_contract(Addr::unchecked(DISTRIBUTION_ACCOUNT), setup.farm.clone(), &FarmExecuteMsg::Pause {}, &[])
+        .unwrap();
+
+    setup.transfer("staker0", "staker1", 40).unwrap();
+    assert_eq!(setup.staker_info("staker0").bond_amount, Uint128::new(60));
+    assert_eq!(setup.staker_info("staker1").bond_amount, Uint128::new(40));
+}
+
+#[test]
+fn only_farm_burns_tokens() {
+    let mut setup = Setup::new();
+    setup.bond("staker0", 100, None);
+
+    let err = setup
+        .app
+        .execute_contract(
+            Addr::unchecked("staker0"),
+            setup.lp_token.clone(),
+            &Cw20ExecuteMsg::Burn { amount: Uint128::new(40) },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    setup
+        .app
+        .execute_contract(
+            Addr::unchecked("staker0"),
+            setup.lp_token.clone(),
+            &Cw20ExecuteMsg::IncreaseAllowance { spender: "staker1".to_string(), amount: Uint128::new(40), expires: None },
+            &[],
+        )
+        .unwrap();
+    let err = setup
+        .app
+        .execute_contract(
+            Addr::unchecked("staker1"),
+            setup.lp_token.clone(),
+            &Cw20ExecuteMsg::BurnFrom { owner: "staker0".to_string(), amount: Uint128::new(40) },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    assert_eq!(setup.lp_balance("staker0"), Uint128::new(100));
+
+    // tokens sent to the farm are burned on unbond
+    setup.unbond("staker0", 100).unwrap();
+    assert_eq!(setup.lp_balance("staker0"), Uint128::zero());
+    assert_eq!(setup.staker_info("staker0").bond_amount, Uint128::zero());
+}
+
+#[test]
+fn unbacked_tokens_are_not_transferred() {
+    let mut setup = Setup::new();
+
+    // tokens minted without a bond are not backed by a position and are not transferred
+    let token_code_id = setup.app.store_code(contract_farm_token());
+    let unbacked = setup
+        .app
+        .instantiate_contract(
+            token_code_id,
+            Addr::unchecked(DISTRIBUTION_ACCOUNT),
+            &cw20_base::msg::InstantiateMsg {
+                name: "unbacked".to_string(),
+                symbol: "UNBACKED".to_string(),
+                decimals: 6,
+                initial_balances: vec![Cw20Coin { address: "staker1".to_string(), amount: Uint128::new(100) }],
+                mint: Some(MinterResponse { minter: setup.farm.to_string(), cap: None }),
+                marketing: None,
+            },
+            &[],
+            "unbacked",
+            None,
+        )
+        .unwrap();
+    let err = setup
+        .app
+        .execute_contract(
+            Addr::unchecked("staker1"),
+            unbacked.clone(),
+            &Cw20ExecuteMsg::Transfer { recipient: "staker0".to_string(), amount: Uint128::new(100) },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(FarmContractError::Unauthorized {}, err.downcast().unwrap());
+    let balance: BalanceResponse = setup
+        .app
+        .wrap()
+        .query_wasm_smart(&unbacked, &Cw20QueryMsg::Balance { address: "staker1".to_string() })
+        .unwrap();
+    assert_eq!(balance.balance, Uint128::new(100));
+}
+
+#[test]
+fn position_is_pulled_on_transfer_after_migration() {
+    let mut setup = Setup::new();
+    setup.bond("staker0", 100, Some(100));
+    setup.bond("staker1", 50, None);
+    let old_farm = setup.migrate();
+
+    // position of the sender is pulled from the old farm before it is moved
+    setup.transfer("staker0", "staker1", 40).unwrap();
+    let sender = setup.staker_info("staker0");
+    assert_eq!(sender.bond_amount, Uint128::new(60));
+    assert_eq!(sender.locks[0].unlock_time, setup.app.block_info().time.seconds() + 100);
+    assert_eq!(setup.staker_info("staker1").bond_amount, Uint128::new(40));
+    let old_info: StakerInfoResponse = setup
+        .app
+        .wrap()
+        .query_wasm_smart(&old_farm, &FarmQueryMsg::StakerInfo { staker: "staker0".to_string(), time: None })
+        .unwrap();
+    assert_eq!(old_info.bond_amount, Uint128::zero());
+    assert_eq!(setup.app.wrap().query_balance(&setup.farm, STAKING_DENOM).unwrap().amount, Uint128::new(100));
+
+    // pulled position is moved without a pull, the position of the recipient is pulled on its transfer
+    setup.transfer("staker0", "staker1", 10).unwrap();
+    setup.transfer("staker1", "staker0", 70).unwrap();
+    assert_eq!(setup.staker_info("staker0").bond_amount, Uint128::new(120));
+    assert_eq!(setup.staker_info("staker1").bond_amount, Uint128::new(30));
+    assert_eq!(setup.app.wrap().query_balance(&setup.farm, STAKING_DENOM).unwrap().amount, Uint128::new(150));
+}
@@ -0,0 +1,75 @@
+use std::fmt;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{to_binary, Addr, Api, BankMsg, Coin, CosmosMsg, StdResult, Uint128, WasmMsg};
+use cw20::Cw20ExecuteMsg;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetInfo {
+    Native { denom: String },
+    Cw20 { address: Addr },
+}
+
+impl AssetInfo {
+    pub fn validate(&self, api: &dyn Api) -> StdResult<AssetInfo> {
+        match self {
+            AssetInfo::Native { denom } => Ok(AssetInfo::Native { denom: denom.clone() }),
+            AssetInfo::Cw20 { address } => Ok(AssetInfo::Cw20 {
+                address: api.addr_validate(address.as_str())?,
+            }),
+        }
+    }
+
+    pub fn is_native(&self) -> bool {
+        matches!(self, AssetInfo::Native { .. })
+    }
+
+    // true if the asset is the cw20 token of the given contract
+    pub fn is_cw20(&self, contract: &Addr) -> bool {
+        matches!(self, AssetInfo::Cw20 { address } if address == contract)
+    }
+
+    // amount of the native asset attached to the message
+    pub fn amount_in(&self, funds: &[Coin]) -> Uint128 {
+        match self {
+            AssetInfo::Native { denom } => funds
+                .iter()
+                .filter(|fund| &fund.denom == denom)
+                .map(|fund| fund.amount)
+                .sum(),
+            AssetInfo::Cw20 { .. } => Uint128::zero(),
+        }
+    }
+
+    pub fn transfer_msg<T>(&self, recipient: &str, amount: Uint128) -> StdResult<CosmosMsg<T>> {
+        match self {
+            AssetInfo::Native { denom } => Ok(CosmosMsg::Bank(BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: vec![Coin {
+                    denom: denom.to_string(),
+                    amount,
+                }],
+            })),
+            AssetInfo::Cw20 { address } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: address.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.to_string(),
+                    amount,
+                })?,
+                funds: vec![],
+            })),
+        }
+    }
+}
+
+impl fmt::Display for AssetInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AssetInfo::Native { denom } => write!(f, "{}", denom),
+            AssetInfo::Cw20 { address } => write!(f, "{}", address),
+        }
+    }
+}
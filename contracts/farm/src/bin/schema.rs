@@ -2,9 +2,10 @@ use std::env::current_dir;
 use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
-use farm_20_20::msg::{
-    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, StakerInfoResponse,
-    StateResponse,
+use cw_controllers::ClaimsResponse;
+use farm::msg::{
+    AllStakersResponse, BondedLiquidityResponse, ConfigResponse, Cw20HookMsg, ExecuteMsg, FundingResponse, InstantiateMsg,
+    QueryMsg, RewardRateResponse, StakerInfoResponse, StateResponse,
 };
 
 fn main() {
@@ -20,4 +21,9 @@ fn main() {
     export_schema(&schema_for!(ConfigResponse), &out_dir);
     export_schema(&schema_for!(StakerInfoResponse), &out_dir);
     export_schema(&schema_for!(StateResponse), &out_dir);
+    export_schema(&schema_for!(FundingResponse), &out_dir);
+    export_schema(&schema_for!(ClaimsResponse), &out_dir);
+    export_schema(&schema_for!(AllStakersResponse), &out_dir);
+    export_schema(&schema_for!(RewardRateResponse), &out_dir);
+    export_schema(&schema_for!(BondedLiquidityResponse), &out_dir);
 }
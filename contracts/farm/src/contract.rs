@@ -0,0 +1,314 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+
+use cosmwasm_std::{to_binary, Addr, Api, Binary, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo, StdResult, Uint128, WasmMsg, SubMsg, Reply};
+use cyber_std::Response;
+use cw2::{get_contract_version, set_contract_version};
+
+use crate::{
+    asset::AssetInfo,
+    msg::{
+        ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg
+    },
+    state::{
+        default_lockup_tiers, read_config, store_config, store_state, Config, LockupTier, RewardAsset,
+        State,
+    },
+    legacy::migrate_legacy_storage,
+    execute::{
+        execute_claim, execute_compound, execute_migrate_staking, execute_receive, execute_withdraw,
+        execute_change_distribution_account, execute_bond_native, execute_add_distribution_periods_native,
+        execute_remove_distribution_period, execute_update_distribution_period,
+        execute_pull_positions, execute_export_positions, execute_export_position, execute_import_positions,
+        execute_set_paused, execute_change_admin, execute_set_withdraw_address,
+        execute_transfer_position, forward_investminted,
+        execute_update_fees, validate_distribution_periods, validate_fees,
+    },
+    query::{
+        query_all_stakers, query_bonded_liquidity, query_claims, query_config, query_funding, query_pool_coin_denom,
+        query_reward_rate, query_staker_info, query_state
+    },
+    error::ContractError
+};
+
+use cw20::MinterResponse;
+use cw20_base::msg::InstantiateMsg as Cw20InstantiateMsg;
+use cw_utils::parse_reply_instantiate_data;
+
+const CONTRACT_NAME: &str = "crates.io:farm";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+pub const MSG_REPLY_ID_TOKEN_INSTANT: u64 = 1;
+pub const MSG_REPLY_ID_INVESTMINT: u64 = 2;
+
+// native reward schedules have to be funded by attached funds,
+// cw20 reward schedules are added later with the cw20 hook
+pub fn validate_rewards(
+    api: &dyn Api,
+    info: &MessageInfo,
+    time: u64,
+    rewards: Vec<RewardAsset>,
+) -> Result<Vec<RewardAsset>, ContractError> {
+    let mut validated: Vec<RewardAsset> = Vec::with_capacity(rewards.len());
+    for reward in rewards {
+        let asset = reward.asset.validate(api)?;
+        if validated.iter().any(|r| r.asset == asset) {
+            return Err(ContractError::DuplicateRewardAsset { asset: asset.to_string() });
+        }
+        validate_distribution_periods(&[], &reward.distribution_schedule, time)?;
+
+        let committed = reward.committed();
+        let received = asset.amount_in(&info.funds);
+        if received != committed {
+            return Err(ContractError::InvalidRewardFunds { expected: committed, received });
+        }
+
+        validated.push(RewardAsset {
+            asset,
+            distribution_schedule: reward.distribution_schedule,
+        });
+    }
+    Ok(validated)
+}
+
+// durations have to be unique and multipliers not zero
+fn validate_lockup_tiers(lockup_tiers: Option<Vec<LockupTier>>) -> Result<Vec<LockupTier>, ContractError> {
+    let lockup_tiers = lockup_tiers.unwrap_or_else(default_lockup_tiers);
+    for (i, tier) in lockup_tiers.iter().enumerate() {
+        if tier.multiplier.is_zero() || lockup_tiers[..i].iter().any(|t| t.duration == tier.duration) {
+            return Err(ContractError::InvalidLockupTiers {});
+        }
+    }
+    if lockup_tiers.is_empty() {
+        return Err(ContractError::InvalidLockupTiers {});
+    }
+    Ok(lockup_tiers)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    let rewards = validate_rewards(deps.api, &info, env.block.time.seconds(), msg.rewards)?;
+    let staking_asset = msg.staking_asset.validate(deps.api)?;
+
+    // farm of the liquidity pool stakes its pool coin
+    if let Some(pool_id) = msg.pool_id {
+        let denom = query_pool_coin_denom(deps.as_ref(), pool_id)?;
+        if staking_asset != (AssetInfo::Native { denom: denom.clone() }) {
+            return Err(ContractError::StakingAssetNotPoolCoin { pool_id, denom });
+        }
+    }
+    let lockup_tiers = validate_lockup_tiers(msg.lockup_tiers)?;
+
+    // auto-compounding bonds reward in the staking asset without lock
+    let auto_compound = msg.auto_compound.unwrap_or_default();
+    if auto_compound
+        && !(rewards.iter().any(|reward| reward.asset == staking_asset)
+            && lockup_tiers.iter().any(|tier| tier.duration == 0))
+    {
+        return Err(ContractError::CannotCompound {});
+    }
+    let global_reward_indexes = vec![Decimal::zero(); rewards.len()];
+    let funded_rewards = rewards.iter().map(|reward| reward.committed()).collect();
+
+    // farm migrated from another farm takes over its LP token
+    let migrated_from = msg.migrate_from.map(|addr| deps.api.addr_validate(&addr)).transpose()?;
+    let lp_token = match &migrated_from {
+        Some(migrated_from) => {
+            let source: ConfigResponse = deps.querier.query_wasm_smart(migrated_from, &QueryMsg::Config {})?;
+            if source.staking_asset != staking_asset {
+                return Err(ContractError::InvalidMigrationSource {});
+            }
+            Some(deps.api.addr_validate(&source.lp_token)?)
+        }
+        None => None,
+    };
+
+    let config = Config {
+        admin: deps.api.addr_validate(msg.admin.as_ref().unwrap_or(&msg.distribution_account))?,
+        distribution_account: deps.api.addr_validate(&msg.distribution_account)?,
+        staking_asset,
+        pool_id: msg.pool_id,
+        lp_token: lp_token.clone(),
+        rewards,
+        lockup_tiers,
+        unbonding_period: msg.unbonding_period,
+        auto_compound,
+        migrated_from,
+        paused: false,
+        treasury: msg.treasury.map(|addr| deps.api.addr_validate(&addr)).transpose()?,
+        withdraw_fee: msg.withdraw_fee.unwrap_or_default(),
+        unbond_fee: msg.unbond_fee.unwrap_or_default(),
+        unbond_fee_decay: msg.unbond_fee_decay.filter(|decay| *decay > 0),
+    };
+    validate_fees(&config)?;
+    store_config(deps.storage, &config, env.block.time.seconds())?;
+
+    store_state(
+        deps.storage,
+        &State {
+            last_distributed: env.block.time.seconds(),
+            total_bond_amount: Uint128::zero(),
+            total_weight: Uint128::zero(),
+            global_reward_indexes,
+            funded_rewards,
+        },
+        env.block.time.seconds(),
+    )?;
+
+    if let Some(lp_token) = lp_token {
+        return Ok(Response::new().add_attribute("lp_token", lp_token));
+    }
+
+    Ok(Response::default()
+       .add_submessage(SubMsg::reply_on_success(
+           CosmosMsg::Wasm(WasmMsg::Instantiate {
+               admin: Some(env.contract.address.to_string()),
+               code_id: msg.token_code_id,
+               funds: vec![],
+               label: "CFv1".to_string(),
+               msg: to_binary(&Cw20InstantiateMsg {
+                   name: format!("CyberFarm V1 - {}", msg.pool_name),
+                   symbol: "CFLST".to_string(),
+                   decimals: 0u8,
+                   initial_balances: vec![],
+                   mint: Some(MinterResponse {
+                       minter: env.contract.address.to_string(),
+                       cap: None,
+                   }),
+                   marketing: None
+               })?,
+           }),
+           MSG_REPLY_ID_TOKEN_INSTANT,
+       ))
+    )
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Bond { lock_duration, recipient } => {
+            execute_bond_native(deps, env, info, lock_duration, recipient)
+        }
+        ExecuteMsg::Receive(msg) => execute_receive(deps, env, info, msg),
+        ExecuteMsg::Withdraw { to, investmint } => execute_withdraw(deps, env, info, to, investmint),
+        ExecuteMsg::SetWithdrawAddress { address } => execute_set_withdraw_address(deps, env, info, address),
+        ExecuteMsg::TransferPosition { from, to, amount } => {
+            execute_transfer_position(deps, env, info, from, to, amount)
+        }
+        ExecuteMsg::Claim {} => execute_claim(deps, env, info),
+        ExecuteMsg::Compound {} => execute_compound(deps, env, info),
+        ExecuteMsg::AddDistributionPeriods { reward_asset, periods } => {
+            execute_add_distribution_periods_native(deps, env, info, reward_asset, periods)
+        }
+        ExecuteMsg::RemoveDistributionPeriod { reward_asset, start } => {
+            execute_remove_distribution_period(deps, env, info, reward_asset, start)
+        }
+        ExecuteMsg::UpdateDistributionPeriod { reward_asset, start, end } => {
+            execute_update_distribution_period(deps, env, info, reward_asset, start, end)
+        }
+        ExecuteMsg::MigrateStaking {
+            new_staking_contract,
+        } => execute_migrate_staking(deps, env, info, new_staking_contract),
+        ExecuteMsg::PullPositions { limit } => execute_pull_positions(deps, env, info, limit),
+        ExecuteMsg::ExportPositions { limit } => execute_export_positions(deps, env, info, limit),
+        ExecuteMsg::ExportPosition { staker } => execute_export_position(deps, env, info, staker),
+        ExecuteMsg::ImportPositions { positions } => execute_import_positions(deps, env, info, positions),
+        ExecuteMsg::ChangeDistributionAccount { new_account } => {
+            execute_change_distribution_account(deps, env, info, new_account)
+        }
+        ExecuteMsg::Pause {} => execute_set_paused(deps, env, info, true),
+        ExecuteMsg::Resume {} => execute_set_paused(deps, env, info, false),
+        ExecuteMsg::ChangeAdmin { new_admin } => execute_change_admin(deps, env, info, new_admin),
+        ExecuteMsg::UpdateFees { treasury, withdraw_fee, unbond_fee, unbond_fee_decay } => {
+            execute_update_fees(deps, env, info, treasury, withdraw_fee, unbond_fee, unbond_fee_decay)
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(
+    deps: DepsMut,
+    env: Env,
+    msg: Reply
+) -> Result<Response, ContractError> {
+    match msg.id {
+        MSG_REPLY_ID_TOKEN_INSTANT => {
+            let address = parse_reply_instantiate_data(msg).unwrap().contract_address;
+
+            let mut config = read_config(deps.storage).unwrap();
+            config.lp_token = Some(Addr::unchecked(address.clone()));
+            store_config(deps.storage, &config, env.block.time.seconds()).unwrap();
+
+            Ok(Response::new()
+                .add_attribute("reply", "token_instant")
+                .add_attribute("token_address", address))
+        }
+        MSG_REPLY_ID_INVESTMINT => forward_investminted(deps, env),
+        _ => Err(ContractError::InvalidReplyId {}),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(
+    deps: Deps,
+    env: Env,
+    msg: QueryMsg
+) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::State { time } => to_binary(&query_state(deps, time)?),
+        QueryMsg::StakerInfo {
+            staker,
+            time,
+        } => to_binary(&query_staker_info(deps, staker, time)?),
+        QueryMsg::Funding {} => to_binary(&query_funding(deps)?),
+        QueryMsg::Claims { address } => to_binary(&query_claims(deps, address)?),
+        QueryMsg::AllStakers { start_after, limit, time } => {
+            to_binary(&query_all_stakers(deps, start_after, limit, time)?)
+        }
+        QueryMsg::BondedLiquidity {} => to_binary(&query_bonded_liquidity(deps)?),
+        QueryMsg::RewardRate { time } => {
+            to_binary(&query_reward_rate(deps, time.unwrap_or_else(|| env.block.time.seconds()))?)
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    deps: DepsMut,
+    env: Env,
+    msg: MigrateMsg
+) -> Result<Response, ContractError> {
+    // farm-nv-nv, farm-nv-20, farm-20-nv and farm-20-20 have no contract version,
+    // their single reward asset becomes the first of the farm rewards
+    // and their block heights are converted to time
+    match get_contract_version(deps.storage) {
+        Ok(version) => {
+            if version.contract != CONTRACT_NAME {
+                return Err(ContractError::CannotMigrate {
+                    previous_contract: version.contract,
+                });
+            }
+        }
+        Err(_) => {
+            let block_time = msg.block_time.ok_or(ContractError::MissingBlockTime {})?;
+            migrate_legacy_storage(deps.storage, &env.block, block_time)?;
+        }
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::default())
+}
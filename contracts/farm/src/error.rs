@@ -0,0 +1,109 @@
+use cosmwasm_std::{Decimal, OverflowError, StdError, Uint128};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Invalid reply ID")]
+    InvalidReplyId {},
+
+    #[error("Invalid staking funds")]
+    InvalidStakingFunds {},
+
+    #[error("Fail to bond")]
+    FailBond {},
+
+    #[error("Fail to unbond")]
+    FailUnbond {},
+
+    #[error("Fail to transfer position")]
+    FailTransferPosition {},
+
+    #[error("Nothing to claim")]
+    NothingToClaim {},
+
+    #[error("Staking asset is not a reward asset or has no lockup tier without lock")]
+    CannotCompound {},
+
+    #[error("Nothing to compound")]
+    NothingToCompound {},
+
+    #[error("Staking asset is not the pool coin {denom} of pool {pool_id}")]
+    StakingAssetNotPoolCoin { pool_id: u64, denom: String },
+
+    #[error("Farm is paused")]
+    Paused {},
+
+    #[error("Farm is not paused")]
+    NotPaused {},
+
+    #[error("Fee {fee} has to be less than 1")]
+    InvalidFee { fee: Decimal },
+
+    #[error("Fees require a treasury")]
+    MissingTreasury {},
+
+    #[error("Farm is migrated to {new_farm}")]
+    FarmMigrated { new_farm: String },
+
+    #[error("Farm is not migrated from a farm with the same staking asset")]
+    InvalidMigrationSource {},
+
+    #[error("Invalid lockup tiers")]
+    InvalidLockupTiers {},
+
+    #[error("No lockup tier with duration {duration}")]
+    InvalidLockDuration { duration: u64 },
+
+    #[error("Lock {id} not found")]
+    LockNotFound { id: u64 },
+
+    #[error("Bond is locked")]
+    LockedBond {},
+
+    #[error("Invalid reward funds: expected {expected}, received {received}")]
+    InvalidRewardFunds { expected: Uint128, received: Uint128 },
+
+    #[error("Invalid distribution period ({start}, {end}): start must be before end")]
+    InvalidDistributionPeriod { start: u64, end: u64 },
+
+    #[error("Distribution period ({start}, {end}) starts in the past")]
+    PastDistributionPeriod { start: u64, end: u64 },
+
+    #[error("Distribution period ({start}, {end}) overlaps the distribution schedule")]
+    OverlappingDistributionPeriod { start: u64, end: u64 },
+
+    #[error("Distribution period ({start}, {end}) is already distributed")]
+    DistributedDistributionPeriod { start: u64, end: u64 },
+
+    #[error("Distribution period starting at {start} not found")]
+    DistributionPeriodNotFound { start: u64 },
+
+    #[error("Unknown reward asset: {asset}")]
+    UnknownRewardAsset { asset: String },
+
+    #[error("Duplicate reward asset: {asset}")]
+    DuplicateRewardAsset { asset: String },
+
+    #[error("Block time in seconds is required to migrate the height based farm")]
+    MissingBlockTime {},
+
+    #[error("Cannot migrate from different contract type: {previous_contract}")]
+    CannotMigrate { previous_contract: String },
+
+    #[error("Overflow: {source}")]
+    Overflow {
+        source: OverflowError,
+    },
+}
+
+impl From<OverflowError> for ContractError {
+    fn from(source: OverflowError) -> ContractError {
+        ContractError::Overflow { source }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,1640 @@
+use cosmwasm_std::{coin, coins, from_binary, to_binary, Addr, BankMsg, Coin, CosmosMsg, SubMsg, Decimal, DepsMut, Env, MessageInfo, Order, Uint128, WasmMsg, StdError, StdResult, Storage};
+use cyber_std::{create_investmint_msg, CyberMsgWrapper, Response};
+use cw_storage_plus::Bound;
+
+use crate::{
+    asset::AssetInfo,
+    contract::MSG_REPLY_ID_INVESTMINT,
+    msg::{
+        ConfigResponse, Cw20HookMsg, ExecuteMsg, Investmint, MigratedPosition, QueryMsg,
+    },
+    state::{
+        next_lock_id, read_config, read_expiring_weights_at, read_locks, read_staker_info, read_state, remove_staker_info,
+        store_config, store_lock, store_staker_info, store_state, Config, ExpiringWeight, Lock, LockupTier, RewardAsset,
+        Migration, PendingInvestmint, StakerInfo, State, CLAIMS, EXPIRED_INDEXES, EXPIRING_WEIGHTS, LOCKS, MIGRATION,
+        PENDING_INVESTMINT, STAKERS_INFO, WITHDRAW_ADDRESSES,
+    },
+};
+
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_utils::Expiration;
+use crate::error::ContractError;
+
+const MAX_EXPORT_LIMIT: u32 = 30;
+const DEFAULT_EXPORT_LIMIT: u32 = 10;
+
+// add distribution periods of native reward asset funded by attached funds
+pub fn execute_add_distribution_periods_native(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    reward_asset: AssetInfo,
+    periods: Vec<(u64, u64, Uint128)>,
+) -> Result<Response, ContractError> {
+    let reward_asset = reward_asset.validate(deps.api)?;
+    let amount = reward_asset.amount_in(&info.funds);
+
+    execute_add_distribution_periods(deps, env, info.sender, reward_asset, periods, amount)
+}
+
+pub fn execute_add_distribution_periods(
+    deps: DepsMut,
+    env: Env,
+    sender_addr: Addr,
+    reward_asset: AssetInfo,
+    periods: Vec<(u64, u64, Uint128)>,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    ensure_not_migrated(deps.storage)?;
+    let mut config: Config = read_config(deps.storage)?;
+    let mut state: State = read_state(deps.storage)?;
+    let time = env.block.time.seconds();
+
+    // farm this farm is migrated from carries over its remaining periods
+    if sender_addr != config.distribution_account && config.migrated_from.as_ref() != Some(&sender_addr) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let committed: Uint128 = periods.iter().map(|period| period.2).sum();
+    if amount != committed {
+        return Err(ContractError::InvalidRewardFunds { expected: committed, received: amount });
+    }
+
+    // distribute up to now, so the new reward asset starts from the current index
+    distribute(deps.storage, &config, &mut state, env.block.time.seconds())?;
+
+    let position = match config.rewards.iter().position(|reward| reward.asset == reward_asset) {
+        Some(position) => position,
+        None => {
+            config.rewards.push(RewardAsset {
+                asset: reward_asset.clone(),
+                distribution_schedule: vec![],
+            });
+            state.global_reward_indexes.push(Decimal::zero());
+            config.rewards.len() - 1
+        }
+    };
+
+    let reward = &mut config.rewards[position];
+    validate_distribution_periods(&reward.distribution_schedule, &periods, time)?;
+    reward.distribution_schedule.extend(periods);
+
+    match state.funded_rewards.get_mut(position) {
+        Some(funded) => *funded += amount,
+        None => state.funded_rewards.push(amount),
+    }
+
+    store_config(deps.storage, &config, time)?;
+    store_state(deps.storage, &state, time)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "add_distribution_periods"),
+        ("reward_asset", reward_asset.to_string().as_str()),
+        ("amount", amount.to_string().as_str()),
+    ]))
+}
+
+// periods have to start now or later, end after start and not overlap
+// each other or the periods of the schedule
+pub fn validate_distribution_periods(
+    distribution_schedule: &[(u64, u64, Uint128)],
+    periods: &[(u64, u64, Uint128)],
+    time: u64,
+) -> Result<(), ContractError> {
+    for (i, &(start, end, _)) in periods.iter().enumerate() {
+        if start >= end {
+            return Err(ContractError::InvalidDistributionPeriod { start, end });
+        }
+        if start < time {
+            return Err(ContractError::PastDistributionPeriod { start, end });
+        }
+        if distribution_schedule
+            .iter()
+            .chain(periods[..i].iter())
+            .any(|period| period.0 < end && start < period.1)
+        {
+            return Err(ContractError::OverlappingDistributionPeriod { start, end });
+        }
+    }
+    Ok(())
+}
+
+pub fn execute_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config: Config = read_config(deps.storage)?;
+
+    match from_binary(&cw20_msg.msg) {
+        Ok(Cw20HookMsg::Bond { lock_duration, recipient }) => {
+            // only staking token contract can execute this message
+            if !config.staking_asset.is_cw20(&info.sender) {
+                return Err(ContractError::Unauthorized {});
+            }
+
+            let beneficiary = match recipient {
+                Some(recipient) => deps.api.addr_validate(&recipient)?,
+                None => deps.api.addr_validate(&cw20_msg.sender)?,
+            };
+            execute_bond(deps, env, beneficiary, cw20_msg.amount, lock_duration)
+        }
+        Ok(Cw20HookMsg::AddDistributionPeriods { periods }) => {
+            let cw20_sender = deps.api.addr_validate(&cw20_msg.sender)?;
+            let reward_asset = AssetInfo::Cw20 { address: info.sender };
+            execute_add_distribution_periods(deps, env, cw20_sender, reward_asset, periods, cw20_msg.amount)
+        }
+        Ok(Cw20HookMsg::Unbond { lock_id }) => {
+            if config.lp_token.unwrap() != info.sender {
+                return Err(ContractError::Unauthorized {});
+            }
+
+            let cw20_sender = deps.api.addr_validate(&cw20_msg.sender)?;
+            execute_unbond(deps, env, cw20_sender, cw20_msg.amount, lock_id)
+        }
+        Ok(Cw20HookMsg::EmergencyUnbond {}) => {
+            if config.lp_token.unwrap() != info.sender {
+                return Err(ContractError::Unauthorized {});
+            }
+
+            let cw20_sender = deps.api.addr_validate(&cw20_msg.sender)?;
+            execute_emergency_unbond(deps, env, cw20_sender, cw20_msg.amount)
+        }
+        Err(_) => Err(ContractError::Unauthorized {}),
+    }
+}
+
+// bond native staking asset attached to the message
+pub fn execute_bond_native(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    lock_duration: Option<u64>,
+    recipient: Option<String>,
+) -> Result<Response, ContractError> {
+    let config: Config = read_config(deps.storage)?;
+
+    if !config.staking_asset.is_native() {
+        return Err(ContractError::InvalidStakingFunds {});
+    }
+
+    let amount = config.staking_asset.amount_in(&info.funds);
+    if amount.is_zero() {
+        return Err(ContractError::FailBond {});
+    }
+
+    let beneficiary = match recipient {
+        Some(recipient) => deps.api.addr_validate(&recipient)?,
+        None => info.sender,
+    };
+    execute_bond(deps, env, beneficiary, amount, lock_duration)
+}
+
+pub fn execute_bond(
+    deps: DepsMut,
+    env: Env,
+    sender_addr: Addr,
+    amount: Uint128,
+    lock_duration: Option<u64>,
+) -> Result<Response, ContractError> {
+    ensure_not_migrated(deps.storage)?;
+    let sender_addr_raw: Addr = deps.api.addr_validate(sender_addr.as_str())?;
+
+    let config: Config = read_config(deps.storage)?;
+    ensure_not_paused(&config)?;
+
+    let duration = lock_duration.unwrap_or_default();
+    let tier = lockup_tier(&config, duration)?;
+
+    let mut state: State = read_state(deps.storage)?;
+    let mut staker_info: StakerInfo = read_staker_info(deps.storage, &sender_addr_raw)?;
+
+    // Compute global reward & staker reward
+    let time = env.block.time.seconds();
+    distribute(deps.storage, &config, &mut state, time)?;
+    compute_staker_reward(deps.storage, &state, &mut staker_info, &[], false)?;
+    let compounded = auto_compound(deps.storage, &config, &mut state, &mut staker_info, &sender_addr_raw, time)?;
+
+    let (lock_id, lock) = bond_to_lock(deps.storage, &mut state, &mut staker_info, &sender_addr_raw, amount, tier, time)?;
+
+    // Store updated state with staker's staker_info
+    store_staker_info(deps.storage, &sender_addr_raw, &staker_info, time)?;
+    store_state(deps.storage, &state, time)?;
+
+    let response = Response::new()
+        .add_attributes(vec![
+            ("action", "bond"),
+            ("owner", sender_addr.as_str()),
+            ("amount", amount.to_string().as_str()),
+            ("lock_id", lock_id.to_string().as_str()),
+            ("unlock_time", lock.unlock_time.to_string().as_str()),
+        ])
+        .add_message(mint_msg(&config, &sender_addr, amount)?);
+    add_compounded(response, &config, &sender_addr, compounded)
+}
+
+fn lockup_tier(config: &Config, duration: u64) -> Result<&LockupTier, ContractError> {
+    config
+        .lockup_tiers
+        .iter()
+        .find(|tier| tier.duration == duration)
+        .ok_or(ContractError::InvalidLockDuration { duration })
+}
+
+// bond without lock joins the unlocked bond of the staker, locked bond gets its own lock
+fn bond_to_lock(
+    storage: &mut dyn Storage,
+    state: &mut State,
+    staker_info: &mut StakerInfo,
+    staker: &Addr,
+    amount: Uint128,
+    tier: &LockupTier,
+    time: u64,
+) -> Result<(u64, Lock), ContractError> {
+    let lock = Lock {
+        amount,
+        duration: tier.duration,
+        multiplier: tier.multiplier,
+        bond_time: time,
+        unlock_time: time + tier.duration,
+    };
+    if tier.duration > 0 {
+        let lock_id = add_lock(storage, state, staker_info, staker, &lock, time)?;
+        return Ok((lock_id, lock));
+    }
+
+    let unlocked = read_locks(storage, staker)?
+        .into_iter()
+        .find(|(_, lock)| lock.duration == 0 && lock.multiplier == tier.multiplier);
+    let (lock_id, mut lock) = match unlocked {
+        Some(unlocked) => unlocked,
+        None => {
+            let lock_id = add_lock(storage, state, staker_info, staker, &lock, time)?;
+            return Ok((lock_id, lock));
+        }
+    };
+    // unlocked bond starts at the average time of its bonds, the unbond fee decays with its age
+    lock.bond_time = average_time(lock.bond_time, lock.amount, time, amount);
+    lock.unlock_time = lock.bond_time;
+    let weight_before = lock.weight(time);
+    lock.amount += amount;
+    let weight = lock.weight(time).checked_sub(weight_before)?;
+    store_lock(storage, staker, lock_id, &lock)?;
+
+    // Increase bond_amount
+    increase_bond_amount(state, staker_info, amount, weight);
+
+    Ok((lock_id, lock))
+}
+
+// bond the new lock with its weight at the time
+fn add_lock(
+    storage: &mut dyn Storage,
+    state: &mut State,
+    staker_info: &mut StakerInfo,
+    staker: &Addr,
+    lock: &Lock,
+    time: u64,
+) -> StdResult<u64> {
+    let lock_id = next_lock_id(storage)?;
+    store_lock(storage, staker, lock_id, lock)?;
+
+    let weight = lock.weight(time);
+    if lock.is_expiring(time) {
+        let expiring = ExpiringWeight { weight, amount: lock.amount };
+        update_expiring(storage, staker_info, lock.unlock_time, &expiring, true, time)?;
+    }
+    increase_bond_amount(state, staker_info, lock.amount, weight);
+    Ok(lock_id)
+}
+
+// add or remove the weight of locked bond expiring at the unlock time for the staker and the farm
+fn update_expiring(
+    storage: &mut dyn Storage,
+    staker_info: &mut StakerInfo,
+    unlock_time: u64,
+    change: &ExpiringWeight,
+    add: bool,
+    time: u64,
+) -> StdResult<()> {
+    let apply = |expiring: &mut ExpiringWeight| -> StdResult<()> {
+        if add {
+            expiring.weight += change.weight;
+            expiring.amount += change.amount;
+        } else {
+            expiring.weight = expiring.weight.checked_sub(change.weight)?;
+            expiring.amount = expiring.amount.checked_sub(change.amount)?;
+        }
+        Ok(())
+    };
+
+    let mut expiring = EXPIRING_WEIGHTS.may_load(storage, unlock_time)?.unwrap_or_default();
+    apply(&mut expiring)?;
+    if expiring == ExpiringWeight::default() {
+        EXPIRING_WEIGHTS.remove(storage, unlock_time, time)?;
+    } else {
+        EXPIRING_WEIGHTS.save(storage, unlock_time, &expiring, time)?;
+    }
+
+    // expiring weights of the staker are kept in the order of their unlock times
+    let position = staker_info.expiring.partition_point(|(time, _)| *time < unlock_time);
+    if staker_info.expiring.get(position).map(|(time, _)| *time) != Some(unlock_time) {
+        staker_info.expiring.insert(position, (unlock_time, ExpiringWeight::default()));
+    }
+    apply(&mut staker_info.expiring[position].1)?;
+    if staker_info.expiring[position].1 == ExpiringWeight::default() {
+        staker_info.expiring.remove(position);
+    }
+    Ok(())
+}
+
+// average of two times weighted by the amounts
+fn average_time(time: u64, amount: Uint128, other_time: u64, other_amount: Uint128) -> u64 {
+    let total = amount + other_amount;
+    if total.is_zero() {
+        return other_time;
+    }
+    if other_time >= time {
+        time + Uint128::from(other_time - time).multiply_ratio(other_amount, total).u128() as u64
+    } else {
+        time - Uint128::from(time - other_time).multiply_ratio(other_amount, total).u128() as u64
+    }
+}
+
+fn mint_msg(config: &Config, recipient: &Addr, amount: Uint128) -> StdResult<CosmosMsg<CyberMsgWrapper>> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: config.lp_token.clone().unwrap().to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Mint {
+            recipient: recipient.to_string(),
+            amount,
+        })?,
+        funds: vec![],
+    }))
+}
+
+// moves pending reward in the staking asset to the unlocked bond of the staker,
+// returns the compounded amount to be minted as LP token
+fn compound_reward(
+    storage: &mut dyn Storage,
+    config: &Config,
+    state: &mut State,
+    staker_info: &mut StakerInfo,
+    staker: &Addr,
+    time: u64,
+) -> Result<Uint128, ContractError> {
+    let position = config
+        .rewards
+        .iter()
+        .position(|reward| reward.asset == config.staking_asset)
+        .ok_or(ContractError::CannotCompound {})?;
+    let amount = match staker_info.pending_rewards.get_mut(position) {
+        Some(pending_reward) => std::mem::take(pending_reward),
+        None => Uint128::zero(),
+    };
+    if !amount.is_zero() {
+        bond_to_lock(storage, state, staker_info, staker, amount, lockup_tier(config, 0)?, time)?;
+    }
+    Ok(amount)
+}
+
+// auto-compounding farm compounds the reward on every interaction of the staker
+fn auto_compound(
+    storage: &mut dyn Storage,
+    config: &Config,
+    state: &mut State,
+    staker_info: &mut StakerInfo,
+    staker: &Addr,
+    time: u64,
+) -> Result<Uint128, ContractError> {
+    if !config.auto_compound {
+        return Ok(Uint128::zero());
+    }
+    compound_reward(storage, config, state, staker_info, staker, time)
+}
+
+fn add_compounded(response: Response, config: &Config, staker: &Addr, amount: Uint128) -> Result<Response, ContractError> {
+    if amount.is_zero() {
+        return Ok(response);
+    }
+    Ok(response
+        .add_message(mint_msg(config, staker, amount)?)
+        .add_attribute("compounded", amount.to_string()))
+}
+
+// compound pending reward in the staking asset to the unlocked bond
+pub fn execute_compound(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    ensure_not_migrated(deps.storage)?;
+    let config: Config = read_config(deps.storage)?;
+    ensure_not_paused(&config)?;
+    let mut state: State = read_state(deps.storage)?;
+    let mut staker_info = read_staker_info(deps.storage, &info.sender)?;
+
+    // Compute global reward & staker reward
+    let time = env.block.time.seconds();
+    distribute(deps.storage, &config, &mut state, time)?;
+    compute_staker_reward(deps.storage, &state, &mut staker_info, &[], false)?;
+
+    let amount = compound_reward(deps.storage, &config, &mut state, &mut staker_info, &info.sender, time)?;
+    if amount.is_zero() {
+        return Err(ContractError::NothingToCompound {});
+    }
+
+    store_staker_info(deps.storage, &info.sender, &staker_info, time)?;
+    store_state(deps.storage, &state, time)?;
+
+    Ok(Response::new()
+        .add_message(mint_msg(&config, &info.sender, amount)?)
+        .add_attributes(vec![
+            ("action", "compound"),
+            ("owner", info.sender.as_str()),
+            ("amount", amount.to_string().as_str()),
+        ]))
+}
+
+pub fn execute_unbond(
+    deps: DepsMut,
+    env: Env,
+    sender_addr: Addr,
+    amount: Uint128,
+    lock_id: Option<u64>,
+) -> Result<Response, ContractError> {
+    ensure_not_migrated(deps.storage)?;
+    let config: Config = read_config(deps.storage)?;
+    ensure_not_paused(&config)?;
+    let sender_addr_raw: Addr = deps.api.addr_validate(sender_addr.clone().as_str())?;
+
+    let mut state: State = read_state(deps.storage)?;
+    let mut staker_info: StakerInfo = read_staker_info(deps.storage, &sender_addr_raw)?;
+
+    if staker_info.bond_amount < amount {
+        return Err(ContractError::FailUnbond {});
+    }
+
+    // Compute global reward & staker reward
+    let time = env.block.time.seconds();
+    distribute(deps.storage, &config, &mut state, time)?;
+    compute_staker_reward(deps.storage, &state, &mut staker_info, &[], false)?;
+    let compounded = auto_compound(deps.storage, &config, &mut state, &mut staker_info, &sender_addr_raw, time)?;
+
+    // Unbond from the given lock or from expired locks in order of their ids
+    let locks = match lock_id {
+        Some(id) => {
+            let lock = LOCKS
+                .may_load(deps.storage, (&sender_addr_raw, id))?
+                .ok_or(ContractError::LockNotFound { id })?;
+            vec![(id, lock)]
+        }
+        None => read_locks(deps.storage, &sender_addr_raw)?,
+    };
+    let mut remaining = amount;
+    let mut weight = Uint128::zero();
+    let mut fee = Uint128::zero();
+    for (id, mut lock) in locks {
+        if remaining.is_zero() {
+            break;
+        }
+        if lock.unlock_time > time {
+            continue;
+        }
+        let weight_before = lock.weight(time);
+        let unbonded = std::cmp::min(remaining, lock.amount);
+        lock.amount -= unbonded;
+        remaining -= unbonded;
+        weight += weight_before.checked_sub(lock.weight(time))?;
+        fee += unbonded * unbond_fee_rate(&config, &lock, time);
+        store_lock(deps.storage, &sender_addr_raw, id, &lock)?;
+    }
+    if !remaining.is_zero() {
+        return Err(ContractError::LockedBond {});
+    }
+
+    // Decrease bond_amount
+    decrease_bond_amount(&mut state, &mut staker_info, amount, weight)?;
+
+    // Store or remove updated rewards info
+    // depends on the left pending reward and bond amount
+    if !staker_info.has_pending_rewards() && staker_info.bond_amount.is_zero() {
+        remove_staker_info(deps.storage, &sender_addr_raw, time)?;
+    } else {
+        store_staker_info(deps.storage, &sender_addr_raw, &staker_info, time)?;
+    }
+
+    // Store updated state
+    store_state(deps.storage, &state, time)?;
+
+    let mut response = add_compounded(Response::new(), &config, &sender_addr, compounded)?
+        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: config.lp_token.clone().unwrap().to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Burn {
+                amount,
+            })?,
+            funds: vec![],
+        }))
+        .add_attributes(vec![
+            ("action", "unbond"),
+            ("owner", sender_addr.to_string().as_str()),
+            ("amount", amount.to_string().as_str()),
+            ("fee", fee.to_string().as_str()),
+        ]);
+    if !fee.is_zero() {
+        response = response.add_message(config.staking_asset.transfer_msg(treasury(&config)?.as_str(), fee)?);
+    }
+
+    // Unbonded amount earns no rewards while waiting for the end of the unbonding period
+    let returned = amount.checked_sub(fee)?;
+    match config.unbonding_period {
+        Some(period) if period > 0 => {
+            let release_at = env.block.time.plus_seconds(period);
+            CLAIMS.create_claim(deps.storage, &sender_addr_raw, returned, Expiration::AtTime(release_at))?;
+            response = response.add_attribute("release_at", release_at.seconds().to_string());
+        }
+        _ => {
+            response = response.add_message(config.staking_asset.transfer_msg(sender_addr.as_str(), returned)?);
+        }
+    }
+
+    Ok(response)
+}
+
+// move the bond backing LP tokens transferred between holders, earliest unlocking locks first,
+// moved locks keep their unlock time, rewards accrued so far stay with the sender
+pub fn execute_transfer_position(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    from: String,
+    to: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    // LP tokens move while the farm is paused, the transfer is retried by the farm after the pull
+    let config: Config = read_config(deps.storage)?;
+    let retried = info.sender == env.contract.address;
+    if config.lp_token.as_ref() != Some(&info.sender) && !retried {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let from = deps.api.addr_validate(&from)?;
+    let to = deps.api.addr_validate(&to)?;
+    if from == to {
+        return Ok(Response::new().add_attribute("action", "transfer_position"));
+    }
+
+    let mut state: State = read_state(deps.storage)?;
+    let mut from_info: StakerInfo = read_staker_info(deps.storage, &from)?;
+    let mut to_info: StakerInfo = read_staker_info(deps.storage, &to)?;
+    if from_info.bond_amount < amount {
+        // position of the sender not pulled yet is pulled from the former farm before the transfer
+        return match config.migrated_from {
+            Some(migrated_from) if !retried => Ok(Response::new()
+                .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: migrated_from.to_string(),
+                    msg: to_binary(&ExecuteMsg::ExportPosition { staker: from.to_string() })?,
+                    funds: vec![],
+                }))
+                .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: env.contract.address.to_string(),
+                    msg: to_binary(&ExecuteMsg::TransferPosition {
+                        from: from.to_string(),
+                        to: to.to_string(),
+                        amount,
+                    })?,
+                    funds: vec![],
+                }))
+                .add_attributes(vec![
+                    ("action", "pull_position"),
+                    ("staker", from.as_str()),
+                    ("migrated_from", migrated_from.as_str()),
+                ])),
+            _ => Err(ContractError::FailTransferPosition {}),
+        };
+    }
+
+    // Compute global reward & stakers reward
+    let time = env.block.time.seconds();
+    distribute(deps.storage, &config, &mut state, time)?;
+    compute_staker_reward(deps.storage, &state, &mut from_info, &[], false)?;
+    compute_staker_reward(deps.storage, &state, &mut to_info, &[], false)?;
+
+    let mut locks = read_locks(deps.storage, &from)?;
+    locks.sort_by_key(|(_, lock)| lock.unlock_time);
+    let mut remaining = amount;
+    for (id, mut lock) in locks {
+        if remaining.is_zero() {
+            break;
+        }
+        let weight_before = lock.weight(time);
+        let moved = std::cmp::min(remaining, lock.amount);
+        lock.amount -= moved;
+        remaining -= moved;
+        let weight = weight_before.checked_sub(lock.weight(time))?;
+        if lock.is_expiring(time) {
+            let expiring = ExpiringWeight { weight, amount: moved };
+            update_expiring(deps.storage, &mut from_info, lock.unlock_time, &expiring, false, time)?;
+        }
+        decrease_bond_amount(&mut state, &mut from_info, moved, weight)?;
+        store_lock(deps.storage, &from, id, &lock)?;
+
+        // moved unlocked bond joins the unlocked bond of the recipient as bonded at its start,
+        // moved locked bond keeps its unlock time
+        if lock.duration == 0 {
+            let tier = LockupTier { duration: 0, multiplier: lock.multiplier };
+            bond_to_lock(deps.storage, &mut state, &mut to_info, &to, moved, &tier, lock.bond_time)?;
+        } else {
+            let moved_lock = Lock { amount: moved, ..lock };
+            add_lock(deps.storage, &mut state, &mut to_info, &to, &moved_lock, time)?;
+        }
+    }
+
+    if !from_info.has_pending_rewards() && from_info.bond_amount.is_zero() {
+        remove_staker_info(deps.storage, &from, time)?;
+    } else {
+        store_staker_info(deps.storage, &from, &from_info, time)?;
+    }
+    store_staker_info(deps.storage, &to, &to_info, time)?;
+    store_state(deps.storage, &state, time)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "transfer_position"),
+        ("from", from.as_str()),
+        ("to", to.as_str()),
+        ("amount", amount.to_string().as_str()),
+    ]))
+}
+
+// claim unbonded amount released after the unbonding period
+pub fn execute_claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config: Config = read_config(deps.storage)?;
+
+    let amount = CLAIMS.claim_tokens(deps.storage, &info.sender, &env.block, None)?;
+    if amount.is_zero() {
+        return Err(ContractError::NothingToClaim {});
+    }
+
+    Ok(Response::new()
+        .add_message(config.staking_asset.transfer_msg(info.sender.as_str(), amount)?)
+        .add_attributes(vec![
+            ("action", "claim"),
+            ("owner", info.sender.as_str()),
+            ("amount", amount.to_string().as_str()),
+        ]))
+}
+
+// withdraw rewards to executor
+pub fn execute_withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    to: Option<String>,
+    investmint: Option<Investmint>,
+) -> Result<Response, ContractError> {
+    let config: Config = read_config(deps.storage)?;
+    ensure_not_paused(&config)?;
+    let recipient = match to {
+        Some(to) => deps.api.addr_validate(&to)?,
+        None => WITHDRAW_ADDRESSES
+            .may_load(deps.storage, &info.sender)?
+            .unwrap_or_else(|| info.sender.clone()),
+    };
+    let mut state: State = read_state(deps.storage)?;
+    let mut staker_info = read_staker_info(deps.storage, &info.sender.clone())?;
+
+    // Compute global reward & staker reward
+    let time = env.block.time.seconds();
+    distribute(deps.storage, &config, &mut state, time)?;
+    compute_staker_reward(deps.storage, &state, &mut staker_info, &[], false)?;
+    let compounded = auto_compound(deps.storage, &config, &mut state, &mut staker_info, &info.sender, time)?;
+
+    let mut messages: Vec<CosmosMsg<CyberMsgWrapper>> = vec![];
+    let mut investmints: Vec<CosmosMsg<CyberMsgWrapper>> = vec![];
+    let mut amounts: Vec<(&AssetInfo, Uint128)> = vec![];
+    let mut fees: Vec<(&AssetInfo, Uint128)> = vec![];
+    for (reward, pending_reward) in config.rewards.iter().zip(staker_info.pending_rewards.iter_mut()) {
+        if !pending_reward.is_zero() {
+            let fee = *pending_reward * config.withdraw_fee;
+            if !fee.is_zero() {
+                messages.push(reward.asset.transfer_msg(treasury(&config)?.as_str(), fee)?);
+                fees.push((&reward.asset, fee));
+            }
+            let amount = pending_reward.checked_sub(fee)?;
+            match (&investmint, &reward.asset) {
+                // only the farm may investmint its funds, the resource is minted to the farm
+                (Some(investmint), AssetInfo::Native { denom }) => investmints.push(create_investmint_msg(
+                    env.contract.address.to_string(),
+                    coin(amount.u128(), denom),
+                    investmint.resource.clone(),
+                    investmint.length,
+                )),
+                _ => messages.push(reward.asset.transfer_msg(recipient.as_str(), amount)?),
+            }
+            amounts.push((&reward.asset, amount));
+        }
+        *pending_reward = Uint128::zero();
+    }
+
+    // Store or remove updated rewards info
+    // depends on the left pending reward and bond amount
+    if staker_info.bond_amount.is_zero() {
+        remove_staker_info(deps.storage, &info.sender, time)?;
+    } else {
+        store_staker_info(deps.storage, &info.sender, &staker_info, time)?;
+    }
+
+    // Store updated state
+    store_state(deps.storage, &state, time)?;
+
+    let mut response = Response::new()
+        .add_messages(messages)
+        .add_attributes(vec![
+            ("action", "withdraw"),
+            ("owner", info.sender.as_str()),
+            ("recipient", recipient.as_str()),
+            ("amount", amounts_attribute(&amounts).as_str()),
+            ("fee", amounts_attribute(&fees).as_str()),
+        ]);
+
+    // resource minted by all investmints is forwarded to the recipient on reply to the last one
+    if let (Some(investmint), Some(last)) = (investmint, investmints.pop()) {
+        let balance_before = deps.querier.query_balance(&env.contract.address, &investmint.resource)?.amount;
+        PENDING_INVESTMINT.save(
+            deps.storage,
+            &PendingInvestmint { recipient, resource: investmint.resource, balance_before },
+        )?;
+        response = response
+            .add_messages(investmints)
+            .add_submessage(SubMsg::reply_on_success(last, MSG_REPLY_ID_INVESTMINT));
+    }
+
+    add_compounded(response, &config, &info.sender, compounded)
+}
+
+// forward the resource minted by the investmint of withdrawn rewards to the recipient
+pub fn forward_investminted(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let pending = PENDING_INVESTMINT.load(deps.storage)?;
+    PENDING_INVESTMINT.remove(deps.storage);
+
+    let balance = deps.querier.query_balance(&env.contract.address, &pending.resource)?.amount;
+    let minted = balance.checked_sub(pending.balance_before)?;
+
+    let mut response = Response::new().add_attributes(vec![
+        ("action", "investmint"),
+        ("recipient", pending.recipient.as_str()),
+        ("amount", format!("{}{}", minted, pending.resource).as_str()),
+    ]);
+    if !minted.is_zero() {
+        response = response.add_message(BankMsg::Send {
+            to_address: pending.recipient.to_string(),
+            amount: coins(minted.u128(), pending.resource),
+        });
+    }
+    Ok(response)
+}
+
+pub fn execute_set_withdraw_address(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: Option<String>,
+) -> Result<Response, ContractError> {
+    let address = match address {
+        Some(address) => deps.api.addr_validate(&address)?,
+        None => info.sender.clone(),
+    };
+    if address == info.sender {
+        WITHDRAW_ADDRESSES.remove(deps.storage, &info.sender);
+    } else {
+        WITHDRAW_ADDRESSES.save(deps.storage, &info.sender, &address)?;
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "set_withdraw_address"),
+        ("owner", info.sender.as_str()),
+        ("withdraw_address", address.as_str()),
+    ]))
+}
+
+pub fn execute_remove_distribution_period(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    reward_asset: AssetInfo,
+    start: u64,
+) -> Result<Response, ContractError> {
+    cut_distribution_period(deps, env, info, reward_asset, start, None)
+}
+
+pub fn execute_update_distribution_period(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    reward_asset: AssetInfo,
+    start: u64,
+    end: u64,
+) -> Result<Response, ContractError> {
+    cut_distribution_period(deps, env, info, reward_asset, start, Some(end))
+}
+
+// cut the period at the given end or remove its future part if no end given,
+// reward of the cut part is refunded to the distribution account
+fn cut_distribution_period(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    reward_asset: AssetInfo,
+    start: u64,
+    new_end: Option<u64>,
+) -> Result<Response, ContractError> {
+    ensure_not_migrated(deps.storage)?;
+    let mut config: Config = read_config(deps.storage)?;
+    let mut state: State = read_state(deps.storage)?;
+
+    if info.sender != config.distribution_account {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let reward_asset = reward_asset.validate(deps.api)?;
+    let position = config
+        .rewards
+        .iter()
+        .position(|reward| reward.asset == reward_asset)
+        .ok_or_else(|| ContractError::UnknownRewardAsset { asset: reward_asset.to_string() })?;
+
+    // distribute up to now with the current schedule
+    let time = env.block.time.seconds();
+    distribute(deps.storage, &config, &mut state, time)?;
+
+    let schedule = &mut config.rewards[position].distribution_schedule;
+    let index = schedule
+        .iter()
+        .position(|period| period.0 == start)
+        .ok_or(ContractError::DistributionPeriodNotFound { start })?;
+    let (_, end, amount) = schedule[index];
+
+    let action = match new_end {
+        Some(_) => "update_distribution_period",
+        None => "remove_distribution_period",
+    };
+    let new_end = match new_end {
+        Some(new_end) if new_end <= start || new_end >= end => {
+            return Err(ContractError::InvalidDistributionPeriod { start, end: new_end });
+        }
+        Some(new_end) => new_end,
+        None => std::cmp::max(start, time),
+    };
+    if new_end < time || end <= time {
+        return Err(ContractError::DistributedDistributionPeriod { start, end });
+    }
+
+    // the kept part is computed with the rate of the period as in compute_reward
+    let kept_amount = if new_end == start {
+        schedule.remove(index);
+        Uint128::zero()
+    } else {
+        let distribution_amount_per_second: Decimal = Decimal::from_ratio(amount, end - start);
+        let kept_amount = distribution_amount_per_second * Uint128::from(new_end - start);
+        schedule[index] = (start, new_end, kept_amount);
+        kept_amount
+    };
+
+    let refund_amount = amount.checked_sub(kept_amount)?;
+    state.funded_rewards[position] = state.funded_rewards[position].saturating_sub(refund_amount);
+
+    store_config(deps.storage, &config, time)?;
+    store_state(deps.storage, &state, time)?;
+
+    let mut response = Response::new();
+    if !refund_amount.is_zero() {
+        response = response.add_message(
+            reward_asset.transfer_msg(config.distribution_account.as_str(), refund_amount)?,
+        );
+    }
+
+    Ok(response.add_attributes(vec![
+        ("action", action),
+        ("reward_asset", reward_asset.to_string().as_str()),
+        ("start", start.to_string().as_str()),
+        ("end", new_end.to_string().as_str()),
+        ("refund_amount", refund_amount.to_string().as_str()),
+    ]))
+}
+
+pub fn execute_migrate_staking(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_staking_contract: String,
+) -> Result<Response, ContractError> {
+    let mut config: Config = read_config(deps.storage)?;
+    let mut state: State = read_state(deps.storage)?;
+
+    ensure_not_migrated(deps.storage)?;
+    let new_farm = deps.api.addr_validate(&new_staking_contract)?;
+
+    if info.sender != config.distribution_account {
+        return Err(ContractError::Unauthorized {});
+    }
+    let new_config: ConfigResponse = deps.querier.query_wasm_smart(&new_farm, &QueryMsg::Config {})?;
+
+    // freeze the farm, positions are exported to the new farm with their rewards settled
+    MIGRATION.save(deps.storage, &Migration { new_farm: new_farm.clone(), exported_until: None })?;
+    config.auto_compound = false;
+
+    // compute global reward, sets last_distributed to env.block.time
+    let time = env.block.time.seconds();
+    distribute(deps.storage, &config, &mut state, time)?;
+
+    let mut messages: Vec<CosmosMsg<CyberMsgWrapper>> = vec![];
+    let mut distributed_amounts: Vec<(&AssetInfo, Uint128)> = vec![];
+    let mut remaining_amounts: Vec<(&AssetInfo, Uint128)> = vec![];
+    for (reward, funded) in config.rewards.iter_mut().zip(state.funded_rewards.iter_mut()) {
+        // periods are cut at the current time, their remaining parts are carried over to the new farm
+        let mut distributed_amount = Uint128::zero();
+        let mut remaining_periods: Vec<(u64, u64, Uint128)> = vec![];
+        for s in std::mem::take(&mut reward.distribution_schedule) {
+            if s.1 <= time {
+                // all distributed
+                distributed_amount += s.2;
+                reward.distribution_schedule.push(s);
+            } else if s.0 >= time {
+                // not started
+                remaining_periods.push(s);
+            } else {
+                // partially distributed slot
+                let duration = s.1 - s.0;
+                let distribution_amount_per_second: Decimal = Decimal::from_ratio(s.2, duration);
+
+                let passed_time = time - s.0;
+                let distributed_amount_on_slot =
+                    distribution_amount_per_second * Uint128::from(passed_time as u128);
+                distributed_amount += distributed_amount_on_slot;
+
+                // modify distribution slot
+                reward.distribution_schedule.push((s.0, time, distributed_amount_on_slot));
+                remaining_periods.push((time, s.1, s.2.checked_sub(distributed_amount_on_slot)?));
+            }
+        }
+        remaining_periods.retain(|period| !period.2.is_zero());
+        let new_schedule = new_config
+            .rewards
+            .iter()
+            .find(|new_reward| new_reward.asset == reward.asset)
+            .map_or(&[][..], |new_reward| &new_reward.distribution_schedule[..]);
+        let remaining_periods = after_schedule(new_schedule, remaining_periods);
+
+        let remaining_amount: Uint128 = remaining_periods.iter().map(|period| period.2).sum();
+        *funded = funded.saturating_sub(remaining_amount);
+        if !remaining_amount.is_zero() {
+            messages.push(add_distribution_periods_msg(&reward.asset, &new_farm, remaining_periods, remaining_amount)?);
+        }
+        distributed_amounts.push((&reward.asset, distributed_amount));
+        remaining_amounts.push((&reward.asset, remaining_amount));
+    }
+
+    // update state
+    store_state(deps.storage, &state, time)?;
+
+    let distributed_amount = amounts_attribute(&distributed_amounts);
+    let remaining_amount = amounts_attribute(&remaining_amounts);
+    // update config
+    store_config(deps.storage, &config, time)?;
+
+    // LP token of the farm keeps representing the positions in the new farm
+    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: config.lp_token.clone().unwrap().to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::UpdateMinter {
+            new_minter: Some(new_farm.to_string()),
+        })?,
+        funds: vec![],
+    }));
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attributes(vec![
+            ("action", "migrate_staking"),
+            ("new_staking_contract", new_farm.as_str()),
+            ("distributed_amount", &distributed_amount),
+            ("remaining_amount", &remaining_amount),
+        ]))
+}
+
+// carried periods start after the last period of the schedule of the new farm,
+// periods overlapping it are delayed keeping their durations
+fn after_schedule(schedule: &[(u64, u64, Uint128)], mut periods: Vec<(u64, u64, Uint128)>) -> Vec<(u64, u64, Uint128)> {
+    let mut next_start = schedule.iter().map(|period| period.1).max().unwrap_or_default();
+    periods.sort_by_key(|period| period.0);
+    periods
+        .into_iter()
+        .map(|(start, end, amount)| {
+            let delayed = std::cmp::max(start, next_start);
+            next_start = delayed + (end - start);
+            (delayed, next_start, amount)
+        })
+        .collect()
+}
+
+// periods of the reward asset added to the farm funded with the amount
+fn add_distribution_periods_msg(
+    reward_asset: &AssetInfo,
+    farm: &Addr,
+    periods: Vec<(u64, u64, Uint128)>,
+    amount: Uint128,
+) -> StdResult<CosmosMsg<CyberMsgWrapper>> {
+    let msg = match reward_asset {
+        AssetInfo::Native { denom } => WasmMsg::Execute {
+            contract_addr: farm.to_string(),
+            msg: to_binary(&ExecuteMsg::AddDistributionPeriods { reward_asset: reward_asset.clone(), periods })?,
+            funds: vec![Coin { denom: denom.clone(), amount }],
+        },
+        AssetInfo::Cw20 { address } => WasmMsg::Execute {
+            contract_addr: address.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: farm.to_string(),
+                amount,
+                msg: to_binary(&Cw20HookMsg::AddDistributionPeriods { periods })?,
+            })?,
+            funds: vec![],
+        },
+    };
+    Ok(CosmosMsg::Wasm(msg))
+}
+
+fn ensure_not_paused(config: &Config) -> Result<(), ContractError> {
+    if config.paused {
+        return Err(ContractError::Paused {});
+    }
+    Ok(())
+}
+
+fn ensure_not_migrated(storage: &dyn Storage) -> Result<(), ContractError> {
+    match MIGRATION.may_load(storage)? {
+        Some(migration) => Err(ContractError::FarmMigrated { new_farm: migration.new_farm.to_string() }),
+        None => Ok(()),
+    }
+}
+
+// request the next positions from the farm this farm is migrated from
+pub fn execute_pull_positions(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let config: Config = read_config(deps.storage)?;
+    let migrated_from = config.migrated_from.ok_or(ContractError::InvalidMigrationSource {})?;
+
+    Ok(Response::new()
+        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: migrated_from.to_string(),
+            msg: to_binary(&ExecuteMsg::ExportPositions { limit })?,
+            funds: vec![],
+        }))
+        .add_attributes(vec![
+            ("action", "pull_positions"),
+            ("migrated_from", migrated_from.as_str()),
+        ]))
+}
+
+// export the positions of the next stakers to the new farm
+pub fn execute_export_positions(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let mut migration = load_migration_of(deps.storage, &info.sender)?;
+
+    let limit = limit.unwrap_or(DEFAULT_EXPORT_LIMIT).min(MAX_EXPORT_LIMIT) as usize;
+    let start = migration.exported_until.as_ref().map(Bound::exclusive);
+    let stakers = STAKERS_INFO
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<Addr>>>()?;
+    if let Some(last) = stakers.last() {
+        migration.exported_until = Some(last.clone());
+    }
+    MIGRATION.save(deps.storage, &migration)?;
+
+    export_positions(deps.storage, &migration, &stakers, env.block.time.seconds())
+}
+
+// export the position of the staker on demand of the new farm, exported position is not exported again
+pub fn execute_export_position(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    staker: String,
+) -> Result<Response, ContractError> {
+    let migration = load_migration_of(deps.storage, &info.sender)?;
+    let staker = deps.api.addr_validate(&staker)?;
+
+    export_positions(deps.storage, &migration, &[staker], env.block.time.seconds())
+}
+
+// migration of the frozen farm to the new farm
+fn load_migration_of(storage: &dyn Storage, new_farm: &Addr) -> Result<Migration, ContractError> {
+    match MIGRATION.may_load(storage)? {
+        Some(migration) if &migration.new_farm == new_farm => Ok(migration),
+        _ => Err(ContractError::Unauthorized {}),
+    }
+}
+
+// settle rewards of the stakers and send their locks with bonded funds to the new farm,
+// pending rewards stay to be withdrawn from this farm
+fn export_positions(
+    storage: &mut dyn Storage,
+    migration: &Migration,
+    stakers: &[Addr],
+    time: u64,
+) -> Result<Response, ContractError> {
+    let config: Config = read_config(storage)?;
+    let mut state: State = read_state(storage)?;
+    distribute(storage, &config, &mut state, time)?;
+
+    let mut positions: Vec<MigratedPosition> = vec![];
+    let mut total = Uint128::zero();
+    for staker in stakers.iter() {
+        if STAKERS_INFO.may_load(storage, staker)?.is_none() {
+            continue;
+        }
+        let mut staker_info = read_staker_info(storage, staker)?;
+        compute_staker_reward(storage, &state, &mut staker_info, &[], false)?;
+
+        total += staker_info.bond_amount;
+        let locks = remove_bond(storage, &mut state, &mut staker_info, staker, time)?;
+
+        if staker_info.has_pending_rewards() {
+            store_staker_info(storage, staker, &staker_info, time)?;
+        } else {
+            remove_staker_info(storage, staker, time)?;
+        }
+        if !locks.is_empty() {
+            positions.push(MigratedPosition {
+                staker: staker.to_string(),
+                locks: locks.into_iter().map(|(_, lock)| lock).collect(),
+            });
+        }
+    }
+    store_state(storage, &state, time)?;
+
+    let mut response = Response::new().add_attributes(vec![
+        ("action", "export_positions"),
+        ("new_farm", migration.new_farm.as_str()),
+        ("positions", positions.len().to_string().as_str()),
+        ("amount", total.to_string().as_str()),
+    ]);
+    if positions.is_empty() {
+        return Ok(response);
+    }
+
+    // native funds are attached to the import, cw20 ones are transferred before it
+    let funds = match &config.staking_asset {
+        AssetInfo::Native { denom } => vec![Coin { denom: denom.clone(), amount: total }],
+        AssetInfo::Cw20 { .. } => {
+            response = response.add_message(config.staking_asset.transfer_msg(migration.new_farm.as_str(), total)?);
+            vec![]
+        }
+    };
+    Ok(response.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: migration.new_farm.to_string(),
+        msg: to_binary(&ExecuteMsg::ImportPositions { positions })?,
+        funds,
+    })))
+}
+
+// bond the exported locks to their stakers, receipt is the shared LP token they already hold
+pub fn execute_import_positions(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    positions: Vec<MigratedPosition>,
+) -> Result<Response, ContractError> {
+    let config: Config = read_config(deps.storage)?;
+    if config.migrated_from.as_ref() != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let total: Uint128 = positions.iter().flat_map(|position| position.locks.iter()).map(|lock| lock.amount).sum();
+    if config.staking_asset.is_native() && config.staking_asset.amount_in(&info.funds) != total {
+        return Err(ContractError::InvalidStakingFunds {});
+    }
+
+    let mut state: State = read_state(deps.storage)?;
+    let time = env.block.time.seconds();
+    distribute(deps.storage, &config, &mut state, time)?;
+
+    for position in positions.iter() {
+        let staker = deps.api.addr_validate(&position.staker)?;
+        let mut staker_info = read_staker_info(deps.storage, &staker)?;
+        compute_staker_reward(deps.storage, &state, &mut staker_info, &[], false)?;
+        for lock in position.locks.iter() {
+            // lock is boosted by the lockup tier of its duration in this farm, not boosted without one
+            let multiplier = config
+                .lockup_tiers
+                .iter()
+                .find(|tier| tier.duration == lock.duration)
+                .map_or_else(Decimal::one, |tier| tier.multiplier);
+            if lock.duration == 0 {
+                // unlocked bond joins the unlocked bond of the staker as bonded at its start
+                let tier = LockupTier { duration: 0, multiplier };
+                bond_to_lock(deps.storage, &mut state, &mut staker_info, &staker, lock.amount, &tier, lock.bond_time)?;
+            } else {
+                let lock = Lock { multiplier, ..lock.clone() };
+                add_lock(deps.storage, &mut state, &mut staker_info, &staker, &lock, time)?;
+            }
+        }
+        store_staker_info(deps.storage, &staker, &staker_info, time)?;
+    }
+    store_state(deps.storage, &state, time)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "import_positions"),
+        ("positions", positions.len().to_string().as_str()),
+        ("amount", total.to_string().as_str()),
+    ]))
+}
+
+pub fn execute_change_distribution_account(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_account: String,
+) -> Result<Response, ContractError> {
+    let mut config: Config = read_config(deps.storage)?;
+
+    if info.sender != config.distribution_account {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    config.distribution_account = deps.api.addr_validate(&new_account)?;
+    store_config(deps.storage, &config, env.block.time.seconds())?;
+
+    Ok(Response::default())
+}
+
+pub fn execute_set_paused(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    paused: bool,
+) -> Result<Response, ContractError> {
+    let mut config: Config = read_config(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    config.paused = paused;
+    store_config(deps.storage, &config, env.block.time.seconds())?;
+
+    Ok(Response::new().add_attribute("action", if paused { "pause" } else { "resume" }))
+}
+
+pub fn execute_change_admin(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_admin: String,
+) -> Result<Response, ContractError> {
+    let mut config: Config = read_config(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    config.admin = deps.api.addr_validate(&new_admin)?;
+    store_config(deps.storage, &config, env.block.time.seconds())?;
+
+    Ok(Response::new().add_attributes(vec![("action", "change_admin"), ("admin", new_admin.as_str())]))
+}
+
+pub fn execute_update_fees(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    treasury: Option<String>,
+    withdraw_fee: Option<Decimal>,
+    unbond_fee: Option<Decimal>,
+    unbond_fee_decay: Option<u64>,
+) -> Result<Response, ContractError> {
+    let mut config: Config = read_config(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(treasury) = treasury {
+        config.treasury = Some(deps.api.addr_validate(&treasury)?);
+    }
+    if let Some(withdraw_fee) = withdraw_fee {
+        config.withdraw_fee = withdraw_fee;
+    }
+    if let Some(unbond_fee) = unbond_fee {
+        config.unbond_fee = unbond_fee;
+    }
+    if let Some(unbond_fee_decay) = unbond_fee_decay {
+        config.unbond_fee_decay = Some(unbond_fee_decay).filter(|decay| *decay > 0);
+    }
+    validate_fees(&config)?;
+    store_config(deps.storage, &config, env.block.time.seconds())?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "update_fees"),
+        ("withdraw_fee", config.withdraw_fee.to_string().as_str()),
+        ("unbond_fee", config.unbond_fee.to_string().as_str()),
+    ]))
+}
+
+// fees are less than the whole amount and are sent to the treasury
+pub fn validate_fees(config: &Config) -> Result<(), ContractError> {
+    for fee in [config.withdraw_fee, config.unbond_fee] {
+        if fee >= Decimal::one() {
+            return Err(ContractError::InvalidFee { fee });
+        }
+    }
+    if config.treasury.is_none() && !(config.withdraw_fee.is_zero() && config.unbond_fee.is_zero()) {
+        return Err(ContractError::MissingTreasury {});
+    }
+    Ok(())
+}
+
+fn treasury(config: &Config) -> Result<&Addr, ContractError> {
+    config.treasury.as_ref().ok_or(ContractError::MissingTreasury {})
+}
+
+// unbond fee of the lock decays with the time since its bond
+fn unbond_fee_rate(config: &Config, lock: &Lock, time: u64) -> Decimal {
+    match config.unbond_fee_decay {
+        Some(decay) => {
+            let age = time.saturating_sub(lock.bond_time);
+            if age >= decay {
+                Decimal::zero()
+            } else {
+                config.unbond_fee * Decimal::from_ratio(decay - age, decay)
+            }
+        }
+        None => config.unbond_fee,
+    }
+}
+
+// return the whole bond of the paused farm without computing rewards, pending rewards are
+// refunded to the distribution account, rewards since the last distribution go to the remaining stakers
+pub fn execute_emergency_unbond(
+    deps: DepsMut,
+    env: Env,
+    sender_addr: Addr,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    ensure_not_migrated(deps.storage)?;
+    let config: Config = read_config(deps.storage)?;
+    if !config.paused {
+        return Err(ContractError::NotPaused {});
+    }
+
+    let mut state: State = read_state(deps.storage)?;
+    let mut staker_info: StakerInfo = read_staker_info(deps.storage, &sender_addr)?;
+    if staker_info.bond_amount.is_zero() || staker_info.bond_amount != amount {
+        return Err(ContractError::FailUnbond {});
+    }
+
+    let time = env.block.time.seconds();
+    // weights expired before the last distribution are settled without computing rewards
+    compute_staker_reward(deps.storage, &state, &mut staker_info, &[], false)?;
+    remove_bond(deps.storage, &mut state, &mut staker_info, &sender_addr, time)?;
+    let forfeited: Vec<(&AssetInfo, Uint128)> = config
+        .rewards
+        .iter()
+        .map(|reward| &reward.asset)
+        .zip(staker_info.pending_rewards.iter().copied())
+        .filter(|(_, pending_reward)| !pending_reward.is_zero())
+        .collect();
+    let mut refunds: Vec<CosmosMsg<CyberMsgWrapper>> = vec![];
+    for (position, pending_reward) in staker_info.pending_rewards.iter().enumerate() {
+        if pending_reward.is_zero() {
+            continue;
+        }
+        state.funded_rewards[position] = state.funded_rewards[position].saturating_sub(*pending_reward);
+        refunds.push(config.rewards[position].asset.transfer_msg(config.distribution_account.as_str(), *pending_reward)?);
+    }
+    let forfeited = amounts_attribute(&forfeited);
+
+    remove_staker_info(deps.storage, &sender_addr, time)?;
+    store_state(deps.storage, &state, time)?;
+
+    Ok(Response::new()
+        .add_messages(vec![
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: config.lp_token.clone().unwrap().to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Burn {
+                    amount,
+                })?,
+                funds: vec![],
+            }),
+            config.staking_asset.transfer_msg(sender_addr.as_str(), amount)?,
+        ])
+        .add_messages(refunds)
+        .add_attributes(vec![
+            ("action", "emergency_unbond"),
+            ("owner", sender_addr.as_str()),
+            ("amount", amount.to_string().as_str()),
+            ("forfeited_reward", forfeited.as_str()),
+        ]))
+}
+
+// remove all locks of the staker with their expiring weights, returns the removed locks
+fn remove_bond(
+    storage: &mut dyn Storage,
+    state: &mut State,
+    staker_info: &mut StakerInfo,
+    staker: &Addr,
+    time: u64,
+) -> StdResult<Vec<(u64, Lock)>> {
+    let locks = read_locks(storage, staker)?;
+    for (id, _) in locks.iter() {
+        LOCKS.remove(storage, (staker, *id));
+    }
+    for (unlock_time, expiring) in staker_info.expiring.clone() {
+        update_expiring(storage, staker_info, unlock_time, &expiring, false, time)?;
+    }
+    let (bond_amount, weight) = (staker_info.bond_amount, staker_info.weight);
+    decrease_bond_amount(state, staker_info, bond_amount, weight)?;
+    Ok(locks)
+}
+
+fn increase_bond_amount(state: &mut State, staker_info: &mut StakerInfo, amount: Uint128, weight: Uint128) {
+    state.total_bond_amount += amount;
+    state.total_weight += weight;
+    staker_info.bond_amount += amount;
+    staker_info.weight += weight;
+}
+
+fn decrease_bond_amount(
+    state: &mut State,
+    staker_info: &mut StakerInfo,
+    amount: Uint128,
+    weight: Uint128,
+) -> StdResult<()> {
+    state.total_bond_amount = state.total_bond_amount.checked_sub(amount)?;
+    state.total_weight = state.total_weight.checked_sub(weight)?;
+    staker_info.bond_amount = staker_info.bond_amount.checked_sub(amount)?;
+    staker_info.weight = staker_info.weight.checked_sub(weight)?;
+    Ok(())
+}
+
+// amounts of reward assets formatted as coins, e.g. "100boot,25bostrom1...", "0" if none
+fn amounts_attribute(amounts: &[(&AssetInfo, Uint128)]) -> String {
+    if amounts.is_empty() {
+        return "0".to_string();
+    }
+    amounts
+        .iter()
+        .map(|(asset, amount)| format!("{}{}", amount, asset))
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+// compute amount of the schedule distributed between last_distributed and time
+pub fn compute_distributed_amount(
+    distribution_schedule: &[(u64, u64, Uint128)],
+    last_distributed: u64,
+    time: u64,
+) -> Uint128 {
+    let mut distributed_amount: Uint128 = Uint128::zero();
+    for s in distribution_schedule.iter() {
+        if s.0 > time || s.1 < last_distributed {
+            continue;
+        }
+
+        // min(s.1, time) - max(s.0, last_distributed)
+        let passed_time =
+            std::cmp::min(s.1, time) - std::cmp::max(s.0, last_distributed);
+
+        let duration = s.1 - s.0;
+        let distribution_amount_per_second: Decimal = Decimal::from_ratio(s.2, duration);
+        distributed_amount += distribution_amount_per_second * Uint128::from(passed_time as u128);
+    }
+    distributed_amount
+}
+
+// compute distributed rewards and update global reward indexes, weights expiring
+// on the way drop at their unlock times, returns the global reward indexes at them,
+// historical computation reads the expiring weights checkpointed at the end of the time
+pub fn compute_reward(
+    storage: &dyn Storage,
+    config: &Config,
+    state: &mut State,
+    time: u64,
+    historical: bool,
+) -> StdResult<Vec<(u64, Vec<Decimal>)>> {
+    // reward assets added since the last distribution start from zero index
+    state.global_reward_indexes.resize(config.rewards.len(), Decimal::zero());
+
+    // rewards are never distributed back in time
+    if time < state.last_distributed {
+        return Ok(vec![]);
+    }
+
+    let expiring = match historical {
+        true => read_expiring_weights_at(storage, state.last_distributed, time)?,
+        false => EXPIRING_WEIGHTS
+            .range(
+                storage,
+                Some(Bound::exclusive(state.last_distributed)),
+                Some(Bound::inclusive(time)),
+                Order::Ascending,
+            )
+            .collect::<StdResult<Vec<_>>>()?,
+    };
+    let mut expired = Vec::with_capacity(expiring.len());
+    for (unlock_time, expiring) in expiring {
+        distribute_reward(config, state, unlock_time);
+        state.total_weight = state.total_weight.checked_sub(expiring.weight)? + expiring.amount;
+        expired.push((unlock_time, state.global_reward_indexes.clone()));
+    }
+    distribute_reward(config, state, time);
+
+    Ok(expired)
+}
+
+// distribute rewards up to time by the total weight
+fn distribute_reward(config: &Config, state: &mut State, time: u64) {
+    if state.total_weight.is_zero() {
+        state.last_distributed = time;
+        return;
+    }
+
+    for (reward, global_reward_index) in config.rewards.iter().zip(state.global_reward_indexes.iter_mut()) {
+        let distributed_amount = compute_distributed_amount(
+            &reward.distribution_schedule,
+            state.last_distributed,
+            time,
+        );
+        *global_reward_index += Decimal::from_ratio(distributed_amount, state.total_weight);
+    }
+
+    state.last_distributed = time;
+}
+
+// compute distributed rewards and keep the global reward indexes at the passed unlock times
+fn distribute(storage: &mut dyn Storage, config: &Config, state: &mut State, time: u64) -> StdResult<()> {
+    for (unlock_time, global_reward_indexes) in compute_reward(storage, config, state, time, false)? {
+        EXPIRED_INDEXES.save(storage, unlock_time, &global_reward_indexes, time)?;
+    }
+    Ok(())
+}
+
+// withdraw rewards to pending rewards, weights of the staker expired since its last update
+// earn with their boost up to their unlock time, global reward indexes at the unlock times
+// are taken from expired or from the storage, checkpointed at the last distribution if historical
+pub fn compute_staker_reward(
+    storage: &dyn Storage,
+    state: &State,
+    staker_info: &mut StakerInfo,
+    expired: &[(u64, Vec<Decimal>)],
+    historical: bool,
+) -> StdResult<()> {
+    let reward_assets = state.global_reward_indexes.len();
+    staker_info.reward_indexes.resize(reward_assets, Decimal::zero());
+    staker_info.pending_rewards.resize(reward_assets, Uint128::zero());
+
+    let settled = staker_info
+        .expiring
+        .iter()
+        .take_while(|(unlock_time, _)| *unlock_time <= state.last_distributed)
+        .count();
+    for (unlock_time, expiring) in staker_info.expiring.drain(..settled).collect::<Vec<_>>() {
+        let global_reward_indexes = match expired.iter().find(|(time, _)| *time == unlock_time) {
+            Some((_, global_reward_indexes)) => global_reward_indexes.clone(),
+            None if historical => EXPIRED_INDEXES
+                .may_load_at_height(storage, unlock_time, state.last_distributed + 1)?
+                .ok_or_else(|| StdError::not_found("expired indexes"))?,
+            None => EXPIRED_INDEXES.load(storage, unlock_time)?,
+        };
+        accrue_reward(staker_info, &global_reward_indexes)?;
+        staker_info.weight = staker_info.weight.checked_sub(expiring.weight)? + expiring.amount;
+    }
+    accrue_reward(staker_info, &state.global_reward_indexes)
+}
+
+// accrue rewards of the staker weight up to the global reward indexes,
+// reward assets added later have no global reward index yet
+fn accrue_reward(staker_info: &mut StakerInfo, global_reward_indexes: &[Decimal]) -> StdResult<()> {
+    let weight = staker_info.weight;
+    for ((global_reward_index, reward_index), pending_reward) in global_reward_indexes
+        .iter()
+        .zip(staker_info.reward_indexes.iter_mut())
+        .zip(staker_info.pending_rewards.iter_mut())
+    {
+        *pending_reward += (weight * *global_reward_index)
+            .checked_sub(weight * *reward_index)?;
+        *reward_index = *global_reward_index;
+    }
+    Ok(())
+}
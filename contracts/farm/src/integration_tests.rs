@@ -0,0 +1,1664 @@
+#![cfg(test)]
+
+use anyhow::Result as AnyResult;
+use std::marker::PhantomData;
+
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    coin, coins, from_binary, to_binary, Addr, BankMsg, ContractResult, Decimal, Deps, DepsMut, Empty, OwnedDeps,
+    QuerierWrapper, Reply, SubMsg, SubMsgResponse, SubMsgResult, SystemError, SystemResult, Uint128,
+};
+use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg};
+use cw20_base::msg::InstantiateMsg as Cw20InstantiateMsg;
+use cw_controllers::{Claim, ClaimsResponse};
+use cw_utils::Expiration;
+use cw_multi_test::{AppResponse, Contract, ContractWrapper, Executor};
+use cyber_std::{
+    create_investmint_msg, CyberMsgWrapper, CyberQuery, CyberQueryWrapper, PoolLiquidityResponse, PoolParamsResponse, PoolSupplyResponse,
+};
+use cyber_std_test::{farm_scenario, run_farm_scenario, CyberApp, FarmHarness, StakerPosition};
+use proptest::prelude::*;
+
+use crate::asset::AssetInfo;
+use crate::contract::{execute, instantiate, migrate, query, reply, MSG_REPLY_ID_INVESTMINT};
+use crate::error::ContractError;
+use crate::msg::{
+    AllStakersResponse, BondedLiquidityResponse, ConfigResponse, Cw20HookMsg, ExecuteMsg, FundingResponse, InstantiateMsg, Investmint, MigrateMsg, QueryMsg,
+    RewardFundingResponse, RewardRateResponse, StakerInfoResponse, StateResponse,
+};
+use crate::legacy::{LegacyConfig, LegacyStakerInfo, LegacyState, LEGACY_CONFIG, LEGACY_STAKERS_INFO, LEGACY_STATE};
+use crate::state::{
+    read_config, read_locks, read_staker_info, read_state, store_config, store_state, LockupTier, RewardAsset,
+};
+
+const STAKING_DENOM: &str = "pool";
+const REWARD_DENOM: &str = "boot";
+const SECOND_REWARD_DENOM: &str = "milk";
+const DISTRIBUTION_ACCOUNT: &str = "distribution";
+const STAKERS: usize = 3;
+
+pub fn contract_farm() -> Box<dyn Contract<CyberMsgWrapper, Empty>> {
+    let contract = ContractWrapper::new(execute, instantiate, query).with_reply(reply);
+    Box::new(contract)
+}
+
+pub fn contract_cw20() -> Box<dyn Contract<CyberMsgWrapper, Empty>> {
+    let contract = ContractWrapper::new_with_empty(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    );
+    Box::new(contract)
+}
+
+fn instantiate_cw20(app: &mut CyberApp, code_id: u64, symbol: &str, holders: &[Addr]) -> Addr {
+    app.instantiate_contract(
+        code_id,
+        Addr::unchecked(DISTRIBUTION_ACCOUNT),
+        &Cw20InstantiateMsg {
+            name: format!("{} token", symbol),
+            symbol: symbol.to_string(),
+            decimals: 6,
+            initial_balances: holders
+                .iter()
+                .map(|holder| Cw20Coin {
+                    address: holder.to_string(),
+                    amount: Uint128::new(u128::MAX / 8),
+                })
+                .collect(),
+            mint: None,
+            marketing: None,
+        },
+        &[],
+        symbol,
+        None,
+    )
+    .unwrap()
+}
+
+struct Farm {
+    app: CyberApp,
+    farm: Addr,
+    lp_token: Addr,
+    staking_asset: AssetInfo,
+    reward_asset: AssetInfo,
+    stakers: Vec<Addr>,
+}
+
+impl Farm {
+    fn new(staking_cw20: bool, reward_cw20: bool) -> Self {
+        Farm::with_config(staking_cw20, reward_cw20, |_| {})
+    }
+
+    // farm with the instantiate message adjusted by configure
+    fn with_config(staking_cw20: bool, reward_cw20: bool, configure: impl FnOnce(&mut InstantiateMsg)) -> Self {
+        let mut app = CyberApp::new();
+        let distribution_account = Addr::unchecked(DISTRIBUTION_ACCOUNT);
+        let stakers: Vec<Addr> = (0..STAKERS).map(|i| Addr::unchecked(format!("staker{}", i))).collect();
+        app.init_modules(|router, _, storage| {
+            router.bank
+                .init_balance(
+                    storage,
+                    &distribution_account,
+                    vec![
+                        coin(u128::MAX / 8, REWARD_DENOM),
+                        coin(u128::MAX / 8, SECOND_REWARD_DENOM),
+                        coin(u128::MAX / 8, STAKING_DENOM),
+                    ],
+                )
+                .unwrap();
+            for staker in stakers.iter() {
+                router.bank
+                    .init_balance(storage, staker, coins(u128::MAX / 8, STAKING_DENOM))
+                    .unwrap();
+            }
+        });
+
+        let token_code_id = app.store_code(contract_cw20());
+        let staking_asset = if staking_cw20 {
+            AssetInfo::Cw20 { address: instantiate_cw20(&mut app, token_code_id, "POOL", &stakers) }
+        } else {
+            AssetInfo::Native { denom: STAKING_DENOM.to_string() }
+        };
+        let reward_asset = if reward_cw20 {
+            AssetInfo::Cw20 {
+                address: instantiate_cw20(&mut app, token_code_id, "BOOT", std::slice::from_ref(&distribution_account)),
+            }
+        } else {
+            AssetInfo::Native { denom: REWARD_DENOM.to_string() }
+        };
+
+        let farm_code_id = app.store_code(contract_farm());
+        let mut msg = InstantiateMsg {
+            admin: None,
+            distribution_account: DISTRIBUTION_ACCOUNT.to_string(),
+            staking_asset: staking_asset.clone(),
+            pool_id: None,
+            rewards: vec![RewardAsset {
+                asset: reward_asset.clone(),
+                distribution_schedule: vec![],
+            }],
+            lockup_tiers: None,
+            unbonding_period: None,
+            auto_compound: None,
+            migrate_from: None,
+            token_code_id,
+            pool_name: "test".to_string(),
+            treasury: None,
+            withdraw_fee: None,
+            unbond_fee: None,
+            unbond_fee_decay: None,
+        };
+        configure(&mut msg);
+        let farm = app
+            .instantiate_contract(farm_code_id, distribution_account, &msg, &[], "farm", None)
+            .unwrap();
+
+        let config: ConfigResponse = app.wrap().query_wasm_smart(&farm, &QueryMsg::Config {}).unwrap();
+        Farm {
+            app,
+            farm,
+            lp_token: Addr::unchecked(config.lp_token),
+            staking_asset,
+            reward_asset,
+            stakers,
+        }
+    }
+
+    // adds periods by the distribution account, funded with the amount of the reward asset
+    fn add_periods(
+        &mut self,
+        reward_asset: &AssetInfo,
+        periods: Vec<(u64, u64, Uint128)>,
+        amount: u128,
+    ) -> AnyResult<AppResponse> {
+        let distribution_account = Addr::unchecked(DISTRIBUTION_ACCOUNT);
+        match reward_asset {
+            AssetInfo::Native { denom } => self.app.execute_contract(
+                distribution_account,
+                self.farm.clone(),
+                &ExecuteMsg::AddDistributionPeriods {
+                    reward_asset: reward_asset.clone(),
+                    periods,
+                },
+                &coins(amount, denom),
+            ),
+            AssetInfo::Cw20 { address } => self.app.execute_contract(
+                distribution_account,
+                address.clone(),
+                &Cw20ExecuteMsg::Send {
+                    contract: self.farm.to_string(),
+                    amount: Uint128::new(amount),
+                    msg: to_binary(&Cw20HookMsg::AddDistributionPeriods { periods })?,
+                },
+                &[],
+            ),
+        }
+    }
+
+    // farm with the same assets and the default lockup tiers migrated from the farm
+    fn instantiate_new_farm(&mut self) -> Addr {
+        let farm_code_id = self.app.store_code(contract_farm());
+        let msg = InstantiateMsg {
+            admin: None,
+            distribution_account: DISTRIBUTION_ACCOUNT.to_string(),
+            staking_asset: self.staking_asset.clone(),
+            pool_id: None,
+            rewards: vec![],
+            lockup_tiers: None,
+            unbonding_period: None,
+            auto_compound: None,
+            migrate_from: Some(self.farm.to_string()),
+            token_code_id: 0,
+            pool_name: "test".to_string(),
+            treasury: None,
+            withdraw_fee: None,
+            unbond_fee: None,
+            unbond_fee_decay: None,
+        };
+        let distribution_account = Addr::unchecked(DISTRIBUTION_ACCOUNT);
+        self.app.instantiate_contract(farm_code_id, distribution_account, &msg, &[], "farm", None).unwrap()
+    }
+
+    fn migrate_staking(&mut self, new_farm: &Addr) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            Addr::unchecked(DISTRIBUTION_ACCOUNT),
+            self.farm.clone(),
+            &ExecuteMsg::MigrateStaking { new_staking_contract: new_farm.to_string() },
+            &[],
+        )
+    }
+
+    fn bond_locked(&mut self, staker: usize, amount: u128, lock_duration: Option<u64>) -> AnyResult<AppResponse> {
+        let staker = self.stakers[staker].clone();
+        match &self.staking_asset {
+            AssetInfo::Native { denom } => self.app.execute_contract(
+                staker,
+                self.farm.clone(),
+                &ExecuteMsg::Bond { lock_duration, recipient: None },
+                &coins(amount, denom),
+            ),
+            AssetInfo::Cw20 { address } => self.app.execute_contract(
+                staker,
+                address.clone(),
+                &Cw20ExecuteMsg::Send {
+                    contract: self.farm.to_string(),
+                    amount: Uint128::new(amount),
+                    msg: to_binary(&Cw20HookMsg::Bond { lock_duration, recipient: None })?,
+                },
+                &[],
+            ),
+        }
+    }
+
+    fn unbond_lock(&mut self, staker: usize, amount: u128, lock_id: Option<u64>) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            self.stakers[staker].clone(),
+            self.lp_token.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: self.farm.to_string(),
+                amount: Uint128::new(amount),
+                msg: to_binary(&Cw20HookMsg::Unbond { lock_id })?,
+            },
+            &[],
+        )
+    }
+
+    fn staker_info(&self, staker: usize) -> StakerInfoResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                &self.farm,
+                &QueryMsg::StakerInfo {
+                    staker: self.stakers[staker].to_string(),
+                    time: Some(self.app.block_info().time.seconds()),
+                },
+            )
+            .unwrap()
+    }
+
+    fn claims(&self, staker: usize) -> Vec<Claim> {
+        let res: ClaimsResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(&self.farm, &QueryMsg::Claims { address: self.stakers[staker].to_string() })
+            .unwrap();
+        res.claims
+    }
+
+    fn claim(&mut self, staker: usize) -> AnyResult<AppResponse> {
+        self.app.execute_contract(self.stakers[staker].clone(), self.farm.clone(), &ExecuteMsg::Claim {}, &[])
+    }
+
+    fn funding(&self) -> Vec<RewardFundingResponse> {
+        let res: FundingResponse = self.app.wrap().query_wasm_smart(&self.farm, &QueryMsg::Funding {}).unwrap();
+        res.rewards
+    }
+
+    fn reward_balance(&self, address: &Addr) -> AnyResult<Uint128> {
+        self.balance(&self.reward_asset, address)
+    }
+
+    fn balance(&self, asset: &AssetInfo, address: &Addr) -> AnyResult<Uint128> {
+        match asset {
+            AssetInfo::Native { denom } => Ok(self.app.wrap().query_balance(address, denom)?.amount),
+            AssetInfo::Cw20 { address: token } => {
+                let res: BalanceResponse = self.app.wrap().query_wasm_smart(
+                    token,
+                    &Cw20QueryMsg::Balance { address: address.to_string() },
+                )?;
+                Ok(res.balance)
+            }
+        }
+    }
+}
+
+impl FarmHarness for Farm {
+    fn stakers(&self) -> usize {
+        self.stakers.len()
+    }
+
+    fn app(&mut self) -> &mut CyberApp {
+        &mut self.app
+    }
+
+    fn clock(&mut self) -> u64 {
+        self.app.block_info().time.seconds()
+    }
+
+    fn bond(&mut self, staker: usize, amount: u128) -> AnyResult<()> {
+        self.bond_locked(staker, amount, None)?;
+        Ok(())
+    }
+
+    fn unbond(&mut self, staker: usize, amount: u128) -> AnyResult<()> {
+        self.unbond_lock(staker, amount, None)?;
+        Ok(())
+    }
+
+    fn withdraw(&mut self, staker: usize) -> AnyResult<u128> {
+        let staker = self.stakers[staker].clone();
+        let before = self.reward_balance(&staker)?;
+        self.app.execute_contract(staker.clone(), self.farm.clone(), &ExecuteMsg::Withdraw { to: None, investmint: None }, &[])?;
+        let after = self.reward_balance(&staker)?;
+        Ok((after - before).u128())
+    }
+
+    fn add_distribution_period(&mut self, period: (u64, u64, u128)) -> AnyResult<()> {
+        let reward_asset = self.reward_asset.clone();
+        let periods = vec![(period.0, period.1, Uint128::new(period.2))];
+        self.add_periods(&reward_asset, periods, period.2)?;
+        Ok(())
+    }
+
+    fn staker_position(&mut self, staker: usize) -> AnyResult<StakerPosition> {
+        let time = self.clock();
+        let info: StakerInfoResponse = self.app.wrap().query_wasm_smart(
+            &self.farm,
+            &QueryMsg::StakerInfo {
+                staker: self.stakers[staker].to_string(),
+                time: Some(time),
+            },
+        )?;
+        Ok(StakerPosition {
+            bond_amount: info.bond_amount.u128(),
+            pending_reward: info.rewards[0].pending_reward.u128(),
+        })
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn rewards_follow_schedule_nv_nv(actions in farm_scenario(STAKERS, 40)) {
+        run_farm_scenario(&mut Farm::new(false, false), &actions)?;
+    }
+
+    #[test]
+    fn rewards_follow_schedule_nv_20(actions in farm_scenario(STAKERS, 40)) {
+        run_farm_scenario(&mut Farm::new(false, true), &actions)?;
+    }
+
+    #[test]
+    fn rewards_follow_schedule_20_nv(actions in farm_scenario(STAKERS, 40)) {
+        run_farm_scenario(&mut Farm::new(true, false), &actions)?;
+    }
+
+    #[test]
+    fn rewards_follow_schedule_20_20(actions in farm_scenario(STAKERS, 40)) {
+        run_farm_scenario(&mut Farm::new(true, true), &actions)?;
+    }
+}
+
+#[test]
+fn bond_rejects_other_assets() {
+    let mut farm = Farm::new(false, false);
+    let staker = farm.stakers[0].clone();
+    let err = farm.app
+        .execute_contract(staker.clone(), farm.farm.clone(), &ExecuteMsg::Bond { lock_duration: None, recipient: None }, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::FailBond {}, err.downcast().unwrap());
+
+    let mut farm = Farm::new(true, false);
+    let err = farm.app
+        .execute_contract(staker, farm.farm.clone(), &ExecuteMsg::Bond { lock_duration: None, recipient: None }, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::InvalidStakingFunds {}, err.downcast().unwrap());
+}
+
+#[test]
+fn withdraw_pays_all_reward_assets() {
+    let mut farm = Farm::new(false, true);
+    let staker = farm.stakers[0].clone();
+    let time = farm.clock();
+
+    farm.add_distribution_period((time, time + 100, 500)).unwrap();
+    let second_asset = AssetInfo::Native { denom: SECOND_REWARD_DENOM.to_string() };
+    farm.add_periods(&second_asset, vec![(time + 50, time + 150, Uint128::new(1000))], 1000)
+        .unwrap();
+
+    farm.bond(0, 100).unwrap();
+    farm.app.advance_seconds(100);
+
+    let info: StakerInfoResponse = farm.app
+        .wrap()
+        .query_wasm_smart(
+            &farm.farm,
+            &QueryMsg::StakerInfo {
+                staker: staker.to_string(),
+                time: Some(time + 100),
+            },
+        )
+        .unwrap();
+    assert_eq!(info.rewards.len(), 2);
+    assert_eq!(info.rewards[0].asset, farm.reward_asset);
+    assert_eq!(info.rewards[0].pending_reward, Uint128::new(500));
+    assert_eq!(info.rewards[1].asset, second_asset);
+    assert_eq!(info.rewards[1].pending_reward, Uint128::new(500));
+
+    farm.app
+        .execute_contract(staker.clone(), farm.farm.clone(), &ExecuteMsg::Withdraw { to: None, investmint: None }, &[])
+        .unwrap();
+    assert_eq!(farm.reward_balance(&staker).unwrap(), Uint128::new(500));
+    assert_eq!(farm.balance(&second_asset, &staker).unwrap(), Uint128::new(500));
+
+    // only the second reward asset is distributed further
+    farm.app.advance_seconds(100);
+    farm.app
+        .execute_contract(staker.clone(), farm.farm.clone(), &ExecuteMsg::Withdraw { to: None, investmint: None }, &[])
+        .unwrap();
+    assert_eq!(farm.reward_balance(&staker).unwrap(), Uint128::new(500));
+    assert_eq!(farm.balance(&second_asset, &staker).unwrap(), Uint128::new(1000));
+}
+
+#[test]
+fn bond_for_recipient_and_withdraw_to_other_address() {
+    let mut farm = Farm::new(false, false);
+    let (sender, staker, withdrawer) = (farm.stakers[0].clone(), farm.stakers[1].clone(), farm.stakers[2].clone());
+    let time = farm.clock();
+    farm.add_distribution_period((time, time + 200, 1000)).unwrap();
+
+    farm.app
+        .execute_contract(
+            sender.clone(),
+            farm.farm.clone(),
+            &ExecuteMsg::Bond { lock_duration: None, recipient: Some(staker.to_string()) },
+            &coins(100, STAKING_DENOM),
+        )
+        .unwrap();
+    assert_eq!(farm.staker_info(0).bond_amount, Uint128::zero());
+    assert_eq!(farm.staker_info(1).bond_amount, Uint128::new(100));
+    let lp_token = AssetInfo::Cw20 { address: farm.lp_token.clone() };
+    assert_eq!(farm.balance(&lp_token, &sender).unwrap(), Uint128::zero());
+    assert_eq!(farm.balance(&lp_token, &staker).unwrap(), Uint128::new(100));
+
+    farm.app
+        .execute_contract(
+            staker.clone(),
+            farm.farm.clone(),
+            &ExecuteMsg::SetWithdrawAddress { address: Some(withdrawer.to_string()) },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(farm.staker_info(1).withdraw_address, Some(withdrawer.to_string()));
+
+    farm.app.advance_seconds(100);
+    farm.app
+        .execute_contract(staker.clone(), farm.farm.clone(), &ExecuteMsg::Withdraw { to: None, investmint: None }, &[])
+        .unwrap();
+    assert_eq!(farm.reward_balance(&withdrawer).unwrap(), Uint128::new(500));
+    assert_eq!(farm.reward_balance(&staker).unwrap(), Uint128::zero());
+
+    // explicit address overrides the withdraw address
+    farm.app.advance_seconds(50);
+    farm.app
+        .execute_contract(
+            staker.clone(),
+            farm.farm.clone(),
+            &ExecuteMsg::Withdraw { to: Some(sender.to_string()), investmint: None },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(farm.reward_balance(&sender).unwrap(), Uint128::new(250));
+
+    // resetting the withdraw address pays the staker again
+    farm.app
+        .execute_contract(
+            staker.clone(),
+            farm.farm.clone(),
+            &ExecuteMsg::SetWithdrawAddress { address: None },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(farm.staker_info(1).withdraw_address, None);
+    farm.app.advance_seconds(50);
+    farm.app
+        .execute_contract(staker.clone(), farm.farm.clone(), &ExecuteMsg::Withdraw { to: None, investmint: None }, &[])
+        .unwrap();
+    assert_eq!(farm.reward_balance(&staker).unwrap(), Uint128::new(250));
+}
+
+#[test]
+fn fees_are_sent_to_treasury() {
+    let treasury = Addr::unchecked("treasury");
+    let mut farm = Farm::with_config(false, false, |msg| {
+        msg.treasury = Some("treasury".to_string());
+        msg.withdraw_fee = Some(Decimal::percent(10));
+        msg.unbond_fee = Some(Decimal::percent(4));
+        msg.unbond_fee_decay = Some(100);
+    });
+    let staker = farm.stakers[0].clone();
+    let staking_asset = farm.staking_asset.clone();
+    let time = farm.clock();
+    farm.add_distribution_period((time, time + 100, 1000)).unwrap();
+    farm.bond(0, 100).unwrap();
+
+    // withdraw fee is taken from the rewards
+    farm.app.advance_seconds(50);
+    assert_eq!(farm.withdraw(0).unwrap(), 450);
+    assert_eq!(farm.reward_balance(&treasury).unwrap(), Uint128::new(50));
+
+    // unbond fee has decayed by half after half of the decay period
+    let before = farm.balance(&staking_asset, &staker).unwrap();
+    farm.unbond_lock(0, 100, None).unwrap();
+    assert_eq!(farm.balance(&staking_asset, &staker).unwrap() - before, Uint128::new(98));
+    assert_eq!(farm.balance(&staking_asset, &treasury).unwrap(), Uint128::new(2));
+
+    // only admin updates the fees, constant unbond fee without decay
+    let update = ExecuteMsg::UpdateFees {
+        treasury: None,
+        withdraw_fee: None,
+        unbond_fee: Some(Decimal::percent(5)),
+        unbond_fee_decay: Some(0),
+    };
+    let err = farm.app.execute_contract(staker.clone(), farm.farm.clone(), &update, &[]).unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    let admin = Addr::unchecked(DISTRIBUTION_ACCOUNT);
+    farm.app.execute_contract(admin.clone(), farm.farm.clone(), &update, &[]).unwrap();
+    let invalid = ExecuteMsg::UpdateFees {
+        treasury: None,
+        withdraw_fee: Some(Decimal::one()),
+        unbond_fee: None,
+        unbond_fee_decay: None,
+    };
+    let err = farm.app.execute_contract(admin, farm.farm.clone(), &invalid, &[]).unwrap_err();
+    assert_eq!(ContractError::InvalidFee { fee: Decimal::one() }, err.downcast().unwrap());
+
+    let config: ConfigResponse = farm.app.wrap().query_wasm_smart(&farm.farm, &QueryMsg::Config {}).unwrap();
+    assert_eq!(config.treasury, Some(treasury.to_string()));
+    assert_eq!(config.withdraw_fee, Decimal::percent(10));
+    assert_eq!(config.unbond_fee, Decimal::percent(5));
+    assert_eq!(config.unbond_fee_decay, None);
+
+    farm.bond(0, 100).unwrap();
+    farm.unbond_lock(0, 100, None).unwrap();
+    assert_eq!(farm.balance(&staking_asset, &treasury).unwrap(), Uint128::new(7));
+}
+
+#[test]
+fn historical_queries_use_checkpoints() {
+    let mut farm = Farm::new(false, false);
+    let (first, second) = (farm.stakers[0].to_string(), farm.stakers[1].to_string());
+    let time = farm.clock();
+    farm.add_distribution_period((time, time + 100, 1000)).unwrap();
+    farm.bond(0, 100).unwrap();
+    farm.app.advance_seconds(40);
+    farm.bond(1, 100).unwrap();
+    farm.app.advance_seconds(20);
+    farm.unbond(0, 100).unwrap();
+    farm.app.advance_seconds(40);
+
+    let staker_at = |farm: &Farm, staker: &str, time: u64| -> StakerInfoResponse {
+        farm.app
+            .wrap()
+            .query_wasm_smart(&farm.farm, &QueryMsg::StakerInfo { staker: staker.to_string(), time: Some(time) })
+            .unwrap()
+    };
+    let state_at = |farm: &Farm, time: u64| -> StateResponse {
+        farm.app.wrap().query_wasm_smart(&farm.farm, &QueryMsg::State { time: Some(time) }).unwrap()
+    };
+
+    let info = staker_at(&farm, &first, time + 40);
+    assert_eq!((info.bond_amount, info.rewards[0].pending_reward), (Uint128::new(100), Uint128::new(400)));
+    let info = staker_at(&farm, &first, time + 50);
+    assert_eq!((info.bond_amount, info.rewards[0].pending_reward), (Uint128::new(100), Uint128::new(450)));
+    let info = staker_at(&farm, &first, time + 80);
+    assert_eq!((info.bond_amount, info.rewards[0].pending_reward), (Uint128::zero(), Uint128::new(500)));
+    let info = staker_at(&farm, &second, time + 20);
+    assert_eq!((info.bond_amount, info.rewards[0].pending_reward), (Uint128::zero(), Uint128::zero()));
+    let info = staker_at(&farm, &second, time + 80);
+    assert_eq!((info.bond_amount, info.rewards[0].pending_reward), (Uint128::new(100), Uint128::new(300)));
+
+    let state = state_at(&farm, time + 50);
+    assert_eq!(state.total_bond_amount, Uint128::new(200));
+    assert_eq!(state.rewards[0].global_reward_index, Decimal::percent(450));
+    let state = state_at(&farm, time + 70);
+    assert_eq!(state.total_bond_amount, Uint128::new(100));
+    assert_eq!(state.rewards[0].global_reward_index, Decimal::percent(600));
+
+    // farm has no history before its instantiation
+    let res: AnyResult<StateResponse> = farm
+        .app
+        .wrap()
+        .query_wasm_smart(&farm.farm, &QueryMsg::State { time: Some(time - 1) })
+        .map_err(Into::into);
+    assert!(res.is_err());
+}
+
+#[test]
+fn historical_queries_keep_the_schedule_and_stakers_of_their_time() {
+    let mut farm = Farm::new(false, false);
+    let reward_asset = farm.reward_asset.clone();
+    let time = farm.clock();
+    farm.add_distribution_period((time, time + 300, 1000)).unwrap();
+    farm.bond(0, 100).unwrap();
+    farm.bond(1, 100).unwrap();
+    farm.app.advance_seconds(100);
+
+    let history = |farm: &Farm| -> (StateResponse, AllStakersResponse, RewardRateResponse) {
+        let querier = farm.app.wrap();
+        let mut stakers: AllStakersResponse = querier
+            .query_wasm_smart(&farm.farm, &QueryMsg::AllStakers { start_after: None, limit: None, time: Some(time + 50) })
+            .unwrap();
+        // locks are reported as they are now
+        stakers.stakers.iter_mut().for_each(|staker| staker.locks.clear());
+        (
+            querier.query_wasm_smart(&farm.farm, &QueryMsg::State { time: Some(time + 50) }).unwrap(),
+            stakers,
+            querier.query_wasm_smart(&farm.farm, &QueryMsg::RewardRate { time: Some(time + 50) }).unwrap(),
+        )
+    };
+    let before = history(&farm);
+    assert_eq!(before.1.stakers.len(), 2);
+    assert_eq!(before.2.rewards[0].emission_per_second, Decimal::from_ratio(1000u128, 300u128));
+
+    // the period is cut after the time and the second staker leaves the farm
+    farm.app
+        .execute_contract(
+            Addr::unchecked(DISTRIBUTION_ACCOUNT),
+            farm.farm.clone(),
+            &ExecuteMsg::RemoveDistributionPeriod { reward_asset, start: time },
+            &[],
+        )
+        .unwrap();
+    farm.unbond(1, 100).unwrap();
+    farm.withdraw(1).unwrap();
+    let res: AllStakersResponse = farm
+        .app
+        .wrap()
+        .query_wasm_smart(&farm.farm, &QueryMsg::AllStakers { start_after: None, limit: None, time: None })
+        .unwrap();
+    assert_eq!(res.stakers.len(), 1);
+
+    assert_eq!(history(&farm), before);
+    let res: AllStakersResponse = farm
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &farm.farm,
+            &QueryMsg::AllStakers { start_after: Some(before.1.stakers[0].staker.clone()), limit: Some(1), time: Some(time + 50) },
+        )
+        .unwrap();
+    assert_eq!(res.stakers[0].staker, before.1.stakers[1].staker);
+    assert_eq!(res.stakers[0].rewards, before.1.stakers[1].rewards);
+}
+
+#[test]
+fn locked_bonds_are_boosted() {
+    const DAY: u64 = 24 * 60 * 60;
+    let tiers = vec![
+        LockupTier { duration: 0, multiplier: Decimal::one() },
+        LockupTier { duration: 30 * DAY, multiplier: Decimal::percent(150) },
+        LockupTier { duration: 90 * DAY, multiplier: Decimal::percent(200) },
+    ];
+    let mut farm = Farm::with_config(true, false, |msg| msg.lockup_tiers = Some(tiers));
+    let time = farm.clock();
+    farm.add_distribution_period((time, time + 1000, 2500)).unwrap();
+
+    let err = farm.bond_locked(1, 100, Some(7 * DAY)).unwrap_err();
+    assert_eq!(ContractError::InvalidLockDuration { duration: 7 * DAY }, err.downcast().unwrap());
+
+    farm.bond_locked(0, 100, None).unwrap();
+    farm.bond_locked(1, 100, Some(30 * DAY)).unwrap();
+    farm.app.advance_seconds(1000);
+    assert_eq!(farm.staker_info(0).weight, Uint128::new(100));
+    assert_eq!(farm.staker_info(1).weight, Uint128::new(150));
+    assert_eq!(farm.withdraw(0).unwrap(), 1000);
+    assert_eq!(farm.withdraw(1).unwrap(), 1500);
+
+    // unlocked bonds join a single lock, locked bonds get their own locks
+    farm.bond_locked(0, 10, None).unwrap();
+    farm.bond_locked(1, 50, None).unwrap();
+    // unlocked bond starts at the average time of its bonds
+    let unlocked = farm.staker_info(0).locks;
+    assert_eq!(unlocked.len(), 1);
+    assert_eq!(unlocked[0].bond_time, time + 90);
+    assert_eq!(unlocked[0].unlock_time, time + 90);
+    let locks = farm.staker_info(1).locks;
+    assert_eq!(locks.len(), 2);
+    assert_eq!(locks[0].amount, Uint128::new(100));
+    assert_eq!(locks[0].bond_time, time);
+    assert_eq!(locks[0].unlock_time, time + 30 * DAY);
+    assert_eq!(locks[1].amount, Uint128::new(50));
+
+    let err = farm.unbond_lock(1, 100, None).unwrap_err();
+    assert_eq!(ContractError::LockedBond {}, err.downcast().unwrap());
+    let err = farm.unbond_lock(1, 100, Some(locks[0].id)).unwrap_err();
+    assert_eq!(ContractError::LockedBond {}, err.downcast().unwrap());
+    let err = farm.unbond_lock(1, 10, Some(42)).unwrap_err();
+    assert_eq!(ContractError::LockNotFound { id: 42 }, err.downcast().unwrap());
+    farm.unbond_lock(1, 50, None).unwrap();
+
+    farm.app.advance_seconds(30 * DAY);
+    farm.unbond_lock(1, 100, Some(locks[0].id)).unwrap();
+    let info = farm.staker_info(1);
+    assert_eq!(info.bond_amount, Uint128::zero());
+    assert_eq!(info.weight, Uint128::zero());
+    assert!(info.locks.is_empty());
+}
+
+#[test]
+fn lock_boost_ends_at_unlock_time() {
+    let tiers = vec![
+        LockupTier { duration: 0, multiplier: Decimal::one() },
+        LockupTier { duration: 100, multiplier: Decimal::percent(300) },
+    ];
+    let mut farm = Farm::with_config(false, false, |msg| msg.lockup_tiers = Some(tiers));
+    let time = farm.clock();
+    farm.add_distribution_period((time, time + 400, 4000)).unwrap();
+    farm.bond_locked(0, 100, None).unwrap();
+    farm.bond_locked(1, 100, Some(100)).unwrap();
+    farm.app.advance_seconds(300);
+
+    // 10 per second by the weight of 100 and 300 up to the unlock time, by 100 and 100 after it
+    let info = farm.staker_info(1);
+    assert_eq!(info.weight, Uint128::new(100));
+    assert_eq!(info.rewards[0].pending_reward, Uint128::new(1750));
+    let state: StateResponse = farm
+        .app
+        .wrap()
+        .query_wasm_smart(&farm.farm, &QueryMsg::State { time: Some(time + 300) })
+        .unwrap();
+    assert_eq!(state.total_weight, Uint128::new(200));
+    let info: StakerInfoResponse = farm
+        .app
+        .wrap()
+        .query_wasm_smart(&farm.farm, &QueryMsg::StakerInfo { staker: farm.stakers[1].to_string(), time: Some(time + 50) })
+        .unwrap();
+    assert_eq!(info.weight, Uint128::new(300));
+
+    assert_eq!(farm.withdraw(0).unwrap(), 1250);
+    assert_eq!(farm.withdraw(1).unwrap(), 1750);
+    farm.app.advance_seconds(100);
+    assert_eq!(farm.withdraw(0).unwrap(), 500);
+    assert_eq!(farm.withdraw(1).unwrap(), 500);
+}
+
+#[test]
+fn historical_queries_replay_the_expiring_weights_of_their_time() {
+    let tiers = vec![
+        LockupTier { duration: 0, multiplier: Decimal::one() },
+        LockupTier { duration: 100, multiplier: Decimal::percent(300) },
+    ];
+    let mut farm = Farm::with_config(false, false, |msg| {
+        msg.admin = Some("admin".to_string());
+        msg.lockup_tiers = Some(tiers);
+    });
+    let time = farm.clock();
+    farm.add_distribution_period((time, time + 1000, 1000)).unwrap();
+    farm.bond_locked(0, 100, Some(100)).unwrap();
+    farm.bond_locked(1, 100, None).unwrap();
+    farm.app.execute_contract(Addr::unchecked("admin"), farm.farm.clone(), &ExecuteMsg::Pause {}, &[]).unwrap();
+    farm.app.advance_seconds(200);
+
+    // the boost of the lock ends at its unlock time between the last distribution and the time
+    let history = |farm: &Farm| -> (StateResponse, StakerInfoResponse) {
+        let querier = farm.app.wrap();
+        let mut info: StakerInfoResponse = querier
+            .query_wasm_smart(&farm.farm, &QueryMsg::StakerInfo { staker: farm.stakers[0].to_string(), time: Some(time + 150) })
+            .unwrap();
+        // locks are reported as they are now
+        info.locks.clear();
+        (querier.query_wasm_smart(&farm.farm, &QueryMsg::State { time: Some(time + 150) }).unwrap(), info)
+    };
+    let before = history(&farm);
+    assert_eq!(before.0.total_weight, Uint128::new(200));
+    assert_eq!(before.1.weight, Uint128::new(100));
+    assert_eq!(before.1.rewards[0].pending_reward, Uint128::new(100));
+
+    // emergency unbond removes the expiring weight of the lock without a distribution
+    farm.app
+        .execute_contract(
+            farm.stakers[0].clone(),
+            farm.lp_token.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: farm.farm.to_string(),
+                amount: Uint128::new(100),
+                msg: to_binary(&Cw20HookMsg::EmergencyUnbond {}).unwrap(),
+            },
+            &[],
+        )
+        .unwrap();
+    assert_eq!(history(&farm), before);
+}
+
+#[test]
+fn unbonded_amount_is_claimed_after_unbonding_period() {
+    const DAY: u64 = 24 * 60 * 60;
+    let mut farm = Farm::with_config(false, false, |msg| msg.unbonding_period = Some(7 * DAY));
+    let time = farm.clock();
+    farm.add_distribution_period((time, time + 1000, 1000)).unwrap();
+    let staker = farm.stakers[0].clone();
+    let staking_asset = farm.staking_asset.clone();
+    let balance = farm.balance(&staking_asset, &staker).unwrap();
+
+    farm.bond_locked(0, 100, None).unwrap();
+    farm.bond_locked(1, 100, None).unwrap();
+    farm.app.advance_seconds(100);
+    let unbond_time = farm.app.block_info().time;
+    farm.unbond_lock(0, 60, None).unwrap();
+    assert_eq!(farm.withdraw(0).unwrap(), 50);
+    farm.app.advance_seconds(DAY);
+    farm.unbond_lock(0, 40, None).unwrap();
+
+    // unbonded amount waits for the release and earns no rewards, 40 of 140 earns 900
+    assert_eq!(farm.balance(&staking_asset, &staker).unwrap(), balance - Uint128::new(100));
+    assert_eq!(farm.staker_info(0).bond_amount, Uint128::zero());
+    assert_eq!(farm.withdraw(0).unwrap(), 257);
+    let claims = farm.claims(0);
+    assert_eq!(claims.len(), 2);
+    assert_eq!(claims[0].amount, Uint128::new(60));
+    assert_eq!(claims[0].release_at, Expiration::AtTime(unbond_time.plus_seconds(7 * DAY)));
+
+    let err = farm.claim(0).unwrap_err();
+    assert_eq!(ContractError::NothingToClaim {}, err.downcast().unwrap());
+
+    farm.app.advance_seconds(6 * DAY);
+    farm.claim(0).unwrap();
+    assert_eq!(farm.balance(&staking_asset, &staker).unwrap(), balance - Uint128::new(40));
+    assert_eq!(farm.claims(0).len(), 1);
+
+    farm.app.advance_seconds(DAY);
+    farm.claim(0).unwrap();
+    assert_eq!(farm.balance(&staking_asset, &staker).unwrap(), balance);
+    assert!(farm.claims(0).is_empty());
+}
+
+#[test]
+fn reward_in_staking_asset_is_compounded() {
+    let mut farm = Farm::new(false, false);
+    let time = farm.clock();
+    let staking_asset = farm.staking_asset.clone();
+    let lp_token = AssetInfo::Cw20 { address: farm.lp_token.clone() };
+    let staker = farm.stakers[0].clone();
+
+    farm.bond_locked(0, 100, None).unwrap();
+    let err = farm.app.execute_contract(staker.clone(), farm.farm.clone(), &ExecuteMsg::Compound {}, &[]).unwrap_err();
+    assert_eq!(ContractError::CannotCompound {}, err.downcast().unwrap());
+
+    farm.add_periods(&staking_asset, vec![(time, time + 100, Uint128::new(1000))], 1000).unwrap();
+    farm.app.advance_seconds(100);
+    farm.app.execute_contract(staker.clone(), farm.farm.clone(), &ExecuteMsg::Compound {}, &[]).unwrap();
+    let info = farm.staker_info(0);
+    assert_eq!(info.bond_amount, Uint128::new(1100));
+    assert_eq!(info.rewards[1].pending_reward, Uint128::zero());
+    assert_eq!(farm.balance(&lp_token, &staker).unwrap(), Uint128::new(1100));
+
+    let err = farm.app.execute_contract(staker, farm.farm.clone(), &ExecuteMsg::Compound {}, &[]).unwrap_err();
+    assert_eq!(ContractError::NothingToCompound {}, err.downcast().unwrap());
+}
+
+#[test]
+fn auto_compound_on_every_interaction() {
+    let mut farm = Farm::with_config(false, false, |msg| {
+        msg.auto_compound = Some(true);
+        msg.rewards.push(RewardAsset {
+            asset: msg.staking_asset.clone(),
+            distribution_schedule: vec![],
+        });
+    });
+    let time = farm.clock();
+    let staking_asset = farm.staking_asset.clone();
+    let reward_asset = farm.reward_asset.clone();
+    let lp_token = AssetInfo::Cw20 { address: farm.lp_token.clone() };
+
+    farm.bond_locked(0, 100, None).unwrap();
+    farm.bond_locked(1, 100, None).unwrap();
+    farm.add_periods(&staking_asset, vec![(time, time + 100, Uint128::new(1000))], 1000).unwrap();
+    farm.add_periods(&reward_asset, vec![(time, time + 100, Uint128::new(500))], 500).unwrap();
+    farm.app.advance_seconds(100);
+
+    // withdraw pays other reward assets and compounds the staking asset
+    assert_eq!(farm.withdraw(0).unwrap(), 250);
+    assert_eq!(farm.staker_info(0).bond_amount, Uint128::new(600));
+    assert_eq!(farm.balance(&lp_token, &farm.stakers[0]).unwrap(), Uint128::new(600));
+
+    farm.unbond_lock(1, 100, None).unwrap();
+    let info = farm.staker_info(1);
+    assert_eq!(info.bond_amount, Uint128::new(500));
+    assert_eq!(info.rewards[1].pending_reward, Uint128::zero());
+    assert_eq!(farm.balance(&lp_token, &farm.stakers[1]).unwrap(), Uint128::new(500));
+}
+
+#[test]
+fn stakers_are_listed_with_reward_rate() {
+    const DAY: u64 = 24 * 60 * 60;
+    let tiers = vec![
+        LockupTier { duration: 0, multiplier: Decimal::one() },
+        LockupTier { duration: 30 * DAY, multiplier: Decimal::percent(300) },
+    ];
+    let mut farm = Farm::with_config(false, false, |msg| msg.lockup_tiers = Some(tiers));
+    let time = farm.clock();
+    farm.add_distribution_period((time + 100, time + 200, 4000)).unwrap();
+    for staker in 0..STAKERS {
+        farm.bond_locked(staker, 100, None).unwrap();
+    }
+    farm.bond_locked(0, 100, Some(30 * DAY)).unwrap();
+    farm.app.advance_seconds(150);
+
+    let query_all = |farm: &Farm, start_after: Option<String>| -> AllStakersResponse {
+        farm.app
+            .wrap()
+            .query_wasm_smart(
+                &farm.farm,
+                &QueryMsg::AllStakers { start_after, limit: Some(2), time: Some(farm.app.block_info().time.seconds()) },
+            )
+            .unwrap()
+    };
+    let page = query_all(&farm, None);
+    assert_eq!(page.stakers.len(), 2);
+    assert_eq!(page.stakers[0], farm.staker_info(0));
+    assert_eq!(page.stakers[1], farm.staker_info(1));
+    assert_eq!(page.next, Some(page.stakers[1].staker.clone()));
+    let page = query_all(&farm, page.next);
+    assert_eq!(page.stakers, vec![farm.staker_info(2)]);
+    assert_eq!(page.next, None);
+
+    // 40 per second by the total weight of 100 * 3 + 100 * 3
+    let rate: RewardRateResponse =
+        farm.app.wrap().query_wasm_smart(&farm.farm, &QueryMsg::RewardRate { time: None }).unwrap();
+    assert_eq!(rate.total_weight, Uint128::new(600));
+    assert_eq!(rate.rewards[0].emission_per_second, Decimal::from_ratio(40u128, 1u128));
+    assert_eq!(rate.rewards[0].reward_per_weight, Decimal::from_ratio(1u128, 15u128));
+    let rate: RewardRateResponse =
+        farm.app.wrap().query_wasm_smart(&farm.farm, &QueryMsg::RewardRate { time: Some(time + 200) }).unwrap();
+    assert_eq!(rate.rewards[0].emission_per_second, Decimal::zero());
+}
+
+#[test]
+fn positions_are_migrated_to_new_farm() {
+    const DAY: u64 = 24 * 60 * 60;
+    let tiers = vec![
+        LockupTier { duration: 0, multiplier: Decimal::one() },
+        LockupTier { duration: 30 * DAY, multiplier: Decimal::percent(200) },
+    ];
+    let mut farm = Farm::with_config(false, false, |msg| msg.lockup_tiers = Some(tiers.clone()));
+    let time = farm.clock();
+    let old_farm = farm.farm.clone();
+    let lp_token = AssetInfo::Cw20 { address: farm.lp_token.clone() };
+    let distribution_account = Addr::unchecked(DISTRIBUTION_ACCOUNT);
+    farm.add_distribution_period((time, time + 1000, 3000)).unwrap();
+    farm.bond_locked(0, 100, None).unwrap();
+    farm.bond_locked(1, 50, Some(30 * DAY)).unwrap();
+    farm.bond_locked(2, 100, None).unwrap();
+    farm.unbond_lock(2, 100, None).unwrap();
+    farm.app.advance_seconds(100);
+
+    let farm_code_id = farm.app.store_code(contract_farm());
+    let new_farm = farm
+        .app
+        .instantiate_contract(
+            farm_code_id,
+            distribution_account.clone(),
+            &InstantiateMsg {
+                admin: None,
+            distribution_account: DISTRIBUTION_ACCOUNT.to_string(),
+                staking_asset: farm.staking_asset.clone(),
+                pool_id: None,
+                rewards: vec![],
+                lockup_tiers: Some(vec![
+                    LockupTier { duration: 0, multiplier: Decimal::one() },
+                    LockupTier { duration: 30 * DAY, multiplier: Decimal::percent(300) },
+                ]),
+                unbonding_period: None,
+                auto_compound: None,
+                migrate_from: Some(old_farm.to_string()),
+                token_code_id: 0,
+                pool_name: "test".to_string(),
+                treasury: None,
+                withdraw_fee: None,
+                unbond_fee: None,
+                unbond_fee_decay: None,
+            },
+            &[],
+            "farm",
+            None,
+        )
+        .unwrap();
+    let config: ConfigResponse = farm.app.wrap().query_wasm_smart(&new_farm, &QueryMsg::Config {}).unwrap();
+    assert_eq!(config.lp_token, farm.lp_token.to_string());
+
+    farm.app
+        .execute_contract(
+            distribution_account,
+            old_farm.clone(),
+            &ExecuteMsg::MigrateStaking { new_staking_contract: new_farm.to_string() },
+            &[],
+        )
+        .unwrap();
+    let err = farm.bond_locked(2, 100, None).unwrap_err();
+    assert_eq!(ContractError::FarmMigrated { new_farm: new_farm.to_string() }, err.downcast().unwrap());
+    let err = farm.unbond_lock(0, 100, None).unwrap_err();
+    assert_eq!(ContractError::FarmMigrated { new_farm: new_farm.to_string() }, err.downcast().unwrap());
+    let err = farm
+        .app
+        .execute_contract(farm.stakers[0].clone(), old_farm.clone(), &ExecuteMsg::ExportPositions { limit: None }, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+
+    // remaining part of the distribution period is carried over to the new farm with its funds
+    assert_eq!(farm.funding()[0].funded, Uint128::new(300));
+    let farm_config: ConfigResponse = farm.app.wrap().query_wasm_smart(&new_farm, &QueryMsg::Config {}).unwrap();
+    assert_eq!(farm_config.rewards[0].distribution_schedule, vec![(time + 100, time + 1000, Uint128::new(2700))]);
+    assert_eq!(farm.balance(&farm_config.rewards[0].asset, &new_farm).unwrap(), Uint128::new(2700));
+
+    // positions are pulled page by page, the staker without bond has nothing to import
+    let pull = |farm: &mut Farm| {
+        let staker = farm.stakers[2].clone();
+        farm.app
+            .execute_contract(staker, new_farm.clone(), &ExecuteMsg::PullPositions { limit: Some(2) }, &[])
+            .unwrap();
+    };
+    pull(&mut farm);
+    farm.farm = new_farm.clone();
+    assert_eq!(farm.staker_info(0).bond_amount, Uint128::new(100));
+    let info = farm.staker_info(1);
+    assert_eq!(info.bond_amount, Uint128::new(50));
+    // the lock is boosted by the lockup tier of the new farm
+    assert_eq!(info.weight, Uint128::new(150));
+    assert_eq!(info.locks[0].multiplier, Decimal::percent(300));
+    assert_eq!(info.locks[0].unlock_time, time + 30 * DAY);
+    pull(&mut farm);
+    assert_eq!(farm.staker_info(2).bond_amount, Uint128::zero());
+    assert_eq!(farm.balance(&farm.staking_asset.clone(), &new_farm).unwrap(), Uint128::new(150));
+    farm.app.advance_seconds(100);
+
+    // settled rewards are withdrawn from the old farm, 100 seconds by weight of 100 and 100
+    farm.farm = old_farm.clone();
+    assert_eq!(farm.staker_info(0).bond_amount, Uint128::zero());
+    assert_eq!(farm.withdraw(0).unwrap(), 150);
+    assert_eq!(farm.withdraw(1).unwrap(), 150);
+
+    // carried rewards are distributed by the new farm, 100 seconds by weight of 100 and 150
+    farm.farm = new_farm;
+    assert_eq!(farm.withdraw(0).unwrap(), 120);
+    assert_eq!(farm.withdraw(1).unwrap(), 180);
+
+    // LP tokens of the old farm are unbonded from the new farm
+    farm.unbond_lock(0, 100, None).unwrap();
+    assert_eq!(farm.staker_info(0).bond_amount, Uint128::zero());
+    assert_eq!(farm.balance(&lp_token, &farm.stakers[0]).unwrap(), Uint128::zero());
+    farm.bond_locked(0, 10, None).unwrap();
+    assert_eq!(farm.balance(&lp_token, &farm.stakers[0]).unwrap(), Uint128::new(10));
+}
+
+#[test]
+fn migrated_positions_follow_the_new_farm() {
+    let mut farm = Farm::new(false, false);
+    let time = farm.clock();
+    let old_farm = farm.farm.clone();
+    farm.add_distribution_period((time, time + 1000, 1000)).unwrap();
+    farm.bond(0, 100).unwrap();
+    farm.app.advance_seconds(100);
+
+    // carried period is delayed after the periods of the new farm keeping its duration
+    let new_farm = farm.instantiate_new_farm();
+    farm.farm = new_farm.clone();
+    farm.add_distribution_period((time + 100, time + 500, 400)).unwrap();
+    farm.farm = old_farm;
+    farm.migrate_staking(&new_farm).unwrap();
+    farm.farm = new_farm.clone();
+    let config: ConfigResponse = farm.app.wrap().query_wasm_smart(&new_farm, &QueryMsg::Config {}).unwrap();
+    assert_eq!(
+        config.rewards[0].distribution_schedule,
+        vec![(time + 100, time + 500, Uint128::new(400)), (time + 500, time + 1400, Uint128::new(900))]
+    );
+
+    // imported unlocked bond joins the unlocked bond of the staker at the average bond time
+    farm.bond(0, 50).unwrap();
+    farm.app
+        .execute_contract(farm.stakers[1].clone(), new_farm, &ExecuteMsg::PullPositions { limit: None }, &[])
+        .unwrap();
+    let info = farm.staker_info(0);
+    assert_eq!(info.bond_amount, Uint128::new(150));
+    assert_eq!(info.locks.len(), 1);
+    assert_eq!(info.locks[0].amount, Uint128::new(150));
+    assert_eq!(info.locks[0].bond_time, time + 34);
+}
+
+#[test]
+fn paused_farm_allows_emergency_unbond() {
+    const DAY: u64 = 24 * 60 * 60;
+    let tiers = vec![
+        LockupTier { duration: 0, multiplier: Decimal::one() },
+        LockupTier { duration: 30 * DAY, multiplier: Decimal::percent(200) },
+    ];
+    let mut farm = Farm::with_config(true, false, |msg| {
+        msg.admin = Some("admin".to_string());
+        msg.lockup_tiers = Some(tiers);
+    });
+    let time = farm.clock();
+    let admin = Addr::unchecked("admin");
+    let staker = farm.stakers[0].clone();
+    let staking_asset = farm.staking_asset.clone();
+    let balance = farm.balance(&staking_asset, &staker).unwrap();
+    farm.add_distribution_period((time, time + 1000, 1000)).unwrap();
+    farm.bond_locked(0, 100, Some(30 * DAY)).unwrap();
+    farm.bond_locked(1, 100, None).unwrap();
+    farm.app.advance_seconds(100);
+    // 100 seconds by the weight of 200 and 100
+    assert_eq!(farm.withdraw(1).unwrap(), 33);
+
+    let err = farm
+        .app
+        .execute_contract(Addr::unchecked(DISTRIBUTION_ACCOUNT), farm.farm.clone(), &ExecuteMsg::Pause {}, &[])
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+    let emergency_unbond = |farm: &mut Farm, staker: usize, amount: u128| {
+        farm.app.execute_contract(
+            farm.stakers[staker].clone(),
+            farm.lp_token.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: farm.farm.to_string(),
+                amount: Uint128::new(amount),
+                msg: to_binary(&Cw20HookMsg::EmergencyUnbond {}).unwrap(),
+            },
+            &[],
+        )
+    };
+    let err = emergency_unbond(&mut farm, 0, 100).unwrap_err();
+    assert_eq!(ContractError::NotPaused {}, err.downcast().unwrap());
+
+    farm.app.execute_contract(admin.clone(), farm.farm.clone(), &ExecuteMsg::Pause {}, &[]).unwrap();
+    let config: ConfigResponse = farm.app.wrap().query_wasm_smart(&farm.farm, &QueryMsg::Config {}).unwrap();
+    assert!(config.paused);
+    let err = farm.bond_locked(2, 100, None).unwrap_err();
+    assert_eq!(ContractError::Paused {}, err.downcast().unwrap());
+    let err = farm.withdraw(1).unwrap_err();
+    assert_eq!(ContractError::Paused {}, err.downcast().unwrap());
+    let err = farm.unbond_lock(1, 100, None).unwrap_err();
+    assert_eq!(ContractError::Paused {}, err.downcast().unwrap());
+
+    // locked bond is returned in full, pending rewards are refunded to the distribution account
+    let err = emergency_unbond(&mut farm, 0, 50).unwrap_err();
+    assert_eq!(ContractError::FailUnbond {}, err.downcast().unwrap());
+    let distribution_account = Addr::unchecked(DISTRIBUTION_ACCOUNT);
+    let refunded = farm.reward_balance(&distribution_account).unwrap();
+    let funded = farm.funding()[0].funded;
+    emergency_unbond(&mut farm, 0, 100).unwrap();
+    assert_eq!(farm.balance(&staking_asset, &staker).unwrap(), balance);
+    assert_eq!(farm.reward_balance(&distribution_account).unwrap() - refunded, Uint128::new(66));
+    assert_eq!(funded - farm.funding()[0].funded, Uint128::new(66));
+    assert_eq!(farm.reward_balance(&farm.farm.clone()).unwrap(), Uint128::new(1000 - 33 - 66));
+    let info = farm.staker_info(0);
+    assert_eq!(info.bond_amount, Uint128::zero());
+    assert!(info.locks.is_empty());
+    assert_eq!(info.rewards[0].pending_reward, Uint128::zero());
+
+    // remaining staker gets the rewards since the last distribution on the first distribution
+    // after resume, rewards of the paused time included
+    farm.app.advance_seconds(100);
+    farm.app.execute_contract(admin, farm.farm.clone(), &ExecuteMsg::Resume {}, &[]).unwrap();
+    assert_eq!(farm.withdraw(1).unwrap(), 100);
+}
+
+#[test]
+fn distribution_periods_are_validated() {
+    for reward_cw20 in [false, true] {
+        let mut farm = Farm::new(false, reward_cw20);
+        let reward_asset = farm.reward_asset.clone();
+        let time = farm.clock();
+        farm.add_periods(&reward_asset, vec![(time + 100, time + 200, Uint128::new(100))], 100)
+            .unwrap();
+
+        let cases = vec![
+            (
+                vec![(time + 300, time + 300, Uint128::new(100))],
+                100,
+                ContractError::InvalidDistributionPeriod { start: time + 300, end: time + 300 },
+            ),
+            (
+                vec![(time - 1, time + 50, Uint128::new(100))],
+                100,
+                ContractError::PastDistributionPeriod { start: time - 1, end: time + 50 },
+            ),
+            (
+                vec![(time + 150, time + 300, Uint128::new(100))],
+                100,
+                ContractError::OverlappingDistributionPeriod { start: time + 150, end: time + 300 },
+            ),
+            (
+                vec![(time + 200, time + 300, Uint128::new(50)), (time + 250, time + 400, Uint128::new(50))],
+                100,
+                ContractError::OverlappingDistributionPeriod { start: time + 250, end: time + 400 },
+            ),
+            (
+                vec![(time + 200, time + 300, Uint128::new(100))],
+                99,
+                ContractError::InvalidRewardFunds { expected: Uint128::new(100), received: Uint128::new(99) },
+            ),
+        ];
+        for (periods, amount, expected) in cases {
+            let err = farm.add_periods(&reward_asset, periods, amount).unwrap_err();
+            assert_eq!(expected, err.downcast().unwrap());
+        }
+
+        // adjacent periods do not overlap
+        farm.add_periods(&reward_asset, vec![(time, time + 100, Uint128::new(50))], 50)
+            .unwrap();
+        farm.add_periods(&reward_asset, vec![(time + 200, time + 300, Uint128::new(70))], 70)
+            .unwrap();
+        assert_eq!(
+            farm.funding(),
+            vec![RewardFundingResponse {
+                asset: reward_asset.clone(),
+                funded: Uint128::new(220),
+                committed: Uint128::new(220),
+            }]
+        );
+    }
+}
+
+#[test]
+fn distribution_periods_are_cut_with_refund() {
+    for reward_cw20 in [false, true] {
+        let mut farm = Farm::new(false, reward_cw20);
+        let reward_asset = farm.reward_asset.clone();
+        let distribution_account = Addr::unchecked(DISTRIBUTION_ACCOUNT);
+        let staker = farm.stakers[0].clone();
+        let time = farm.clock();
+        farm.add_periods(
+            &reward_asset,
+            vec![(time, time + 100, Uint128::new(1000)), (time + 200, time + 300, Uint128::new(500))],
+            1500,
+        )
+        .unwrap();
+        farm.bond(0, 100).unwrap();
+        farm.app.advance_seconds(40);
+
+        let cut = |farm: &mut Farm, sender: &Addr, msg: ExecuteMsg| -> Result<Uint128, ContractError> {
+            let before = farm.balance(&reward_asset, &distribution_account).unwrap();
+            farm.app
+                .execute_contract(sender.clone(), farm.farm.clone(), &msg, &[])
+                .map_err(|err| err.downcast::<ContractError>().unwrap())?;
+            let after = farm.balance(&reward_asset, &distribution_account).unwrap();
+            Ok(after - before)
+        };
+        let update = |start: u64, end: u64| ExecuteMsg::UpdateDistributionPeriod {
+            reward_asset: reward_asset.clone(),
+            start,
+            end,
+        };
+        let remove = |start: u64| ExecuteMsg::RemoveDistributionPeriod {
+            reward_asset: reward_asset.clone(),
+            start,
+        };
+
+        assert_eq!(cut(&mut farm, &staker, update(time, time + 50)), Err(ContractError::Unauthorized {}));
+        assert_eq!(
+            cut(&mut farm, &distribution_account, update(time, time + 100)),
+            Err(ContractError::InvalidDistributionPeriod { start: time, end: time + 100 })
+        );
+        assert_eq!(
+            cut(&mut farm, &distribution_account, update(time, time + 30)),
+            Err(ContractError::DistributedDistributionPeriod { start: time, end: time + 100 })
+        );
+        assert_eq!(
+            cut(&mut farm, &distribution_account, remove(time + 1)),
+            Err(ContractError::DistributionPeriodNotFound { start: time + 1 })
+        );
+        assert_eq!(
+            cut(
+                &mut farm,
+                &distribution_account,
+                ExecuteMsg::RemoveDistributionPeriod {
+                    reward_asset: AssetInfo::Native { denom: SECOND_REWARD_DENOM.to_string() },
+                    start: time,
+                },
+            ),
+            Err(ContractError::UnknownRewardAsset { asset: SECOND_REWARD_DENOM.to_string() })
+        );
+
+        // shorten the running period and remove the future one
+        assert_eq!(cut(&mut farm, &distribution_account, update(time, time + 50)), Ok(Uint128::new(500)));
+        assert_eq!(cut(&mut farm, &distribution_account, remove(time + 200)), Ok(Uint128::new(500)));
+        assert_eq!(farm.funding()[0].funded, Uint128::new(500));
+        assert_eq!(farm.funding()[0].committed, Uint128::new(500));
+
+        farm.app.advance_seconds(100);
+        assert_eq!(
+            cut(&mut farm, &distribution_account, remove(time)),
+            Err(ContractError::DistributedDistributionPeriod { start: time, end: time + 50 })
+        );
+        assert_eq!(farm.withdraw(0).unwrap(), 500);
+
+        // remove the running period at the current time
+        let time = farm.clock();
+        farm.add_periods(&reward_asset, vec![(time, time + 100, Uint128::new(1000))], 1000)
+            .unwrap();
+        farm.app.advance_seconds(30);
+        assert_eq!(cut(&mut farm, &distribution_account, remove(time)), Ok(Uint128::new(700)));
+        farm.app.advance_seconds(100);
+        assert_eq!(farm.withdraw(0).unwrap(), 300);
+        assert_eq!(farm.funding()[0].funded, farm.funding()[0].committed);
+    }
+}
+
+#[test]
+fn distribution_periods_are_funded_by_distribution_account() {
+    let mut farm = Farm::new(false, true);
+    let staker = farm.stakers[0].clone();
+    let time = farm.clock();
+    let periods = vec![(time, time + 100, Uint128::new(100))];
+
+    // cw20 rewards are deposited with the hook only
+    let err = farm.app
+        .execute_contract(
+            Addr::unchecked(DISTRIBUTION_ACCOUNT),
+            farm.farm.clone(),
+            &ExecuteMsg::AddDistributionPeriods {
+                reward_asset: farm.reward_asset.clone(),
+                periods: periods.clone(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        ContractError::InvalidRewardFunds { expected: Uint128::new(100), received: Uint128::zero() },
+        err.downcast().unwrap()
+    );
+
+    let reward_token = match &farm.reward_asset {
+        AssetInfo::Cw20 { address } => address.clone(),
+        AssetInfo::Native { .. } => unreachable!(),
+    };
+    farm.app
+        .execute_contract(
+            Addr::unchecked(DISTRIBUTION_ACCOUNT),
+            reward_token.clone(),
+            &Cw20ExecuteMsg::Transfer { recipient: staker.to_string(), amount: Uint128::new(100) },
+            &[],
+        )
+        .unwrap();
+    let err = farm.app
+        .execute_contract(
+            staker,
+            reward_token,
+            &Cw20ExecuteMsg::Send {
+                contract: farm.farm.to_string(),
+                amount: Uint128::new(100),
+                msg: to_binary(&Cw20HookMsg::AddDistributionPeriods { periods }).unwrap(),
+            },
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(ContractError::Unauthorized {}, err.downcast().unwrap());
+}
+
+#[test]
+fn instantiate_validates_rewards() {
+    let mut app = CyberApp::new();
+    app.init_modules(|router, _, storage| {
+        router.bank
+            .init_balance(storage, &Addr::unchecked(DISTRIBUTION_ACCOUNT), coins(1000, REWARD_DENOM))
+            .unwrap();
+    });
+    let token_code_id = app.store_code(contract_cw20());
+    let farm_code_id = app.store_code(contract_farm());
+    let time = app.block_info().time.seconds();
+    let reward = RewardAsset {
+        asset: AssetInfo::Native { denom: REWARD_DENOM.to_string() },
+        distribution_schedule: vec![(time, time + 100, Uint128::new(1000))],
+    };
+    let mut instantiate = |rewards: Vec<RewardAsset>,
+                           funds: u128,
+                           lockup_tiers: Option<Vec<LockupTier>>,
+                           auto_compound: Option<bool>| {
+        app.instantiate_contract(
+            farm_code_id,
+            Addr::unchecked(DISTRIBUTION_ACCOUNT),
+            &InstantiateMsg {
+                admin: None,
+            distribution_account: DISTRIBUTION_ACCOUNT.to_string(),
+                staking_asset: AssetInfo::Native { denom: STAKING_DENOM.to_string() },
+                pool_id: None,
+                rewards,
+                lockup_tiers,
+                unbonding_period: None,
+                auto_compound,
+                migrate_from: None,
+                token_code_id,
+                pool_name: "test".to_string(),
+                treasury: None,
+                withdraw_fee: None,
+                unbond_fee: None,
+                unbond_fee_decay: None,
+            },
+            &coins(funds, REWARD_DENOM),
+            "farm",
+            None,
+        )
+    };
+
+    let err = instantiate(vec![reward.clone(), reward.clone()], 1000, None, None).unwrap_err();
+    assert_eq!(
+        ContractError::DuplicateRewardAsset { asset: REWARD_DENOM.to_string() },
+        err.downcast().unwrap()
+    );
+    let err = instantiate(vec![reward.clone()], 900, None, None).unwrap_err();
+    assert_eq!(
+        ContractError::InvalidRewardFunds { expected: Uint128::new(1000), received: Uint128::new(900) },
+        err.downcast().unwrap()
+    );
+
+    let tier = LockupTier { duration: 0, multiplier: Decimal::one() };
+    for lockup_tiers in [vec![], vec![tier.clone(), tier], vec![LockupTier { duration: 0, multiplier: Decimal::zero() }]] {
+        let err = instantiate(vec![reward.clone()], 1000, Some(lockup_tiers), None).unwrap_err();
+        assert_eq!(ContractError::InvalidLockupTiers {}, err.downcast().unwrap());
+    }
+
+
+    // auto-compounding needs the staking asset as a reward asset and a lockup tier without lock
+    let err = instantiate(vec![reward.clone()], 1000, None, Some(true)).unwrap_err();
+    assert_eq!(ContractError::CannotCompound {}, err.downcast().unwrap());
+    let staking_reward = RewardAsset {
+        asset: AssetInfo::Native { denom: STAKING_DENOM.to_string() },
+        distribution_schedule: vec![],
+    };
+    let locked = vec![LockupTier { duration: 100, multiplier: Decimal::one() }];
+    let err = instantiate(vec![reward.clone(), staking_reward], 1000, Some(locked), Some(true)).unwrap_err();
+    assert_eq!(ContractError::CannotCompound {}, err.downcast().unwrap());
+
+    let farm = instantiate(vec![reward], 1000, None, None).unwrap();
+    let funding: FundingResponse = app.wrap().query_wasm_smart(&farm, &QueryMsg::Funding {}).unwrap();
+    assert_eq!(funding.rewards[0].funded, Uint128::new(1000));
+    assert_eq!(funding.rewards[0].committed, Uint128::new(1000));
+}
+
+#[test]
+fn pool_farm_stakes_pool_coin() {
+    const POOL_DENOM: &str = "pool1";
+    let querier = MockQuerier::<CyberQueryWrapper>::new(&[]).with_custom_handler(|query| {
+        let res = match query.query_data {
+            CyberQuery::PoolParams { pool_id: 1 } => to_binary(&PoolParamsResponse {
+                type_id: 1,
+                reserve_coin_denoms: vec![REWARD_DENOM.to_string(), "hydrogen".to_string()],
+                reserve_account_address: "reserve".to_string(),
+                pool_coin_denom: POOL_DENOM.to_string(),
+            }),
+            CyberQuery::PoolSupply { pool_id: 1 } => to_binary(&PoolSupplyResponse { supply: coin(1000, POOL_DENOM) }),
+            CyberQuery::PoolLiquidity { pool_id: 1 } => to_binary(&PoolLiquidityResponse {
+                liquidity: vec![coin(1000, REWARD_DENOM), coin(4000, "hydrogen")],
+            }),
+            _ => return SystemResult::Err(SystemError::UnsupportedRequest { kind: "pool".to_string() }),
+        };
+        SystemResult::Ok(ContractResult::Ok(res.unwrap()))
+    });
+    let mut deps = OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier,
+        custom_query_type: PhantomData::<CyberQueryWrapper>,
+    };
+    let mut instantiate_pool_farm = |denom: &str| {
+        let deps = DepsMut {
+            storage: &mut deps.storage,
+            api: &deps.api,
+            querier: QuerierWrapper::new(&deps.querier),
+        };
+        let msg = InstantiateMsg {
+            admin: None,
+            distribution_account: DISTRIBUTION_ACCOUNT.to_string(),
+            staking_asset: AssetInfo::Native { denom: denom.to_string() },
+            pool_id: Some(1),
+            rewards: vec![],
+            lockup_tiers: None,
+            unbonding_period: None,
+            auto_compound: None,
+            migrate_from: None,
+            token_code_id: 1,
+            pool_name: "test".to_string(),
+            treasury: None,
+            withdraw_fee: None,
+            unbond_fee: None,
+            unbond_fee_decay: None,
+        };
+        instantiate(deps, mock_env(), mock_info(DISTRIBUTION_ACCOUNT, &[]), msg)
+    };
+
+    let err = instantiate_pool_farm(STAKING_DENOM).unwrap_err();
+    assert_eq!(err, ContractError::StakingAssetNotPoolCoin { pool_id: 1, denom: POOL_DENOM.to_string() });
+    instantiate_pool_farm(POOL_DENOM).unwrap();
+
+    // quarter of the pool coins is bonded
+    let mut state = read_state(&deps.storage).unwrap();
+    state.total_bond_amount = Uint128::new(250);
+    store_state(&mut deps.storage, &state, mock_env().block.time.seconds()).unwrap();
+    let deps = Deps { storage: &deps.storage, api: &deps.api, querier: QuerierWrapper::new(&deps.querier) };
+    let res: BondedLiquidityResponse =
+        from_binary(&query(deps, mock_env(), QueryMsg::BondedLiquidity {}).unwrap()).unwrap();
+    assert_eq!(res.pool_supply, Uint128::new(1000));
+    assert_eq!(res.liquidity, vec![coin(250, REWARD_DENOM), coin(1000, "hydrogen")]);
+}
+
+#[test]
+fn withdraw_investmints_native_rewards() {
+    let mut deps = mock_dependencies();
+    let mut env = mock_env();
+    let time = env.block.time.seconds();
+    let msg = InstantiateMsg {
+        admin: None,
+        distribution_account: DISTRIBUTION_ACCOUNT.to_string(),
+        staking_asset: AssetInfo::Native { denom: STAKING_DENOM.to_string() },
+        pool_id: None,
+        rewards: vec![RewardAsset {
+            asset: AssetInfo::Native { denom: "hydrogen".to_string() },
+            distribution_schedule: vec![(time, time + 100, Uint128::new(1000))],
+        }],
+        lockup_tiers: None,
+        unbonding_period: None,
+        auto_compound: None,
+        migrate_from: None,
+        token_code_id: 1,
+        pool_name: "POOL".to_string(),
+        treasury: None,
+        withdraw_fee: None,
+        unbond_fee: None,
+        unbond_fee_decay: None,
+    };
+    instantiate(deps.as_mut(), env.clone(), mock_info(DISTRIBUTION_ACCOUNT, &coins(1000, "hydrogen")), msg).unwrap();
+    let mut config = read_config(deps.as_ref().storage).unwrap();
+    config.lp_token = Some(Addr::unchecked("lp_token"));
+    store_config(deps.as_mut().storage, &config, env.block.time.seconds()).unwrap();
+
+    let bond = ExecuteMsg::Bond { lock_duration: None, recipient: None };
+    execute(deps.as_mut(), env.clone(), mock_info("staker0", &coins(100, STAKING_DENOM)), bond).unwrap();
+
+    env.block.time = env.block.time.plus_seconds(50);
+    let withdraw = ExecuteMsg::Withdraw {
+        to: None,
+        investmint: Some(Investmint { resource: "millivolt".to_string(), length: 86400 }),
+    };
+    let res = execute(deps.as_mut(), env.clone(), mock_info("staker0", &[]), withdraw).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_on_success(
+            create_investmint_msg(MOCK_CONTRACT_ADDR.to_string(), coin(500, "hydrogen"), "millivolt".to_string(), 86400),
+            MSG_REPLY_ID_INVESTMINT,
+        )]
+    );
+
+    // minted resource is forwarded to the staker
+    deps.querier.update_balance(MOCK_CONTRACT_ADDR, coins(20, "millivolt"));
+    let investminted = Reply {
+        id: MSG_REPLY_ID_INVESTMINT,
+        result: SubMsgResult::Ok(SubMsgResponse { events: vec![], data: None }),
+    };
+    let res = reply(deps.as_mut(), env, investminted).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send { to_address: "staker0".to_string(), amount: coins(20, "millivolt") })]
+    );
+}
+
+#[test]
+fn migrate_legacy_storage() {
+    let mut deps = mock_dependencies();
+    let legacy = LegacyConfig {
+        distribution_account: Addr::unchecked(DISTRIBUTION_ACCOUNT),
+        reward_denom: None,
+        reward_token: Some(Addr::unchecked("boot_token")),
+        staking_denom: Some(STAKING_DENOM.to_string()),
+        staking_token: None,
+        lp_token: Some(Addr::unchecked("lp_token")),
+        distribution_schedule: vec![(10, 20, Uint128::new(100))],
+    };
+    LEGACY_CONFIG.save(deps.as_mut().storage, &legacy).unwrap();
+    LEGACY_STATE
+        .save(
+            deps.as_mut().storage,
+            &LegacyState {
+                last_distributed: 15,
+                total_bond_amount: Uint128::new(10),
+                global_reward_index: Decimal::percent(500),
+            },
+        )
+        .unwrap();
+    let staker = Addr::unchecked("staker0");
+    LEGACY_STAKERS_INFO
+        .save(
+            deps.as_mut().storage,
+            &staker,
+            &LegacyStakerInfo {
+                reward_index: Decimal::percent(200),
+                bond_amount: Uint128::new(10),
+                pending_reward: Uint128::new(7),
+            },
+        )
+        .unwrap();
+
+    let err = migrate(deps.as_mut(), mock_env(), MigrateMsg { block_time: None }).unwrap_err();
+    assert_eq!(err, ContractError::MissingBlockTime {});
+
+    let env = mock_env();
+    migrate(deps.as_mut(), env.clone(), MigrateMsg { block_time: Some(5) }).unwrap();
+    let to_time = |height: u64| env.block.time.seconds() - (env.block.height - height) * 5;
+
+    let config = read_config(deps.as_ref().storage).unwrap();
+    assert_eq!(config.staking_asset, AssetInfo::Native { denom: STAKING_DENOM.to_string() });
+    assert_eq!(config.lp_token, legacy.lp_token);
+    assert_eq!(
+        config.rewards,
+        vec![RewardAsset {
+            asset: AssetInfo::Cw20 { address: Addr::unchecked("boot_token") },
+            distribution_schedule: vec![(to_time(10), to_time(20), Uint128::new(100))],
+        }]
+    );
+    let state = read_state(deps.as_ref().storage).unwrap();
+    assert_eq!(state.last_distributed, to_time(15));
+    assert_eq!(state.funded_rewards, vec![Uint128::new(100)]);
+    assert_eq!(state.global_reward_indexes, vec![Decimal::percent(500)]);
+    let staker_info = read_staker_info(deps.as_ref().storage, &staker).unwrap();
+    assert_eq!(staker_info.reward_indexes, vec![Decimal::percent(200)]);
+    assert_eq!(staker_info.pending_rewards, vec![Uint128::new(7)]);
+    assert_eq!(staker_info.weight, Uint128::new(10));
+    let locks = read_locks(deps.as_ref().storage, &staker).unwrap();
+    assert_eq!(locks.len(), 1);
+    assert_eq!(locks[0].1.amount, Uint128::new(10));
+
+    // a second migration keeps the converted storage
+    migrate(deps.as_mut(), mock_env(), MigrateMsg { block_time: None }).unwrap();
+    assert_eq!(read_config(deps.as_ref().storage).unwrap(), config);
+    assert_eq!(read_state(deps.as_ref().storage).unwrap(), state);
+}
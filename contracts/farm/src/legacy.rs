@@ -0,0 +1,159 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, BlockInfo, Decimal, Order, StdError, StdResult, Storage, Uint128};
+use cw_storage_plus::{Item, Map};
+
+use crate::{
+    asset::AssetInfo,
+    state::{
+        default_lockup_tiers, next_lock_id, store_config, store_lock, store_staker_info, store_state,
+        Config, Lock, RewardAsset, StakerInfo, State,
+    },
+};
+
+// Storage layouts of the farm-nv-nv, farm-nv-20, farm-20-nv and farm-20-20 contracts,
+// stored under the same keys as the current ones, their schedules are in block heights
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyConfig {
+    pub distribution_account: Addr,
+    pub reward_denom: Option<String>,
+    pub reward_token: Option<Addr>,
+    pub staking_denom: Option<String>,
+    pub staking_token: Option<Addr>,
+    pub lp_token: Option<Addr>,
+    pub distribution_schedule: Vec<(u64, u64, Uint128)>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyState {
+    pub last_distributed: u64,
+    pub total_bond_amount: Uint128,
+    pub global_reward_index: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyStakerInfo {
+    pub reward_index: Decimal,
+    pub bond_amount: Uint128,
+    pub pending_reward: Uint128,
+}
+
+pub const LEGACY_CONFIG: Item<LegacyConfig> = Item::new("config");
+pub const LEGACY_STATE: Item<LegacyState> = Item::new("state");
+pub const LEGACY_STAKERS_INFO: Map<&Addr, LegacyStakerInfo> = Map::new("reward");
+
+impl LegacyConfig {
+    pub fn into_config(self, to_time: impl Fn(u64) -> u64) -> StdResult<Config> {
+        let reward_asset = legacy_asset(self.reward_denom, self.reward_token)?;
+        let staking_asset = legacy_asset(self.staking_denom, self.staking_token)?;
+        Ok(Config {
+            admin: self.distribution_account.clone(),
+            distribution_account: self.distribution_account,
+            staking_asset,
+            pool_id: None,
+            lp_token: self.lp_token,
+            rewards: vec![RewardAsset {
+                asset: reward_asset,
+                distribution_schedule: self
+                    .distribution_schedule
+                    .into_iter()
+                    .map(|(start, end, amount)| (to_time(start), to_time(end), amount))
+                    .collect(),
+            }],
+            lockup_tiers: default_lockup_tiers(),
+            unbonding_period: None,
+            auto_compound: false,
+            migrated_from: None,
+            paused: false,
+            treasury: None,
+            withdraw_fee: Decimal::zero(),
+            unbond_fee: Decimal::zero(),
+            unbond_fee_decay: None,
+        })
+    }
+}
+
+fn legacy_asset(denom: Option<String>, token: Option<Addr>) -> StdResult<AssetInfo> {
+    match (denom, token) {
+        (Some(denom), None) => Ok(AssetInfo::Native { denom }),
+        (None, Some(address)) => Ok(AssetInfo::Cw20 { address }),
+        _ => Err(StdError::generic_err("unknown farm config layout")),
+    }
+}
+
+// converts heights to unix time in seconds assuming block_time seconds per block,
+// heights before the genesis of unix time are converted to zero
+fn height_to_time(block: &BlockInfo, block_time: u64, height: u64) -> u64 {
+    let now = block.time.seconds();
+    if height >= block.height {
+        now.saturating_add((height - block.height).saturating_mul(block_time))
+    } else {
+        now.saturating_sub((block.height - height).saturating_mul(block_time))
+    }
+}
+
+// converts config, state and every staker to the farm with a single reward asset and unlocked bonds,
+// the share of each period distributed at the current block is kept by the conversion to time
+pub fn migrate_legacy_storage(storage: &mut dyn Storage, block: &BlockInfo, block_time: u64) -> StdResult<()> {
+    let to_time = |height| height_to_time(block, block_time, height);
+
+    // legacy entries are removed first, checkpoints keep none of them
+    let config = LEGACY_CONFIG.load(storage)?.into_config(to_time)?;
+    LEGACY_CONFIG.remove(storage);
+    store_config(storage, &config, block.time.seconds())?;
+
+    let state = LEGACY_STATE.load(storage)?;
+    LEGACY_STATE.remove(storage);
+    store_state(
+        storage,
+        &State {
+            last_distributed: to_time(state.last_distributed),
+            total_bond_amount: state.total_bond_amount,
+            total_weight: state.total_bond_amount,
+            global_reward_indexes: vec![state.global_reward_index],
+            // legacy farms were funded by transfers to the contract,
+            // their schedules are assumed to be funded in full
+            funded_rewards: config.rewards.iter().map(|reward| reward.committed()).collect(),
+        },
+        block.time.seconds(),
+    )?;
+
+    let stakers = LEGACY_STAKERS_INFO
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (staker, info) in stakers {
+        // bond of the legacy farm becomes unlocked bond
+        if !info.bond_amount.is_zero() {
+            let lock_id = next_lock_id(storage)?;
+            store_lock(
+                storage,
+                &staker,
+                lock_id,
+                &Lock {
+                    amount: info.bond_amount,
+                    duration: 0,
+                    multiplier: Decimal::one(),
+                    bond_time: 0,
+                    unlock_time: 0,
+                },
+            )?;
+        }
+        LEGACY_STAKERS_INFO.remove(storage, &staker);
+        store_staker_info(
+            storage,
+            &staker,
+            &StakerInfo {
+                bond_amount: info.bond_amount,
+                weight: info.bond_amount,
+                reward_indexes: vec![info.reward_index],
+                pending_rewards: vec![info.pending_reward],
+                expiring: vec![],
+            },
+            block.time.seconds(),
+        )?;
+    }
+
+    Ok(())
+}
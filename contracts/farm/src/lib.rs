@@ -1,6 +1,10 @@
+pub mod asset;
 pub mod contract;
 pub mod state;
+pub mod legacy;
 pub mod execute;
 pub mod msg;
 pub mod query;
 pub mod error;
+#[cfg(test)]
+mod integration_tests;
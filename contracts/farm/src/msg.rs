@@ -0,0 +1,332 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Coin, Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+use crate::asset::AssetInfo;
+use crate::state::{Lock, LockupTier, RewardAsset};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// Account that pauses the farm, the distribution account by default
+    pub admin: Option<String>,
+    pub distribution_account: String,
+    pub staking_asset: AssetInfo,
+    /// Liquidity pool of the staking asset, the staking asset has to be its pool coin
+    pub pool_id: Option<u64>,
+    pub rewards: Vec<RewardAsset>,
+    /// Lockup tiers to choose from at bond, single tier without lock by default
+    pub lockup_tiers: Option<Vec<LockupTier>>,
+    /// Unbonded amount is claimable after the unbonding period in seconds, immediately if none
+    pub unbonding_period: Option<u64>,
+    /// Compound reward in the staking asset on every interaction, the staking asset
+    /// has to be a reward asset and a lockup tier without lock has to exist
+    pub auto_compound: Option<bool>,
+    /// Farm the positions are imported from after its `MigrateStaking` to this farm,
+    /// the LP token of the farm is taken over instead of instantiating a new one
+    pub migrate_from: Option<String>,
+    pub token_code_id: u64,
+    pub pool_name: String,
+    /// Receiver of the fees, required if any fee is set
+    pub treasury: Option<String>,
+    /// Share of withdrawn rewards sent to the treasury, none by default
+    pub withdraw_fee: Option<Decimal>,
+    /// Share of unbonded amount sent to the treasury, none by default
+    pub unbond_fee: Option<Decimal>,
+    /// Unbond fee decreases linearly to zero over the period in seconds since the bond, constant if none
+    pub unbond_fee_decay: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Bond native staking asset attached to the message for the recipient, the sender by default,
+    /// locked for the duration of one of the lockup tiers, no lock by default
+    Bond {
+        lock_duration: Option<u64>,
+        recipient: Option<String>,
+    },
+    Receive(Cw20ReceiveMsg),
+    /// Withdraw pending rewards of all reward assets to the given address,
+    /// the withdraw address of the staker or the staker by default,
+    /// native rewards are investminted into the resource if given
+    Withdraw {
+        to: Option<String>,
+        investmint: Option<Investmint>,
+    },
+    /// Set the address rewards of the sender are withdrawn to, none resets it to the sender
+    SetWithdrawAddress {
+        address: Option<String>,
+    },
+    /// Move bond backing LP tokens transferred between holders, sent by the LP token,
+    /// position of the sender not pulled from the former farm yet is pulled before
+    TransferPosition {
+        from: String,
+        to: String,
+        amount: Uint128,
+    },
+    /// Claim unbonded amount released after the unbonding period
+    Claim {},
+    /// Move pending reward in the staking asset to the unlocked bond and mint LP token for it
+    Compound {},
+    /// Owner operation to stop distribution on current staking contract
+    /// and add the remaining distribution periods with their funds to the new contract,
+    /// periods overlapping the schedule of the new contract are delayed after it keeping their durations,
+    /// the farm is frozen and the new contract becomes the minter of the LP token
+    /// and may pull the positions
+    MigrateStaking {
+        new_staking_contract: String,
+    },
+    /// Pull the next positions from the farm this farm is migrated from
+    PullPositions {
+        limit: Option<u32>,
+    },
+    /// Export the next positions of the frozen farm with their bonded funds, sent by the new farm
+    ExportPositions {
+        limit: Option<u32>,
+    },
+    /// Export the position of the staker of the frozen farm with its bonded funds, sent by the new farm
+    ExportPosition {
+        staker: String,
+    },
+    /// Import positions exported by the farm this farm is migrated from,
+    /// native bonded funds are attached, cw20 ones are transferred before
+    ImportPositions {
+        positions: Vec<MigratedPosition>,
+    },
+    /// Add a list of distribution periods of the native reward asset funded by attached funds,
+    /// unknown reward asset is added to the farm rewards. Sent by the distribution account
+    /// or by the farm this farm is migrated from.
+    AddDistributionPeriods {
+        reward_asset: AssetInfo,
+        periods: Vec<(u64, u64, Uint128)>,
+    },
+    /// Remove the distribution period of the reward asset starting at start,
+    /// the running period is cut at the current time, undistributed reward is refunded.
+    RemoveDistributionPeriod {
+        reward_asset: AssetInfo,
+        start: u64,
+    },
+    /// Shorten the distribution period of the reward asset starting at start to the end
+    /// not before the current time, reward of the cut part is refunded.
+    UpdateDistributionPeriod {
+        reward_asset: AssetInfo,
+        start: u64,
+        end: u64,
+    },
+    /// Change the distribution account.
+    ChangeDistributionAccount {
+        new_account: String,
+    },
+    /// Admin operation to pause bond, unbond, withdraw and compound, emergency unbond is allowed,
+    /// transfers of positions and changes of the distribution periods keep distributing rewards
+    Pause {},
+    /// Admin operation to resume the paused farm
+    Resume {},
+    /// Change the admin.
+    ChangeAdmin {
+        new_admin: String,
+    },
+    /// Admin operation to update the treasury and the fees, fields not given are kept,
+    /// unbond fee decay of 0 makes the unbond fee constant
+    UpdateFees {
+        treasury: Option<String>,
+        withdraw_fee: Option<Decimal>,
+        unbond_fee: Option<Decimal>,
+        unbond_fee_decay: Option<u64>,
+    },
+}
+
+/// Locks of the staker keep their unlock times in the new farm, their multipliers
+/// are the ones of the lockup tiers of the new farm with the same durations, 1 without one,
+/// unlocked bond joins the unlocked bond of the staker in the new farm
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigratedPosition {
+    pub staker: String,
+    pub locks: Vec<Lock>,
+}
+
+/// Resource (millivolt or milliampere) minted for native rewards locked for the length in seconds
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Investmint {
+    pub resource: String,
+    pub length: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Bond cw20 staking asset for the recipient, the sender by default, sent by the staking token,
+    /// locked for the duration of one of the lockup tiers, no lock by default
+    Bond {
+        lock_duration: Option<u64>,
+        recipient: Option<String>,
+    },
+    /// Unbond from the lock or from unlocked bond if no lock given, sent by the LP token
+    Unbond {
+        lock_id: Option<u64>,
+    },
+    /// Unbond the whole bond of the paused farm regardless of locks, sent by the LP token,
+    /// the amount has to be the bond amount, pending rewards are forfeited to the distribution account
+    EmergencyUnbond {},
+    /// Add a list of distribution periods of the cw20 reward asset funded by the sent amount,
+    /// sent by the reward token on behalf of the distribution account
+    AddDistributionPeriods {
+        periods: Vec<(u64, u64, Uint128)>,
+    },
+}
+
+/// Migrates the farm-nv-nv, farm-nv-20, farm-20-nv and farm-20-20 contracts as well,
+/// their schedules in block heights are converted to time with the given average block time
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {
+    pub block_time: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Config {},
+    /// State with rewards distributed up to the time in seconds, if given
+    State {
+        time: Option<u64>,
+    },
+    /// Staker info with rewards computed up to the time in seconds, if given,
+    /// bond, weight and rewards are the ones of the time, locks and withdraw address are current
+    StakerInfo {
+        staker: String,
+        time: Option<u64>,
+    },
+    /// Funded and committed rewards of each reward asset
+    Funding {},
+    /// Unbonded amounts of the address waiting for release
+    Claims {
+        address: String,
+    },
+    /// Stakers in the order of their addresses with rewards computed up to the time in seconds, if given,
+    /// stakers of the time are listed in the order of address length, then address,
+    /// with locks and withdraw addresses of now, a page of a past time may list fewer stakers than the limit
+    /// with more of them after its next
+    AllStakers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+        time: Option<u64>,
+    },
+    /// Emission per second of the distribution periods active at the time in seconds,
+    /// current time by default
+    RewardRate {
+        time: Option<u64>,
+    },
+    /// Reserves of the liquidity pool backing the bonded pool coins
+    BondedLiquidity {},
+}
+
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub admin: String,
+    pub distribution_account: String,
+    pub staking_asset: AssetInfo,
+    pub pool_id: Option<u64>,
+    pub lp_token: String,
+    pub rewards: Vec<RewardAsset>,
+    pub lockup_tiers: Vec<LockupTier>,
+    pub unbonding_period: Option<u64>,
+    pub auto_compound: bool,
+    pub migrated_from: Option<String>,
+    pub migrated_to: Option<String>,
+    pub paused: bool,
+    pub treasury: Option<String>,
+    pub withdraw_fee: Decimal,
+    pub unbond_fee: Decimal,
+    pub unbond_fee_decay: Option<u64>,
+}
+
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StateResponse {
+    pub last_distributed: u64,
+    pub total_bond_amount: Uint128,
+    pub total_weight: Uint128,
+    pub rewards: Vec<RewardIndexResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FundingResponse {
+    pub rewards: Vec<RewardFundingResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardFundingResponse {
+    pub asset: AssetInfo,
+    pub funded: Uint128,
+    pub committed: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardIndexResponse {
+    pub asset: AssetInfo,
+    pub global_reward_index: Decimal,
+}
+
+// We define a custom struct for each query response
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakerInfoResponse {
+    pub staker: String,
+    pub bond_amount: Uint128,
+    pub weight: Uint128,
+    pub rewards: Vec<StakerRewardResponse>,
+    pub locks: Vec<LockResponse>,
+    pub withdraw_address: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AllStakersResponse {
+    pub stakers: Vec<StakerInfoResponse>,
+    /// Start after of the next page, none after the last staker
+    pub next: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardRateResponse {
+    pub time: u64,
+    pub total_bond_amount: Uint128,
+    pub total_weight: Uint128,
+    pub rewards: Vec<AssetRewardRateResponse>,
+}
+
+/// Reward per unit of weight is the reward per second of unlocked bond unit,
+/// locked bond earns it multiplied by the multiplier of its lockup tier until its unlock time
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AssetRewardRateResponse {
+    pub asset: AssetInfo,
+    pub emission_per_second: Decimal,
+    pub reward_per_weight: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BondedLiquidityResponse {
+    pub pool_id: u64,
+    pub total_bond_amount: Uint128,
+    pub pool_supply: Uint128,
+    /// Share of the pool reserves of the total bond amount
+    pub liquidity: Vec<Coin>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LockResponse {
+    pub id: u64,
+    pub amount: Uint128,
+    pub duration: u64,
+    pub multiplier: Decimal,
+    pub bond_time: u64,
+    pub unlock_time: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakerRewardResponse {
+    pub asset: AssetInfo,
+    pub reward_index: Decimal,
+    pub pending_reward: Uint128,
+}
@@ -0,0 +1,305 @@
+use cosmwasm_std::{Addr, Coin, Decimal, Deps, Order, QuerierWrapper, StdError, StdResult, Uint128};
+use cw_controllers::ClaimsResponse;
+use cw_storage_plus::Bound;
+use cyber_std::{CyberQuerier, CyberQueryWrapper};
+
+use crate::{
+    msg::{
+        AllStakersResponse, AssetRewardRateResponse, BondedLiquidityResponse, ConfigResponse, FundingResponse, RewardRateResponse, LockResponse, RewardFundingResponse, RewardIndexResponse, StakerInfoResponse, StakerRewardResponse, StateResponse,
+    },
+    state::{
+        read_config, read_config_at, read_locks, read_staker_info, read_staker_info_at, read_state, read_state_at, Config, StakerInfo, State, CLAIMS, MIGRATION, STAKERS_INFO, WITHDRAW_ADDRESSES,
+    },
+    execute::{compute_reward, compute_staker_reward}
+};
+
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+// stakers of the changelog scanned for each staker listed at a past time
+const SCAN_FACTOR: usize = 10;
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let cfg = read_config(deps.storage)?;
+    let resp = ConfigResponse {
+        admin: cfg.admin.to_string(),
+        distribution_account: cfg.distribution_account.to_string(),
+        staking_asset: cfg.staking_asset,
+        pool_id: cfg.pool_id,
+        lp_token: cfg.lp_token.unwrap().to_string(),
+        rewards: cfg.rewards,
+        lockup_tiers: cfg.lockup_tiers,
+        unbonding_period: cfg.unbonding_period,
+        auto_compound: cfg.auto_compound,
+        migrated_from: cfg.migrated_from.map(|addr| addr.to_string()),
+        migrated_to: MIGRATION.may_load(deps.storage)?.map(|migration| migration.new_farm.to_string()),
+        paused: cfg.paused,
+        treasury: cfg.treasury.map(|addr| addr.to_string()),
+        withdraw_fee: cfg.withdraw_fee,
+        unbond_fee: cfg.unbond_fee,
+        unbond_fee_decay: cfg.unbond_fee_decay,
+    };
+
+    Ok(resp)
+}
+
+pub fn query_claims(deps: Deps, address: String) -> StdResult<ClaimsResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    CLAIMS.query_claims(deps, &address)
+}
+
+pub fn query_funding(deps: Deps) -> StdResult<FundingResponse> {
+    let config = read_config(deps.storage)?;
+    let state: State = read_state(deps.storage)?;
+
+    Ok(FundingResponse {
+        rewards: config
+            .rewards
+            .into_iter()
+            .zip(state.funded_rewards)
+            .map(|(reward, funded)| RewardFundingResponse {
+                committed: reward.committed(),
+                asset: reward.asset,
+                funded,
+            })
+            .collect(),
+    })
+}
+
+// config checkpointed at the time, current config if no time given
+fn config_at(deps: Deps, time: Option<u64>) -> StdResult<Config> {
+    match time {
+        Some(time) => read_config_at(deps.storage, time)?
+            .ok_or_else(|| StdError::generic_err(format!("farm has no config at {}", time))),
+        None => read_config(deps.storage),
+    }
+}
+
+// state with the global reward indexes at the unlock times passed by its distribution
+type Distribution = (State, Vec<(u64, Vec<Decimal>)>);
+
+// state checkpointed at the time with rewards distributed up to the time
+fn state_at(deps: Deps, config: &Config, time: u64) -> StdResult<Distribution> {
+    let mut state = read_state_at(deps.storage, time)?
+        .ok_or_else(|| StdError::generic_err(format!("farm has no state at {}", time)))?;
+    let expired = compute_reward(deps.storage, config, &mut state, time, true)?;
+    Ok((state, expired))
+}
+
+pub fn query_state(deps: Deps, time: Option<u64>) -> StdResult<StateResponse> {
+    let config = config_at(deps, time)?;
+    let mut state: State = match time {
+        Some(time) => state_at(deps, &config, time)?.0,
+        None => read_state(deps.storage)?,
+    };
+
+    // reward assets added after the last distribution have zero index
+    state.global_reward_indexes.resize(config.rewards.len(), Decimal::zero());
+
+    Ok(StateResponse {
+        last_distributed: state.last_distributed,
+        total_bond_amount: state.total_bond_amount,
+        total_weight: state.total_weight,
+        rewards: config
+            .rewards
+            .into_iter()
+            .zip(state.global_reward_indexes)
+            .map(|(reward, global_reward_index)| RewardIndexResponse {
+                asset: reward.asset,
+                global_reward_index,
+            })
+            .collect(),
+    })
+}
+
+pub fn query_staker_info(
+    deps: Deps,
+    staker: String,
+    time: Option<u64>,
+) -> StdResult<StakerInfoResponse> {
+    let staker_addr = deps.api.addr_validate(&staker)?;
+    let config = config_at(deps, time)?;
+    let (staker_info, state) = match time {
+        Some(time) => (
+            read_staker_info_at(deps.storage, &staker_addr, time)?,
+            Some(state_at(deps, &config, time)?),
+        ),
+        None => (read_staker_info(deps.storage, &staker_addr)?, None),
+    };
+
+    staker_info_response(deps, &config, state.as_ref(), staker_addr, staker_info)
+}
+
+pub fn query_all_stakers(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+    time: Option<u64>,
+) -> StdResult<AllStakersResponse> {
+    let config = config_at(deps, time)?;
+    let state = time.map(|time| state_at(deps, &config, time)).transpose()?;
+
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after.map(|addr| deps.api.addr_validate(&addr)).transpose()?;
+    let (stakers, next) = match time {
+        Some(time) => stakers_at(deps, start_after.as_ref(), limit, time)?,
+        None => {
+            let stakers = STAKERS_INFO
+                .range(deps.storage, start_after.as_ref().map(Bound::exclusive), None, Order::Ascending)
+                .take(limit)
+                .collect::<StdResult<Vec<_>>>()?;
+            let next = match stakers.len() == limit {
+                true => stakers.last().map(|(staker, _)| staker.clone()),
+                false => None,
+            };
+            (stakers, next)
+        }
+    };
+
+    Ok(AllStakersResponse {
+        stakers: stakers
+            .into_iter()
+            .map(|(staker, staker_info)| staker_info_response(deps, &config, state.as_ref(), staker, staker_info))
+            .collect::<StdResult<_>>()?,
+        next: next.map(|staker| staker.to_string()),
+    })
+}
+
+// stakers of the time found in the changelog of the staker infos, stakers removed later included,
+// the changelog skips to the next staker past all changes of the previous one, the scan stops
+// after limit * SCAN_FACTOR stakers and returns the last scanned one to continue after
+fn stakers_at(
+    deps: Deps,
+    start_after: Option<&Addr>,
+    limit: usize,
+    time: u64,
+) -> StdResult<(Vec<(Addr, StakerInfo)>, Option<Addr>)> {
+    let mut stakers: Vec<(Addr, StakerInfo)> = vec![];
+    let mut last: Option<Addr> = start_after.cloned();
+    for _ in 0..limit * SCAN_FACTOR {
+        let start = last.as_ref().map(|addr| Bound::exclusive((addr, u64::MAX)));
+        let staker = match STAKERS_INFO.changelog().keys(deps.storage, start, None, Order::Ascending).next() {
+            Some(key) => key?.0,
+            None => return Ok((stakers, None)),
+        };
+        if let Some(staker_info) = STAKERS_INFO.may_load_at_height(deps.storage, &staker, time + 1)? {
+            stakers.push((staker.clone(), staker_info));
+        }
+        last = Some(staker);
+        if stakers.len() == limit {
+            break;
+        }
+    }
+    Ok((stakers, last))
+}
+
+// staker info with rewards computed up to the distribution of the state, if given
+fn staker_info_response(
+    deps: Deps,
+    config: &Config,
+    distribution: Option<&Distribution>,
+    staker: Addr,
+    mut staker_info: StakerInfo,
+) -> StdResult<StakerInfoResponse> {
+    if let Some((state, expired)) = distribution {
+        compute_staker_reward(deps.storage, state, &mut staker_info, expired, true)?;
+    }
+
+    // reward assets added after the last update of the staker have no rewards yet
+    staker_info.reward_indexes.resize(config.rewards.len(), Decimal::zero());
+    staker_info.pending_rewards.resize(config.rewards.len(), Uint128::zero());
+
+    Ok(StakerInfoResponse {
+        staker: staker.to_string(),
+        bond_amount: staker_info.bond_amount,
+        weight: staker_info.weight,
+        rewards: config
+            .rewards
+            .iter()
+            .zip(staker_info.reward_indexes.into_iter().zip(staker_info.pending_rewards))
+            .map(|(reward, (reward_index, pending_reward))| StakerRewardResponse {
+                asset: reward.asset.clone(),
+                reward_index,
+                pending_reward,
+            })
+            .collect(),
+        locks: read_locks(deps.storage, &staker)?
+            .into_iter()
+            .map(|(id, lock)| LockResponse {
+                id,
+                amount: lock.amount,
+                duration: lock.duration,
+                multiplier: lock.multiplier,
+                bond_time: lock.bond_time,
+                unlock_time: lock.unlock_time,
+            })
+            .collect(),
+        withdraw_address: WITHDRAW_ADDRESSES.may_load(deps.storage, &staker)?.map(|addr| addr.to_string()),
+    })
+}
+
+pub fn query_reward_rate(deps: Deps, time: u64) -> StdResult<RewardRateResponse> {
+    let config = config_at(deps, Some(time))?;
+    let (state, _) = state_at(deps, &config, time)?;
+
+    Ok(RewardRateResponse {
+        time,
+        total_bond_amount: state.total_bond_amount,
+        total_weight: state.total_weight,
+        rewards: config
+            .rewards
+            .into_iter()
+            .map(|reward| {
+                // periods of the reward asset don't overlap, at most one of them is active
+                let (emission_per_second, reward_per_weight) = match reward
+                    .distribution_schedule
+                    .iter()
+                    .find(|period| period.0 <= time && time < period.1)
+                {
+                    Some(&(start, end, amount)) if !state.total_weight.is_zero() => (
+                        Decimal::from_ratio(amount, end - start),
+                        Decimal::from_ratio(amount, Uint128::from(end - start).checked_mul(state.total_weight)?),
+                    ),
+                    Some(&(start, end, amount)) => (Decimal::from_ratio(amount, end - start), Decimal::zero()),
+                    None => (Decimal::zero(), Decimal::zero()),
+                };
+                Ok(AssetRewardRateResponse {
+                    asset: reward.asset,
+                    emission_per_second,
+                    reward_per_weight,
+                })
+            })
+            .collect::<StdResult<_>>()?,
+    })
+}
+
+pub fn query_pool_coin_denom(deps: Deps, pool_id: u64) -> StdResult<String> {
+    let querier = QuerierWrapper::<CyberQueryWrapper>::new(&*deps.querier);
+    Ok(CyberQuerier::new(&querier).query_pool_params(pool_id)?.pool_coin_denom)
+}
+
+pub fn query_bonded_liquidity(deps: Deps) -> StdResult<BondedLiquidityResponse> {
+    let config = read_config(deps.storage)?;
+    let state = read_state(deps.storage)?;
+    let pool_id = config.pool_id.ok_or_else(|| StdError::generic_err("farm has no liquidity pool"))?;
+
+    let querier = QuerierWrapper::<CyberQueryWrapper>::new(&*deps.querier);
+    let cyber_querier = CyberQuerier::new(&querier);
+    let pool_supply = cyber_querier.query_pool_supply(pool_id)?.supply.amount;
+    let liquidity = cyber_querier.query_pool_liquidity(pool_id)?.liquidity;
+
+    Ok(BondedLiquidityResponse {
+        pool_id,
+        total_bond_amount: state.total_bond_amount,
+        pool_supply,
+        liquidity: liquidity
+            .into_iter()
+            .map(|reserve| Coin {
+                amount: match pool_supply.is_zero() {
+                    true => Uint128::zero(),
+                    false => reserve.amount.multiply_ratio(state.total_bond_amount, pool_supply),
+                },
+                denom: reserve.denom,
+            })
+            .collect(),
+    })
+}
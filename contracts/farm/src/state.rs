@@ -0,0 +1,300 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Decimal, Order, StdResult, Storage, Uint128};
+use cw_controllers::Claims;
+use cw_storage_plus::{Bound, Item, Map, SnapshotItem, SnapshotMap, Strategy};
+
+use crate::asset::AssetInfo;
+
+/// Distribution schedule consists of (start, end, amount) periods with unix time in seconds
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardAsset {
+    pub asset: AssetInfo,
+    pub distribution_schedule: Vec<(u64, u64, Uint128)>,
+}
+
+impl RewardAsset {
+    // total amount of the distribution schedule
+    pub fn committed(&self) -> Uint128 {
+        self.distribution_schedule.iter().map(|period| period.2).sum()
+    }
+}
+
+/// Bond locked for the duration in seconds gets reward weight of amount * multiplier until it unlocks
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LockupTier {
+    pub duration: u64,
+    pub multiplier: Decimal,
+}
+
+// single tier without lock and boost
+pub fn default_lockup_tiers() -> Vec<LockupTier> {
+    vec![LockupTier {
+        duration: 0,
+        multiplier: Decimal::one(),
+    }]
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// Account that pauses the farm
+    pub admin: Addr,
+    pub distribution_account: Addr,
+    pub staking_asset: AssetInfo,
+    /// Liquidity pool the staking asset is the pool coin of
+    pub pool_id: Option<u64>,
+    pub lp_token: Option<Addr>,
+    pub rewards: Vec<RewardAsset>,
+    pub lockup_tiers: Vec<LockupTier>,
+    /// Unbonded amount is claimable after the unbonding period in seconds, immediately if none
+    pub unbonding_period: Option<u64>,
+    /// Reward in the staking asset is compounded to the unlocked bond on every interaction
+    pub auto_compound: bool,
+    /// Farm the positions are imported from, its LP token is shared by both farms
+    pub migrated_from: Option<Addr>,
+    /// Paused farm rejects bond, unbond, withdraw and compound, rewards keep accruing.
+    /// Transfers, exports and imports of positions and changes of the distribution periods
+    /// still distribute to the stakers bonded at their time, the first distribution after resume does the rest
+    pub paused: bool,
+    /// Receiver of the withdraw and unbond fees
+    pub treasury: Option<Addr>,
+    /// Share of withdrawn rewards sent to the treasury
+    pub withdraw_fee: Decimal,
+    /// Share of unbonded amount sent to the treasury
+    pub unbond_fee: Decimal,
+    /// Unbond fee decreases linearly to zero over the period in seconds since the bond, constant if none
+    pub unbond_fee_decay: Option<u64>,
+}
+
+/// Config is checkpointed by block time in seconds, historical queries use the distribution schedule of their time
+pub const CONFIG: SnapshotItem<Config> =
+    SnapshotItem::new("config", "config__checkpoints", "config__changelog", Strategy::EveryBlock);
+
+pub fn read_config(storage: &dyn Storage) -> StdResult<Config> {
+    CONFIG.load(storage)
+}
+
+/// Config at the end of the given time, none before the farm existed
+pub fn read_config_at(storage: &dyn Storage, time: u64) -> StdResult<Option<Config>> {
+    CONFIG.may_load_at_height(storage, time + 1)
+}
+
+pub fn store_config(storage: &mut dyn Storage, config: &Config, time: u64) -> StdResult<()> {
+    CONFIG.save(storage, config, time)
+}
+
+/// Rewards are distributed up to last_distributed unix time in seconds by total weight of locks,
+/// global reward indexes and funded rewards are kept in the order of `Config::rewards`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct State {
+    pub last_distributed: u64,
+    pub total_bond_amount: Uint128,
+    pub total_weight: Uint128,
+    pub global_reward_indexes: Vec<Decimal>,
+    /// Rewards deposited with the distribution periods less the refunded ones
+    pub funded_rewards: Vec<Uint128>,
+}
+
+/// State is checkpointed by block time in seconds for historical queries
+pub const STATE: SnapshotItem<State> =
+    SnapshotItem::new("state", "state__checkpoints", "state__changelog", Strategy::EveryBlock);
+
+pub fn read_state(storage: &dyn Storage) -> StdResult<State> {
+    STATE.load(storage)
+}
+
+/// State at the end of the given time, none before the farm existed
+pub fn read_state_at(storage: &dyn Storage, time: u64) -> StdResult<Option<State>> {
+    STATE.may_load_at_height(storage, time + 1)
+}
+
+pub fn store_state(storage: &mut dyn Storage, state: &State, time: u64) -> StdResult<()> {
+    STATE.save(storage, state, time)
+}
+
+/// Reward indexes and pending rewards are kept in the order of `Config::rewards`,
+/// reward assets added after the last update of the staker are missing
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakerInfo {
+    pub bond_amount: Uint128,
+    pub weight: Uint128,
+    pub reward_indexes: Vec<Decimal>,
+    pub pending_rewards: Vec<Uint128>,
+    /// Boosted locks of the staker in the order of their unlock times, not settled yet
+    pub expiring: Vec<(u64, ExpiringWeight)>,
+}
+
+impl StakerInfo {
+    pub fn has_pending_rewards(&self) -> bool {
+        self.pending_rewards.iter().any(|reward| !reward.is_zero())
+    }
+}
+
+/// Staker info is checkpointed by block time in seconds for historical queries
+pub const STAKERS_INFO: SnapshotMap<&Addr, StakerInfo> =
+    SnapshotMap::new("reward", "reward__checkpoints", "reward__changelog", Strategy::EveryBlock);
+
+pub fn store_staker_info(storage: &mut dyn Storage, owner: &Addr, staker_info: &StakerInfo, time: u64) -> StdResult<()> {
+    STAKERS_INFO.save(storage, owner, staker_info, time)
+}
+
+/// Removes the staker info, the changelog records only the removal of an existing staker
+pub fn remove_staker_info(storage: &mut dyn Storage, owner: &Addr, time: u64) -> StdResult<()> {
+    if STAKERS_INFO.may_load(storage, owner)?.is_some() {
+        STAKERS_INFO.remove(storage, owner, time)?;
+    }
+    Ok(())
+}
+
+pub fn read_staker_info(storage: &dyn Storage, owner: &Addr) -> StdResult<StakerInfo> {
+    Ok(STAKERS_INFO.may_load(storage, owner)?.unwrap_or_else(empty_staker_info))
+}
+
+/// Staker info at the end of the given time
+pub fn read_staker_info_at(storage: &dyn Storage, owner: &Addr, time: u64) -> StdResult<StakerInfo> {
+    Ok(STAKERS_INFO.may_load_at_height(storage, owner, time + 1)?.unwrap_or_else(empty_staker_info))
+}
+
+fn empty_staker_info() -> StakerInfo {
+    StakerInfo {
+        bond_amount: Uint128::zero(),
+        weight: Uint128::zero(),
+        reward_indexes: vec![],
+        pending_rewards: vec![],
+        expiring: vec![],
+    }
+}
+
+/// Address the rewards of the staker are withdrawn to instead of the staker
+pub const WITHDRAW_ADDRESSES: Map<&Addr, Addr> = Map::new("withdraw_addresses");
+
+/// Resource minted by the investmint of withdrawn rewards, forwarded to the recipient on reply
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingInvestmint {
+    pub recipient: Addr,
+    pub resource: String,
+    /// Balance of the resource held by the farm before the investmint
+    pub balance_before: Uint128,
+}
+
+pub const PENDING_INVESTMINT: Item<PendingInvestmint> = Item::new("pending_investmint");
+
+/// Bonded amount of the staker locked for the duration since bond_time until unlock_time
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Lock {
+    pub amount: Uint128,
+    pub duration: u64,
+    pub multiplier: Decimal,
+    /// Start of the bond, the average time of the bonds joining unlocked bond,
+    /// the unbond fee decays with the time since it
+    pub bond_time: u64,
+    /// End of the lock and of its boost, the bond time of unlocked bond
+    pub unlock_time: u64,
+}
+
+impl Lock {
+    /// Weight of the lock at the time, the boost of a locked bond ends at its unlock time
+    pub fn weight(&self, time: u64) -> Uint128 {
+        if self.duration > 0 && self.unlock_time <= time {
+            self.amount
+        } else {
+            self.amount * self.multiplier
+        }
+    }
+
+    /// Locked bond with the boost ending after the time
+    pub fn is_expiring(&self, time: u64) -> bool {
+        self.duration > 0 && self.unlock_time > time
+    }
+}
+
+/// Weight and amount of the locks ending their boost at the same unlock time,
+/// the weight drops to the amount at the unlock time
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct ExpiringWeight {
+    pub weight: Uint128,
+    pub amount: Uint128,
+}
+
+/// Expiring weights of all stakers by unlock time, checkpointed by block time in seconds
+/// for historical queries replaying the distribution
+pub const EXPIRING_WEIGHTS: SnapshotMap<u64, ExpiringWeight> = SnapshotMap::new(
+    "expiring_weights",
+    "expiring_weights__checkpoints",
+    "expiring_weights__changelog",
+    Strategy::EveryBlock,
+);
+
+/// Expiring weights with unlock times after the start until the given time at the end of the time,
+/// weights removed since then are found in the changelog
+pub fn read_expiring_weights_at(storage: &dyn Storage, start: u64, time: u64) -> StdResult<Vec<(u64, ExpiringWeight)>> {
+    let mut unlock_times = EXPIRING_WEIGHTS
+        .keys(storage, Some(Bound::exclusive(start)), Some(Bound::inclusive(time)), Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for key in EXPIRING_WEIGHTS.changelog().keys(
+        storage,
+        Some(Bound::exclusive((start, u64::MAX))),
+        Some(Bound::inclusive((time, u64::MAX))),
+        Order::Ascending,
+    ) {
+        unlock_times.push(key?.0);
+    }
+    unlock_times.sort_unstable();
+    unlock_times.dedup();
+
+    let mut expiring = vec![];
+    for unlock_time in unlock_times {
+        if let Some(weight) = EXPIRING_WEIGHTS.may_load_at_height(storage, unlock_time, time + 1)? {
+            expiring.push((unlock_time, weight));
+        }
+    }
+    Ok(expiring)
+}
+
+/// Global reward indexes at the unlock times passed by the distribution,
+/// expiring weights of the stakers are settled with them
+pub const EXPIRED_INDEXES: SnapshotMap<u64, Vec<Decimal>> = SnapshotMap::new(
+    "expired_indexes",
+    "expired_indexes__checkpoints",
+    "expired_indexes__changelog",
+    Strategy::EveryBlock,
+);
+
+pub const LOCK_SEQ: Item<u64> = Item::new("lock_seq");
+pub const LOCKS: Map<(&Addr, u64), Lock> = Map::new("locks");
+
+pub fn next_lock_id(storage: &mut dyn Storage) -> StdResult<u64> {
+    let id = LOCK_SEQ.may_load(storage)?.unwrap_or_default() + 1;
+    LOCK_SEQ.save(storage, &id)?;
+    Ok(id)
+}
+
+pub fn read_locks(storage: &dyn Storage, owner: &Addr) -> StdResult<Vec<(u64, Lock)>> {
+    LOCKS
+        .prefix(owner)
+        .range(storage, None, None, Order::Ascending)
+        .collect()
+}
+
+pub fn store_lock(storage: &mut dyn Storage, owner: &Addr, id: u64, lock: &Lock) -> StdResult<()> {
+    if lock.amount.is_zero() {
+        LOCKS.remove(storage, (owner, id));
+        Ok(())
+    } else {
+        LOCKS.save(storage, (owner, id), lock)
+    }
+}
+
+/// Unbonded amounts waiting for the end of the unbonding period
+pub const CLAIMS: Claims = Claims::new("claims");
+
+/// Positions of the frozen farm are exported to the new farm in the order of staker addresses
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Migration {
+    pub new_farm: Addr,
+    /// Last staker exported to the new farm
+    pub exported_until: Option<Addr>,
+}
+
+pub const MIGRATION: Item<Migration> = Item::new("migration");
@@ -24,7 +24,7 @@ pub enum ExecuteMsg {
     },
     UpdateEntry {
         id: u64,
-        active: String,
+        active: Option<String>,
         source_chain_id: Option<String>,
         destination_chain_id: Option<String>,
         source_channel_id: Option<String>,
@@ -44,6 +44,16 @@ pub enum QueryMsg {
         start_after: Option<u64>,
         limit: Option<u32>,
     },
+    GetEntriesSourceChainId {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        source_chain_id: String,
+    },
+    GetEntriesDestinationChainId {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        destination_chain_id: String,
+    },
     GetEntry { id: u64 }
 }
 
@@ -2,13 +2,15 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 // use cosmwasm_std::{Uint64};
 
-use cosmwasm_std::Addr;
-use cw_storage_plus::{Item, Map};
+use cw_storage_plus::{Index, IndexList, IndexedMap, MultiIndex};
+use hub_base::validating::{validate_by_basic_rule, validate_particle, validate_url_symbols};
+use hub_base::state::ENTRIES_NAMESPACE;
+use hub_base::{ContractError, HubEntry, Registry};
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct Config {
-    pub owner: Option<Addr>,
-}
+pub use hub_base::state::{Config, CONFIG, ENTRY_SEQ};
+
+/// Explorer urls are templates with the `{hash}` placeholder and without a query string
+pub const EXPLORER_URL_SYMBOLS: &[u8] = b"}{:/.-_";
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Entry {
@@ -22,6 +24,41 @@ pub struct Entry {
     pub particle: String,
 }
 
-pub const CONFIG: Item<Config> = Item::new("config");
-pub const ENTRY_SEQ: Item<u64> = Item::new("entry_seq");
-pub const LIST: Map<u64, Entry> = Map::new("list");
+impl HubEntry for Entry {
+    fn validate(&self) -> Result<(), ContractError> {
+        validate_particle(&self.particle)?;
+        validate_by_basic_rule(&self.source_chain_id, "source_chain_id")?;
+        validate_by_basic_rule(&self.destination_chain_id, "destination_chain_id")?;
+        validate_by_basic_rule(&self.source_channel_id, "source_channel_id")?;
+        validate_by_basic_rule(&self.destination_channel_id, "destination_channel_id")?;
+        validate_url_symbols(&self.explorer_url, "explorer_url", EXPLORER_URL_SYMBOLS)
+    }
+}
+
+pub struct EntryIndexes<'a> {
+    pub source_chain_id: MultiIndex<'a, String, Entry, u64>,
+    pub destination_chain_id: MultiIndex<'a, String, Entry, u64>,
+}
+
+impl<'a> IndexList<Entry> for EntryIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Entry>> + '_> {
+        let v: Vec<&dyn Index<Entry>> = vec![&self.source_chain_id, &self.destination_chain_id];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn registry<'a>() -> Registry<'a, Entry, EntryIndexes<'a>> {
+    let indexes = EntryIndexes {
+        source_chain_id: MultiIndex::new(
+            |d| d.source_chain_id.clone(),
+            ENTRIES_NAMESPACE,
+            "list_source_chain_id"
+        ),
+        destination_chain_id: MultiIndex::new(
+            |d| d.destination_chain_id.clone(),
+            ENTRIES_NAMESPACE,
+            "list_destination_chain_id"
+        ),
+    };
+    Registry::new(IndexedMap::new(ENTRIES_NAMESPACE, indexes))
+}
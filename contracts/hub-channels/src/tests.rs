@@ -3,11 +3,14 @@
 #[cfg(test)]
 mod tests {
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-    use cosmwasm_std::{attr, from_binary, Addr};
+    use cosmwasm_std::{attr, from_binary, Addr, Deps};
     use std::vec::Vec;
-    use crate::state::{Entry, CONFIG, Config};
-    use crate::msg::{ ListResponse, QueryMsg, InstantiateMsg, ExecuteMsg};
-    use crate::contract::{query, execute, instantiate};
+    use crate::state::{Entry, CONFIG, Config, registry};
+    use crate::ContractError;
+    use crate::msg::{ ListResponse, QueryMsg, InstantiateMsg, ExecuteMsg, MigrateMsg};
+    use crate::contract::{query, execute, instantiate, migrate};
+    use cw_storage_plus::Map;
+    use hub_base::state::ENTRIES_NAMESPACE;
 
     #[test]
     fn proper_initialization() {
@@ -134,7 +137,7 @@ mod tests {
         // Update entry
         let message = ExecuteMsg::UpdateEntry {
             id: 1,
-            active: "active".to_string(),
+            active: Some("active".to_string()),
             source_chain_id: Some("bostrom-1".to_string()),
             destination_chain_id: Some("cosmos-1".to_string()),
             source_channel_id: Some("channel-256".to_string()),
@@ -225,4 +228,211 @@ mod tests {
             list.entries
         );
     }
+
+    #[test]
+    fn query_entries_by_source_chain_id() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), env.clone(), info.clone(), InstantiateMsg { owner: None }).unwrap();
+        for source_chain_id in ["bostrom", "osmosis-1", "bostrom"] {
+            let msg = ExecuteMsg::CreateEntry {
+                active: "active".to_string(),
+                source_chain_id: source_chain_id.to_string(),
+                destination_chain_id: "cosmoshub-4".to_string(),
+                source_channel_id: "channel-256".to_string(),
+                destination_channel_id: "channel-1".to_string(),
+                explorer_url: "https://explorer.com/{hash}".to_string(),
+                particle: None,
+            };
+            execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        }
+
+        let query_ids = |source_chain_id: &str, start_after: Option<u64>, limit: Option<u32>| -> Vec<u64> {
+            let msg = QueryMsg::GetEntriesSourceChainId { start_after, limit, source_chain_id: source_chain_id.to_string() };
+            let list: ListResponse = from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+            list.entries.iter().map(|entry| entry.id).collect()
+        };
+        assert_eq!(query_ids("bostrom", None, None), vec![1, 3]);
+        assert_eq!(query_ids("bostrom", None, Some(1)), vec![1]);
+        assert_eq!(query_ids("bostrom", Some(1), Some(1)), vec![3]);
+        assert_eq!(query_ids("osmosis-1", None, None), vec![2]);
+        assert_eq!(query_ids("bostrom", Some(3), None), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn migrate_indexes_list_entries() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg { owner: None }).unwrap();
+
+        // entries saved by the former LIST map have no index entries
+        let entry = |id: u64, source_chain_id: &str, destination_chain_id: &str| Entry {
+            id,
+            active: "active".to_string(),
+            source_chain_id: source_chain_id.to_string(),
+            destination_chain_id: destination_chain_id.to_string(),
+            source_channel_id: "channel-256".to_string(),
+            destination_channel_id: "channel-1".to_string(),
+            explorer_url: "https://explorer.com/{hash}".to_string(),
+            particle: "".to_string(),
+        };
+        let list: Map<u64, Entry> = Map::new(ENTRIES_NAMESPACE);
+        list.save(deps.as_mut().storage, 1, &entry(1, "bostrom", "osmosis-1")).unwrap();
+        list.save(deps.as_mut().storage, 2, &entry(2, "osmosis-1", "bostrom")).unwrap();
+        list.save(deps.as_mut().storage, 3, &entry(3, "bostrom", "cosmoshub-4")).unwrap();
+
+        let query_ids = |deps: Deps, msg: QueryMsg| -> Vec<u64> {
+            let list: ListResponse = from_binary(&query(deps, env.clone(), msg).unwrap()).unwrap();
+            list.entries.iter().map(|entry| entry.id).collect()
+        };
+        let by_source = |chain_id: &str| QueryMsg::GetEntriesSourceChainId {
+            start_after: None,
+            limit: None,
+            source_chain_id: chain_id.to_string(),
+        };
+        let by_destination = |chain_id: &str| QueryMsg::GetEntriesDestinationChainId {
+            start_after: None,
+            limit: None,
+            destination_chain_id: chain_id.to_string(),
+        };
+        assert!(query_ids(deps.as_ref(), by_source("bostrom")).is_empty());
+        assert!(query_ids(deps.as_ref(), by_destination("bostrom")).is_empty());
+
+        // both indexes are built from the source and destination of each entry
+        migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+        assert_eq!(query_ids(deps.as_ref(), by_source("bostrom")), vec![1, 3]);
+        assert_eq!(query_ids(deps.as_ref(), by_source("osmosis-1")), vec![2]);
+        assert_eq!(query_ids(deps.as_ref(), by_destination("bostrom")), vec![2]);
+        assert_eq!(query_ids(deps.as_ref(), by_destination("osmosis-1")), vec![1]);
+        assert_eq!(query_ids(deps.as_ref(), by_destination("cosmoshub-4")), vec![3]);
+        assert_eq!(query_ids(deps.as_ref(), QueryMsg::GetEntries { start_after: None, limit: None }), vec![1, 2, 3]);
+    }
+
+    fn update_msg(field: &str, value: &str) -> ExecuteMsg {
+        let value_of = |name: &str| if name == field { Some(value.to_string()) } else { None };
+        ExecuteMsg::UpdateEntry {
+            id: 1,
+            active: value_of("active"),
+            source_chain_id: value_of("source_chain_id"),
+            destination_chain_id: value_of("destination_chain_id"),
+            source_channel_id: value_of("source_channel_id"),
+            destination_channel_id: value_of("destination_channel_id"),
+            explorer_url: value_of("explorer_url"),
+            particle: value_of("particle"),
+        }
+    }
+
+    #[test]
+    fn update_entry_fields() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), env.clone(), info.clone(), InstantiateMsg { owner: None }).unwrap();
+
+        let msg = ExecuteMsg::CreateEntry {
+            active: "active".to_string(),
+            source_chain_id: "bostrom-1".to_string(),
+            destination_chain_id: "cosmos-1".to_string(),
+            source_channel_id: "channel-256".to_string(),
+            destination_channel_id: "channel-1".to_string(),
+            explorer_url: "https://explorer.com/{hash}".to_string(),
+            particle: Some("QmYpTB36duejmy1szbdL1D2EzC5fgRL4dyhSFsHkMYPtny".to_string()),
+        };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        let mut expected = registry().entry(&deps.storage, 1).unwrap();
+
+        // nothing provided, nothing updated
+        execute(deps.as_mut(), env.clone(), info.clone(), update_msg("", "")).unwrap();
+        assert_eq!(registry().entry(&deps.storage, 1).unwrap(), expected);
+
+        // every field is updated alone and the other fields are kept, empty particle clears it
+        for (field, value) in [
+            ("active", "inactive"),
+            ("source_chain_id", "bostrom"),
+            ("destination_chain_id", "osmosis-1"),
+            ("source_channel_id", "channel-2"),
+            ("destination_channel_id", "channel-95"),
+            ("explorer_url", "https://mintscan.io/osmosis/txs/{hash}"),
+            ("particle", "QmRX8qYgeZoYM3M5zzQaWEpVFdpin6FvVXvp6RPQK3oufV"),
+            ("particle", ""),
+        ] {
+            execute(deps.as_mut(), env.clone(), info.clone(), update_msg(field, value)).unwrap();
+            match field {
+                "active" => expected.active = value.to_string(),
+                "source_chain_id" => expected.source_chain_id = value.to_string(),
+                "destination_chain_id" => expected.destination_chain_id = value.to_string(),
+                "source_channel_id" => expected.source_channel_id = value.to_string(),
+                "destination_channel_id" => expected.destination_channel_id = value.to_string(),
+                "explorer_url" => expected.explorer_url = value.to_string(),
+                "particle" => expected.particle = value.to_string(),
+                _ => unreachable!(),
+            }
+            assert_eq!(registry().entry(&deps.storage, 1).unwrap(), expected);
+        }
+
+        // invalid values are rejected with the error of their field
+        for (field, value, err) in [
+            ("source_chain_id", "Bostrom", ContractError::InvalidBasicValue { field: "source_chain_id".to_string() }),
+            ("destination_chain_id", "osmosis_1", ContractError::InvalidBasicValue { field: "destination_chain_id".to_string() }),
+            ("source_channel_id", "channel 2", ContractError::InvalidBasicValue { field: "source_channel_id".to_string() }),
+            ("destination_channel_id", "Channel-95", ContractError::InvalidBasicValue { field: "destination_channel_id".to_string() }),
+            ("explorer_url", "https://explorer.com/tx?hash=<hash>", ContractError::InvalidUrl { field: "explorer_url".to_string() }),
+            ("explorer_url", "https://explorer.com/txs/hash={hash}", ContractError::InvalidUrl { field: "explorer_url".to_string() }),
+            ("particle", "particle", ContractError::InvalidCid { field: "particle".to_string() }),
+        ] {
+            let res = execute(deps.as_mut(), env.clone(), info.clone(), update_msg(field, value));
+            assert_eq!(res.unwrap_err(), err);
+            assert_eq!(registry().entry(&deps.storage, 1).unwrap(), expected);
+        }
+
+        // a provided particle of a new entry is a cid
+        let msg = ExecuteMsg::CreateEntry {
+            active: "active".to_string(),
+            source_chain_id: "bostrom-1".to_string(),
+            destination_chain_id: "cosmos-1".to_string(),
+            source_channel_id: "channel-256".to_string(),
+            destination_channel_id: "channel-1".to_string(),
+            explorer_url: "https://explorer.com/{hash}".to_string(),
+            particle: Some("".to_string()),
+        };
+        let res = execute(deps.as_mut(), env, info, msg);
+        assert_eq!(res.unwrap_err(), ContractError::InvalidCid { field: "particle".to_string() });
+    }
+
+    #[test]
+    fn explorer_url_is_validated_on_create_and_update() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), env.clone(), info.clone(), InstantiateMsg { owner: None }).unwrap();
+
+        // explorer urls are templates with the hash placeholder and without a query string
+        let create_msg = |explorer_url: &str| ExecuteMsg::CreateEntry {
+            active: "active".to_string(),
+            source_chain_id: "bostrom-1".to_string(),
+            destination_chain_id: "cosmos-1".to_string(),
+            source_channel_id: "channel-256".to_string(),
+            destination_channel_id: "channel-1".to_string(),
+            explorer_url: explorer_url.to_string(),
+            particle: None,
+        };
+        let res = execute(deps.as_mut(), env.clone(), info.clone(), create_msg("https://explorer.com/txs/hash={hash}"));
+        assert_eq!(res.unwrap_err(), ContractError::InvalidUrl { field: "explorer_url".to_string() });
+        execute(deps.as_mut(), env.clone(), info.clone(), create_msg("https://explorer.com/{hash}")).unwrap();
+
+        let msg = ExecuteMsg::UpdateEntry {
+            id: 1,
+            active: None,
+            source_chain_id: None,
+            destination_chain_id: None,
+            source_channel_id: None,
+            destination_channel_id: None,
+            explorer_url: Some("https://explorer.com/txs/hash={hash}".to_string()),
+            particle: None,
+        };
+        let res = execute(deps.as_mut(), env, info, msg);
+        assert_eq!(res.unwrap_err(), ContractError::InvalidUrl { field: "explorer_url".to_string() });
+        assert_eq!(registry().entry(&deps.storage, 1).unwrap().explorer_url, "https://explorer.com/{hash}");
+    }
 }
@@ -41,6 +41,11 @@ pub enum QueryMsg {
         start_after: Option<u64>,
         limit: Option<u32>,
     },
+    GetEntriesChainId {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        chain_id: String,
+    },
     GetEntry { id: u64 }
 }
 
@@ -2,13 +2,12 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 // use cosmwasm_std::{Uint64};
 
-use cosmwasm_std::Addr;
-use cw_storage_plus::{Item, Map};
+use cw_storage_plus::{Index, IndexList, IndexedMap, MultiIndex};
+use hub_base::validating::{validate_by_basic_rule, validate_ipfs_cid, validate_particle, validate_url};
+use hub_base::state::ENTRIES_NAMESPACE;
+use hub_base::{ContractError, HubEntry, Registry};
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct Config {
-    pub owner: Option<Addr>,
-}
+pub use hub_base::state::{Config, CONFIG, ENTRY_SEQ};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Entry {
@@ -21,6 +20,35 @@ pub struct Entry {
     pub particle: String,
 }
 
-pub const CONFIG: Item<Config> = Item::new("config");
-pub const ENTRY_SEQ: Item<u64> = Item::new("entry_seq");
-pub const LIST: Map<u64, Entry> = Map::new("list");
+impl HubEntry for Entry {
+    fn validate(&self) -> Result<(), ContractError> {
+        validate_particle(&self.particle)?;
+        validate_by_basic_rule(&self.address, "address")?;
+        validate_ipfs_cid(&self.query_cid, "query_cid")?;
+        validate_ipfs_cid(&self.execute_cid, "execute_cid")?;
+        validate_url(&self.version, "version")?;
+        validate_url(&self.chain_id, "chain_id")
+    }
+}
+
+pub struct EntryIndexes<'a> {
+    pub chain_id: MultiIndex<'a, String, Entry, u64>,
+}
+
+impl<'a> IndexList<Entry> for EntryIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Entry>> + '_> {
+        let v: Vec<&dyn Index<Entry>> = vec![&self.chain_id];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn registry<'a>() -> Registry<'a, Entry, EntryIndexes<'a>> {
+    let indexes = EntryIndexes {
+        chain_id: MultiIndex::new(
+            |d| d.chain_id.clone(),
+            ENTRIES_NAMESPACE,
+            "list_chain_id"
+        ),
+    };
+    Registry::new(IndexedMap::new(ENTRIES_NAMESPACE, indexes))
+}
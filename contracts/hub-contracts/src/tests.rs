@@ -7,7 +7,8 @@ mod tests {
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
     use cosmwasm_std::{attr, from_binary, Addr};
     use std::vec::Vec;
-    use crate::state::{Entry, CONFIG, Config};
+    use crate::state::{Entry, CONFIG, Config, registry};
+    use crate::ContractError;
     use crate::msg::{ ListResponse, QueryMsg, InstantiateMsg, ExecuteMsg};
     // use crate::msg::{ ExecuteMsg, InstantiateMsg, QueryMsg};
     use crate::contract::{query, execute, instantiate};
@@ -222,4 +223,107 @@ mod tests {
             list.entries
         );
     }
+
+    #[test]
+    fn query_entries_by_chain_id() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), env.clone(), info.clone(), InstantiateMsg { owner: None }).unwrap();
+        for chain_id in ["bostrom", "space-pussy", "bostrom"] {
+            let msg = ExecuteMsg::CreateEntry {
+                address: "bostrom1fzm6gzyccl8jvdv3qq6hp9vs6ylaruervs4m06c7k0ntzn2f8faq7ha2z2".to_string(),
+                query_cid: "QmYpTB36duejmy1szbdL1D2EzC5fgRL4dyhSFsHkMYPtny".to_string(),
+                execute_cid: "QmYpTB36duejmy1szbdL1D2EzC5fgRL4dyhSFsHkMYPtny".to_string(),
+                version: "v=1&s=2&j=3".to_string(),
+                chain_id: chain_id.to_string(),
+                particle: None,
+            };
+            execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        }
+
+        let query_ids = |chain_id: &str, start_after: Option<u64>, limit: Option<u32>| -> Vec<u64> {
+            let msg = QueryMsg::GetEntriesChainId { start_after, limit, chain_id: chain_id.to_string() };
+            let list: ListResponse = from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+            list.entries.iter().map(|entry| entry.id).collect()
+        };
+        assert_eq!(query_ids("bostrom", None, None), vec![1, 3]);
+        assert_eq!(query_ids("bostrom", None, Some(1)), vec![1]);
+        assert_eq!(query_ids("bostrom", Some(1), Some(1)), vec![3]);
+        assert_eq!(query_ids("space-pussy", None, None), vec![2]);
+        assert_eq!(query_ids("bostrom", Some(3), None), Vec::<u64>::new());
+    }
+
+    fn update_msg(field: &str, value: &str) -> ExecuteMsg {
+        let value_of = |name: &str| if name == field { Some(value.to_string()) } else { None };
+        ExecuteMsg::UpdateEntry {
+            id: 1,
+            address: value_of("address"),
+            query_cid: value_of("query_cid"),
+            execute_cid: value_of("execute_cid"),
+            version: value_of("version"),
+            chain_id: value_of("chain_id"),
+            particle: value_of("particle"),
+        }
+    }
+
+    #[test]
+    fn update_entry_fields() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), env.clone(), info.clone(), InstantiateMsg { owner: None }).unwrap();
+
+        let msg = ExecuteMsg::CreateEntry {
+            address: "bostrom1fzm6gzyccl8jvdv3qq6hp9vs6ylaruervs4m06c7k0ntzn2f8faq7ha2z2".to_string(),
+            query_cid: "QmYpTB36duejmy1szbdL1D2EzC5fgRL4dyhSFsHkMYPtny".to_string(),
+            execute_cid: "QmYpTB36duejmy1szbdL1D2EzC5fgRL4dyhSFsHkMYPtny".to_string(),
+            version: "v=1&s=2&j=3".to_string(),
+            chain_id: "test-1".to_string(),
+            particle: Some("QmYpTB36duejmy1szbdL1D2EzC5fgRL4dyhSFsHkMYPtny".to_string()),
+        };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        let mut expected = registry().entry(&deps.storage, 1).unwrap();
+
+        // nothing provided, nothing updated
+        execute(deps.as_mut(), env.clone(), info.clone(), update_msg("", "")).unwrap();
+        assert_eq!(registry().entry(&deps.storage, 1).unwrap(), expected);
+
+        // every field is updated alone and the other fields are kept, empty particle clears it
+        for (field, value) in [
+            ("address", "bostrom1fzm6gzyccl8jvdv3qq6hp9vs6ylaruervs4m06c7k0ntzn2f8faq7ha2z3"),
+            ("query_cid", "QmRX8qYgeZoYM3M5zzQaWEpVFdpin6FvVXvp6RPQK3oufV"),
+            ("execute_cid", "QmRX8qYgeZoYM3M5zzQaWEpVFdpin6FvVXvp6RPQK3oufV"),
+            ("version", "v=2"),
+            ("chain_id", "bostrom"),
+            ("particle", "QmRX8qYgeZoYM3M5zzQaWEpVFdpin6FvVXvp6RPQK3oufV"),
+            ("particle", ""),
+        ] {
+            execute(deps.as_mut(), env.clone(), info.clone(), update_msg(field, value)).unwrap();
+            match field {
+                "address" => expected.address = value.to_string(),
+                "query_cid" => expected.query_cid = value.to_string(),
+                "execute_cid" => expected.execute_cid = value.to_string(),
+                "version" => expected.version = value.to_string(),
+                "chain_id" => expected.chain_id = value.to_string(),
+                "particle" => expected.particle = value.to_string(),
+                _ => unreachable!(),
+            }
+            assert_eq!(registry().entry(&deps.storage, 1).unwrap(), expected);
+        }
+
+        // invalid values are rejected with the error of their field
+        for (field, value, err) in [
+            ("address", "Bostrom1", ContractError::InvalidBasicValue { field: "address".to_string() }),
+            ("query_cid", "query", ContractError::InvalidCid { field: "query_cid".to_string() }),
+            ("execute_cid", "execute", ContractError::InvalidCid { field: "execute_cid".to_string() }),
+            ("version", "v 2", ContractError::InvalidUrl { field: "version".to_string() }),
+            ("chain_id", "test 1", ContractError::InvalidUrl { field: "chain_id".to_string() }),
+            ("particle", "particle", ContractError::InvalidCid { field: "particle".to_string() }),
+        ] {
+            let res = execute(deps.as_mut(), env.clone(), info.clone(), update_msg(field, value));
+            assert_eq!(res.unwrap_err(), err);
+            assert_eq!(registry().entry(&deps.storage, 1).unwrap(), expected);
+        }
+    }
 }
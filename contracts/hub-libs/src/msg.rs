@@ -21,8 +21,8 @@ pub enum ExecuteMsg {
     },
     UpdateEntry {
         id: u64,
-        address: String,
-        init_particle: Option<String>,        
+        address: Option<String>,
+        init_particle: Option<String>,
         version: Option<String>,
         chain_id: Option<String>,
         particle: Option<String>,
@@ -39,6 +39,11 @@ pub enum QueryMsg {
         start_after: Option<u64>,
         limit: Option<u32>,
     },
+    GetEntriesChainId {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        chain_id: String,
+    },
     GetEntry { id: u64 }
 }
 
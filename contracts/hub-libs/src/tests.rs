@@ -7,7 +7,8 @@ mod tests {
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
     use cosmwasm_std::{attr, from_binary, Addr};
     use std::vec::Vec;
-    use crate::state::{Entry, CONFIG, Config};
+    use crate::state::{Entry, CONFIG, Config, registry};
+    use crate::ContractError;
     use crate::msg::{ ListResponse, QueryMsg, InstantiateMsg, ExecuteMsg};
     // use crate::msg::{ ExecuteMsg, InstantiateMsg, QueryMsg};
     use crate::contract::{query, execute, instantiate};
@@ -214,4 +215,72 @@ mod tests {
             list.entries
         );
     }
+
+    fn update_msg(field: &str, value: &str) -> ExecuteMsg {
+        let value_of = |name: &str| if name == field { Some(value.to_string()) } else { None };
+        ExecuteMsg::UpdateEntry {
+            id: 1,
+            address: value_of("address"),
+            init_particle: value_of("init_particle"),
+            version: value_of("version"),
+            chain_id: value_of("chain_id"),
+            particle: value_of("particle"),
+        }
+    }
+
+    #[test]
+    fn update_entry_fields() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), env.clone(), info.clone(), InstantiateMsg { owner: None }).unwrap();
+
+        let msg = ExecuteMsg::CreateEntry {
+            address: "bostrom1fzm6gzyccl8jvdv3qq6hp9vs6ylaruervs4m06c7k0ntzn2f8faq7ha2z2".to_string(),
+            init_particle: "QmYpTB36duejmy1szbdL1D2EzC5fgRL4dyhSFsHkMYPtny".to_string(),
+            version: "v=1&s=2&j=3".to_string(),
+            chain_id: "test-1".to_string(),
+            particle: Some("QmYpTB36duejmy1szbdL1D2EzC5fgRL4dyhSFsHkMYPtny".to_string()),
+        };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        let mut expected = registry().entry(&deps.storage, 1).unwrap();
+
+        // nothing provided, nothing updated
+        execute(deps.as_mut(), env.clone(), info.clone(), update_msg("", "")).unwrap();
+        assert_eq!(registry().entry(&deps.storage, 1).unwrap(), expected);
+
+        // every field is updated alone and the other fields are kept, empty particle clears it
+        for (field, value) in [
+            ("address", "bostrom1fzm6gzyccl8jvdv3qq6hp9vs6ylaruervs4m06c7k0ntzn2f8faq7ha2z3"),
+            ("init_particle", "QmRX8qYgeZoYM3M5zzQaWEpVFdpin6FvVXvp6RPQK3oufV"),
+            ("version", "v=2"),
+            ("chain_id", "bostrom"),
+            ("particle", "QmRX8qYgeZoYM3M5zzQaWEpVFdpin6FvVXvp6RPQK3oufV"),
+            ("particle", ""),
+        ] {
+            execute(deps.as_mut(), env.clone(), info.clone(), update_msg(field, value)).unwrap();
+            match field {
+                "address" => expected.address = value.to_string(),
+                "init_particle" => expected.init_particle = value.to_string(),
+                "version" => expected.version = value.to_string(),
+                "chain_id" => expected.chain_id = value.to_string(),
+                "particle" => expected.particle = value.to_string(),
+                _ => unreachable!(),
+            }
+            assert_eq!(registry().entry(&deps.storage, 1).unwrap(), expected);
+        }
+
+        // invalid values are rejected with the error of their field
+        for (field, value, err) in [
+            ("address", "Bostrom1", ContractError::InvalidBasicValue { field: "address".to_string() }),
+            ("init_particle", "init", ContractError::InvalidCid { field: "init_particle".to_string() }),
+            ("version", "v 2", ContractError::InvalidUrl { field: "version".to_string() }),
+            ("chain_id", "test 1", ContractError::InvalidUrl { field: "chain_id".to_string() }),
+            ("particle", "particle", ContractError::InvalidCid { field: "particle".to_string() }),
+        ] {
+            let res = execute(deps.as_mut(), env.clone(), info.clone(), update_msg(field, value));
+            assert_eq!(res.unwrap_err(), err);
+            assert_eq!(registry().entry(&deps.storage, 1).unwrap(), expected);
+        }
+    }
 }
@@ -1,15 +1,13 @@
-use cosmwasm_std::{
-    Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, to_binary,
-};
+use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult, to_binary};
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cw2::{get_contract_version, set_contract_version};
+use hub_base::ContractError;
+use hub_base::execute::execute_update_owner;
 
-use crate::error::ContractError;
-use crate::execute::{execute_create_entry, execute_delete_entry, execute_update_entry, execute_update_owner};
+use crate::execute::{execute_create_entry, execute_delete_entry, execute_update_entry};
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
-use crate::query::{query_entry, query_list};
-use crate::state::{Config, CONFIG, ENTRY_SEQ};
+use crate::query::{query_entry, query_list, query_list_by_protocol};
+use crate::state::registry;
 
 //@TODO git version iteract
 const CONTRACT_NAME: &str = "hub-networks";
@@ -22,34 +20,18 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
-    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-
-    let owner = msg
-        .owner
-        .and_then(|addr_string| deps.api.addr_validate(addr_string.as_str()).ok())
-        .unwrap_or(info.sender);
-
-    let config = Config {
-        owner: Some(owner.clone()),
-    };
-    CONFIG.save(deps.storage, &config)?;
-
-    ENTRY_SEQ.save(deps.storage, &0u64)?;
-
-    Ok(Response::new()
-        .add_attribute("method", "instantiate")
-        .add_attribute("owner", owner))
+    hub_base::execute::instantiate(deps, info, msg.owner, CONTRACT_NAME, CONTRACT_VERSION)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
-    env: Env,
+    _env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::UpdateOwner { new_owner } => execute_update_owner(deps, env, info, new_owner),
+        ExecuteMsg::UpdateOwner { new_owner } => execute_update_owner(deps, info, new_owner),
         ExecuteMsg::CreateEntry {
             name,
             chain_id,
@@ -81,6 +63,9 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::GetEntries { start_after, limit } => {
             to_binary(&query_list(deps, start_after, limit)?)
         }
+        QueryMsg::GetEntriesProtocol { start_after, limit, protocol } => {
+            to_binary(&query_list_by_protocol(deps, start_after, limit, protocol)?)
+        }
         QueryMsg::GetEntry { id } => {
             to_binary(&query_entry(deps, id)?)
         }
@@ -89,12 +74,7 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
-    let version = get_contract_version(deps.storage)?;
-    if version.contract != CONTRACT_NAME {
-        return Err(ContractError::CannotMigrate {
-            previous_contract: version.contract,
-        });
-    }
+    registry().migrate(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     Ok(Response::default())
-}
\ No newline at end of file
+}
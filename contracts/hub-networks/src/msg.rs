@@ -45,6 +45,11 @@ pub enum QueryMsg {
         start_after: Option<u64>,
         limit: Option<u32>,
     },
+    GetEntriesProtocol {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        protocol: String,
+    },
     GetEntry { id: u64 }
 }
 
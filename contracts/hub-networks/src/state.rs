@@ -1,14 +1,12 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-// use cosmwasm_std::{Uint64};
 
-use cosmwasm_std::Addr;
-use cw_storage_plus::{Item, Map};
+use cw_storage_plus::{Index, IndexList, IndexedMap, MultiIndex};
+use hub_base::validating::{validate_by_basic_rule, validate_ipfs_cid, validate_particle, validate_period};
+use hub_base::state::ENTRIES_NAMESPACE;
+use hub_base::{ContractError, HubEntry, Registry};
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct Config {
-    pub owner: Option<Addr>,
-}
+pub use hub_base::state::{Config, CONFIG, ENTRY_SEQ};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Entry {
@@ -24,6 +22,37 @@ pub struct Entry {
     
 }
 
-pub const CONFIG: Item<Config> = Item::new("config");
-pub const ENTRY_SEQ: Item<u64> = Item::new("entry_seq");
-pub const LIST: Map<u64, Entry> = Map::new("list");
+impl HubEntry for Entry {
+    fn validate(&self) -> Result<(), ContractError> {
+        validate_ipfs_cid(&self.logo, "logo")?;
+        validate_particle(&self.particle)?;
+        validate_by_basic_rule(&self.chain_id, "chain_id")?;
+        validate_by_basic_rule(&self.prefix, "prefix")?;
+        validate_by_basic_rule(&self.genesis_hash, "genesis_hash")?;
+        validate_by_basic_rule(&self.name, "name")?;
+        validate_by_basic_rule(&self.protocol, "protocol")?;
+        validate_period(&self.unbonding_period, "unbonding_period")
+    }
+}
+
+pub struct EntryIndexes<'a> {
+    pub protocol: MultiIndex<'a, String, Entry, u64>,
+}
+
+impl<'a> IndexList<Entry> for EntryIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Entry>> + '_> {
+        let v: Vec<&dyn Index<Entry>> = vec![&self.protocol];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn registry<'a>() -> Registry<'a, Entry, EntryIndexes<'a>> {
+    let indexes = EntryIndexes {
+        protocol: MultiIndex::new(
+            |d| d.protocol.clone(),
+            ENTRIES_NAMESPACE,
+            "list_protocol"
+        ),
+    };
+    Registry::new(IndexedMap::new(ENTRIES_NAMESPACE, indexes))
+}
@@ -5,7 +5,8 @@ mod tests {
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
     use cosmwasm_std::{attr, from_binary, Addr};
     use std::vec::Vec;
-    use crate::state::{Entry, CONFIG, Config};
+    use crate::state::{Entry, CONFIG, Config, registry};
+    use crate::ContractError;
     use crate::msg::{ ListResponse, QueryMsg, InstantiateMsg, ExecuteMsg};
     use crate::contract::{query, execute, instantiate};
 
@@ -235,4 +236,119 @@ mod tests {
             list.entries
         );
     }
+
+    #[test]
+    fn query_entries_by_protocol() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), env.clone(), info.clone(), InstantiateMsg { owner: None }).unwrap();
+        for protocol in ["cosmos", "ethereum", "cosmos"] {
+            let msg = ExecuteMsg::CreateEntry {
+                name: "tst".to_string(),
+                protocol: protocol.to_string(),
+                chain_id: "cyber-1".to_string(),
+                prefix: "cyb".to_string(),
+                genesis_hash: "bostrom0x1".to_string(),
+                unbonding_period: "42".to_string(),
+                logo: "QmYpTB36duejmy1szbdL1D2EzC5fgRL4dyhSFsHkMYPtny".to_string(),
+                particle: None,
+            };
+            execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        }
+
+        let query_ids = |protocol: &str, start_after: Option<u64>, limit: Option<u32>| -> Vec<u64> {
+            let msg = QueryMsg::GetEntriesProtocol { start_after, limit, protocol: protocol.to_string() };
+            let list: ListResponse = from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+            list.entries.iter().map(|entry| entry.id).collect()
+        };
+        assert_eq!(query_ids("cosmos", None, None), vec![1, 3]);
+        assert_eq!(query_ids("cosmos", None, Some(1)), vec![1]);
+        assert_eq!(query_ids("cosmos", Some(1), Some(1)), vec![3]);
+        assert_eq!(query_ids("ethereum", None, None), vec![2]);
+        assert_eq!(query_ids("cosmos", Some(3), None), Vec::<u64>::new());
+    }
+
+    fn update_msg(field: &str, value: &str) -> ExecuteMsg {
+        let value_of = |name: &str| if name == field { Some(value.to_string()) } else { None };
+        ExecuteMsg::UpdateEntry {
+            id: 1,
+            name: value_of("name"),
+            chain_id: value_of("chain_id"),
+            prefix: value_of("prefix"),
+            genesis_hash: value_of("genesis_hash"),
+            protocol: value_of("protocol"),
+            unbonding_period: value_of("unbonding_period"),
+            logo: value_of("logo"),
+            particle: value_of("particle"),
+        }
+    }
+
+    #[test]
+    fn update_entry_fields() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), env.clone(), info.clone(), InstantiateMsg { owner: None }).unwrap();
+
+        let msg = ExecuteMsg::CreateEntry {
+            name: "tst".to_string(),
+            protocol: "tst2".to_string(),
+            chain_id: "cyber-1".to_string(),
+            prefix: "cyb".to_string(),
+            genesis_hash: "bostrom0x1".to_string(),
+            unbonding_period: "42".to_string(),
+            logo: "QmYpTB36duejmy1szbdL1D2EzC5fgRL4dyhSFsHkMYPtny".to_string(),
+            particle: Some("QmYpTB36duejmy1szbdL1D2EzC5fgRL4dyhSFsHkMYPtny".to_string()),
+        };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        let mut expected = registry().entry(&deps.storage, 1).unwrap();
+
+        // nothing provided, nothing updated
+        execute(deps.as_mut(), env.clone(), info.clone(), update_msg("", "")).unwrap();
+        assert_eq!(registry().entry(&deps.storage, 1).unwrap(), expected);
+
+        // every field is updated alone and the other fields are kept, empty particle clears it
+        for (field, value) in [
+            ("name", "bostrom"),
+            ("chain_id", "bostrom"),
+            ("prefix", "bostrom"),
+            ("genesis_hash", "0x2"),
+            ("protocol", "cosmos"),
+            ("unbonding_period", "1814400"),
+            ("logo", "QmRX8qYgeZoYM3M5zzQaWEpVFdpin6FvVXvp6RPQK3oufV"),
+            ("particle", "QmRX8qYgeZoYM3M5zzQaWEpVFdpin6FvVXvp6RPQK3oufV"),
+            ("particle", ""),
+        ] {
+            execute(deps.as_mut(), env.clone(), info.clone(), update_msg(field, value)).unwrap();
+            match field {
+                "name" => expected.name = value.to_string(),
+                "chain_id" => expected.chain_id = value.to_string(),
+                "prefix" => expected.prefix = value.to_string(),
+                "genesis_hash" => expected.genesis_hash = value.to_string(),
+                "protocol" => expected.protocol = value.to_string(),
+                "unbonding_period" => expected.unbonding_period = value.to_string(),
+                "logo" => expected.logo = value.to_string(),
+                "particle" => expected.particle = value.to_string(),
+                _ => unreachable!(),
+            }
+            assert_eq!(registry().entry(&deps.storage, 1).unwrap(), expected);
+        }
+
+        // invalid values are rejected with the error of their field
+        for (field, value, err) in [
+            ("name", "Bostrom", ContractError::InvalidBasicValue { field: "name".to_string() }),
+            ("chain_id", "bostrom_1", ContractError::InvalidBasicValue { field: "chain_id".to_string() }),
+            ("prefix", "Boot", ContractError::InvalidBasicValue { field: "prefix".to_string() }),
+            ("genesis_hash", "0xABC", ContractError::InvalidBasicValue { field: "genesis_hash".to_string() }),
+            ("protocol", "Cosmos", ContractError::InvalidBasicValue { field: "protocol".to_string() }),
+            ("unbonding_period", "21d", ContractError::InvalidPeriod { field: "unbonding_period".to_string() }),
+            ("logo", "logo", ContractError::InvalidCid { field: "logo".to_string() }),
+            ("particle", "particle", ContractError::InvalidCid { field: "particle".to_string() }),
+        ] {
+            let res = execute(deps.as_mut(), env.clone(), info.clone(), update_msg(field, value));
+            assert_eq!(res.unwrap_err(), err);
+            assert_eq!(registry().entry(&deps.storage, 1).unwrap(), expected);
+        }
+    }
 }
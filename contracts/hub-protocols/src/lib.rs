@@ -1,10 +1,8 @@
 pub mod contract;
-mod error;
 pub mod msg;
 pub mod execute;
 pub mod state;
-pub mod validating;
 mod tests;
 mod query;
 
-pub use crate::error::ContractError;
+pub use hub_base::ContractError;
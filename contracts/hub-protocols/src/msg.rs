@@ -18,7 +18,7 @@ pub enum ExecuteMsg {
     },
     UpdateEntry {
         id: u64,
-        data_type: String,
+        data_type: Option<String>,
         particle: Option<String>,
     },
     DeleteEntry {
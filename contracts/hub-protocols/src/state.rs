@@ -1,13 +1,12 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::Addr;
-use cw_storage_plus::{Item, Map};
+use cw_storage_plus::{Index, IndexList, IndexedMap};
+use hub_base::validating::{validate_by_basic_rule, validate_particle};
+use hub_base::state::ENTRIES_NAMESPACE;
+use hub_base::{ContractError, HubEntry, Registry};
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct Config {
-    pub owner: Option<Addr>,
-}
+pub use hub_base::state::{Config, CONFIG, ENTRY_SEQ};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Entry {
@@ -16,6 +15,21 @@ pub struct Entry {
     pub particle: String,
 }
 
-pub const CONFIG: Item<Config> = Item::new("config");
-pub const ENTRY_SEQ: Item<u64> = Item::new("entry_seq");
-pub const LIST: Map<u64, Entry> = Map::new("list");
+impl HubEntry for Entry {
+    fn validate(&self) -> Result<(), ContractError> {
+        validate_particle(&self.particle)?;
+        validate_by_basic_rule(&self.data_type, "data_type")
+    }
+}
+
+pub struct EntryIndexes {}
+
+impl IndexList<Entry> for EntryIndexes {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Entry>> + '_> {
+        Box::new(std::iter::empty())
+    }
+}
+
+pub fn registry<'a>() -> Registry<'a, Entry, EntryIndexes> {
+    Registry::new(IndexedMap::new(ENTRIES_NAMESPACE, EntryIndexes {}))
+}
@@ -7,7 +7,8 @@ mod tests {
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
     use cosmwasm_std::{attr, from_binary, Addr};
     use std::vec::Vec;
-    use crate::state::{Entry, CONFIG, Config};
+    use crate::state::{Entry, CONFIG, Config, registry};
+    use crate::ContractError;
     use crate::msg::{ ListResponse, QueryMsg, InstantiateMsg, ExecuteMsg};
     // use crate::msg::{ ExecuteMsg, InstantiateMsg, QueryMsg};
     use crate::contract::{query, execute, instantiate};
@@ -118,10 +119,9 @@ mod tests {
         // Update entry
         let message = ExecuteMsg::UpdateEntry {
             id: 1,
-            data_type: "testchain-1".to_string(),
-            particle: None,
-            // particle: Some("QmYpTB36duejmy1szbdL1D2EzC5fgRL4dyhSFsHkMYPtny".to_string()),
-            //None
+            data_type: Some("testchain-1".to_string()),
+            // empty particle clears the particle of the entry
+            particle: Some("".to_string()),
         };
 
         let res = execute(deps.as_mut(), env.clone(), info.clone(), message).unwrap();
@@ -194,4 +194,57 @@ mod tests {
             list.entries
         );
     }
+
+    fn update_msg(field: &str, value: &str) -> ExecuteMsg {
+        let value_of = |name: &str| if name == field { Some(value.to_string()) } else { None };
+        ExecuteMsg::UpdateEntry {
+            id: 1,
+            data_type: value_of("data_type"),
+            particle: value_of("particle"),
+        }
+    }
+
+    #[test]
+    fn update_entry_fields() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), env.clone(), info.clone(), InstantiateMsg { owner: None }).unwrap();
+
+        let msg = ExecuteMsg::CreateEntry {
+            data_type: "testchain-1".to_string(),
+            particle: Some("QmYpTB36duejmy1szbdL1D2EzC5fgRL4dyhSFsHkMYPtny".to_string()),
+        };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        let mut expected = registry().entry(&deps.storage, 1).unwrap();
+
+        // nothing provided, nothing updated
+        execute(deps.as_mut(), env.clone(), info.clone(), update_msg("", "")).unwrap();
+        assert_eq!(registry().entry(&deps.storage, 1).unwrap(), expected);
+
+        // every field is updated alone and the other fields are kept, empty particle clears it
+        for (field, value) in [
+            ("data_type", "cosmos-sdk"),
+            ("particle", "QmRX8qYgeZoYM3M5zzQaWEpVFdpin6FvVXvp6RPQK3oufV"),
+            ("particle", ""),
+        ] {
+            execute(deps.as_mut(), env.clone(), info.clone(), update_msg(field, value)).unwrap();
+            match field {
+                "data_type" => expected.data_type = value.to_string(),
+                "particle" => expected.particle = value.to_string(),
+                _ => unreachable!(),
+            }
+            assert_eq!(registry().entry(&deps.storage, 1).unwrap(), expected);
+        }
+
+        // invalid values are rejected with the error of their field
+        for (field, value, err) in [
+            ("data_type", "Cosmos SDK", ContractError::InvalidBasicValue { field: "data_type".to_string() }),
+            ("particle", "particle", ContractError::InvalidCid { field: "particle".to_string() }),
+        ] {
+            let res = execute(deps.as_mut(), env.clone(), info.clone(), update_msg(field, value));
+            assert_eq!(res.unwrap_err(), err);
+            assert_eq!(registry().entry(&deps.storage, 1).unwrap(), expected);
+        }
+    }
 }
@@ -2,12 +2,12 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use cosmwasm_std::Addr;
-use cw_storage_plus::{Item, UniqueIndex, IndexList, IndexedMap, Index, MultiIndex};
+use cw_storage_plus::{UniqueIndex, IndexList, IndexedMap, Index, MultiIndex};
+use hub_base::validating::{validate_by_basic_rule, validate_particle, validate_url};
+use hub_base::state::ENTRIES_NAMESPACE;
+use hub_base::{ContractError, HubEntry, Registry};
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct Config {
-    pub owner: Option<Addr>,
-}
+pub use hub_base::state::{Config, CONFIG, ENTRY_SEQ};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Entry {
@@ -20,14 +20,26 @@ pub struct Entry {
     pub particle: String,
 }
 
-pub const CONFIG: Item<Config> = Item::new("config");
-pub const ENTRY_SEQ: Item<u64> = Item::new("entry_seq");
+impl HubEntry for Entry {
+    fn validate(&self) -> Result<(), ContractError> {
+        validate_particle(&self.particle)?;
+        validate_by_basic_rule(&self.neuron, "neuron")?;
+        validate_by_basic_rule(&self.network, "network")?;
+        validate_by_basic_rule(&self.protocol, "protocol")?;
+        validate_url(&self.endpoint, "endpoint")
+    }
+
+    // skills are managed by the neurons who registered them
+    fn owner(&self) -> Option<&Addr> {
+        Some(&self.owner)
+    }
+}
 
 pub struct EntryIndexes<'a> {
     pub id: UniqueIndex<'a, u64, Entry>,
-    pub owner: MultiIndex<'a, String, Entry, String>,
-    pub network: MultiIndex<'a, String, Entry, String>,
-    pub protocol: MultiIndex<'a, String, Entry, String>,
+    pub owner: MultiIndex<'a, String, Entry, u64>,
+    pub network: MultiIndex<'a, String, Entry, u64>,
+    pub protocol: MultiIndex<'a, String, Entry, u64>,
 }
 
 impl<'a> IndexList<Entry> for EntryIndexes<'a> {
@@ -37,21 +49,21 @@ impl<'a> IndexList<Entry> for EntryIndexes<'a> {
     }
 }
 
-pub fn entries<'a>() -> IndexedMap<'a, u64, Entry, EntryIndexes<'a>> {
+pub fn registry<'a>() -> Registry<'a, Entry, EntryIndexes<'a>> {
     let indexes = EntryIndexes {
         owner: MultiIndex::new(
             |d| (d.owner.clone().to_string()),
-            "list",
+            ENTRIES_NAMESPACE,
             "list_owner"
         ),
         network: MultiIndex::new(
             |d| (d.network.clone().to_string()),
-            "list",
+            ENTRIES_NAMESPACE,
             "list_network"
         ),
         protocol: MultiIndex::new(
             |d| (d.protocol.clone().to_string()),
-            "list",
+            ENTRIES_NAMESPACE,
             "list_protocol"
         ),
         id: UniqueIndex::new(
@@ -60,5 +72,5 @@ pub fn entries<'a>() -> IndexedMap<'a, u64, Entry, EntryIndexes<'a>> {
         ),
 
     };
-    IndexedMap::new("list", indexes)
+    Registry::new(IndexedMap::new(ENTRIES_NAMESPACE, indexes))
 }
\ No newline at end of file
@@ -8,7 +8,8 @@ mod tests {
     use cosmwasm_std::{attr, from_binary, Addr};
     // use std::convert::TryFrom;
     use std::vec::Vec;
-    use crate::state::{Entry, CONFIG, Config};
+    use crate::state::{Entry, CONFIG, Config, registry};
+    use crate::ContractError;
     use crate::msg::{ListResponse, QueryMsg, InstantiateMsg, ExecuteMsg, EntryResponse};
     // use crate::msg::{ ExecuteMsg, InstantiateMsg, QueryMsg};
     use crate::contract::{query, execute, instantiate};
@@ -282,4 +283,101 @@ mod tests {
             list.entries.len()
         );
     }
+
+    #[test]
+    fn query_entries_by_network() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), env.clone(), info.clone(), InstantiateMsg { owner: None }).unwrap();
+        for network in ["cosmos", "ethereum", "cosmos"] {
+            let msg = ExecuteMsg::CreateEntry {
+                neuron: "testchain-1".to_string(),
+                network: network.to_string(),
+                protocol: "cosmos-1".to_string(),
+                endpoint: "https:/abcd.com".to_string(),
+                particle: None,
+            };
+            execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        }
+
+        let query_ids = |network: &str, start_after: Option<u64>, limit: Option<u32>| -> Vec<u64> {
+            let msg = QueryMsg::GetEntriesNetwork { start_after, limit, network: network.to_string() };
+            let list: ListResponse = from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+            list.entries.iter().map(|entry| entry.id).collect()
+        };
+        assert_eq!(query_ids("cosmos", None, None), vec![1, 3]);
+        assert_eq!(query_ids("cosmos", None, Some(1)), vec![1]);
+        assert_eq!(query_ids("cosmos", Some(1), Some(1)), vec![3]);
+        assert_eq!(query_ids("ethereum", None, None), vec![2]);
+        assert_eq!(query_ids("cosmos", Some(3), None), Vec::<u64>::new());
+    }
+
+    fn update_msg(field: &str, value: &str) -> ExecuteMsg {
+        let value_of = |name: &str| if name == field { Some(value.to_string()) } else { None };
+        ExecuteMsg::UpdateEntry {
+            id: 1,
+            neuron: value_of("neuron"),
+            network: value_of("network"),
+            protocol: value_of("protocol"),
+            endpoint: value_of("endpoint"),
+            particle: value_of("particle"),
+        }
+    }
+
+    #[test]
+    fn update_entry_fields() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), env.clone(), info.clone(), InstantiateMsg { owner: None }).unwrap();
+
+        let msg = ExecuteMsg::CreateEntry {
+            neuron: "testchain-1".to_string(),
+            network: "cosmos".to_string(),
+            protocol: "cosmos-1".to_string(),
+            endpoint: "https:/abcd.com".to_string(),
+            particle: Some("QmYpTB36duejmy1szbdL1D2EzC5fgRL4dyhSFsHkMYPtny".to_string()),
+        };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        let mut expected = registry().entry(&deps.storage, 1).unwrap();
+
+        // nothing provided, nothing updated
+        execute(deps.as_mut(), env.clone(), info.clone(), update_msg("", "")).unwrap();
+        assert_eq!(registry().entry(&deps.storage, 1).unwrap(), expected);
+
+        // every field is updated alone and the other fields are kept, empty particle clears it
+        for (field, value) in [
+            ("neuron", "bostrom"),
+            ("network", "bostrom"),
+            ("protocol", "ibc"),
+            ("endpoint", "https://rpc.bostrom.cybernode.ai"),
+            ("particle", "QmRX8qYgeZoYM3M5zzQaWEpVFdpin6FvVXvp6RPQK3oufV"),
+            ("particle", ""),
+        ] {
+            execute(deps.as_mut(), env.clone(), info.clone(), update_msg(field, value)).unwrap();
+            match field {
+                "neuron" => expected.neuron = value.to_string(),
+                "network" => expected.network = value.to_string(),
+                "protocol" => expected.protocol = value.to_string(),
+                "endpoint" => expected.endpoint = value.to_string(),
+                "particle" => expected.particle = value.to_string(),
+                _ => unreachable!(),
+            }
+            assert_eq!(registry().entry(&deps.storage, 1).unwrap(), expected);
+        }
+
+        // invalid values are rejected with the error of their field
+        for (field, value, err) in [
+            ("neuron", "Neuron", ContractError::InvalidBasicValue { field: "neuron".to_string() }),
+            ("network", "bostrom 1", ContractError::InvalidBasicValue { field: "network".to_string() }),
+            ("protocol", "IBC", ContractError::InvalidBasicValue { field: "protocol".to_string() }),
+            ("endpoint", "https://rpc.bostrom.ai/?q=<q>", ContractError::InvalidUrl { field: "endpoint".to_string() }),
+            ("particle", "particle", ContractError::InvalidCid { field: "particle".to_string() }),
+        ] {
+            let res = execute(deps.as_mut(), env.clone(), info.clone(), update_msg(field, value));
+            assert_eq!(res.unwrap_err(), err);
+            assert_eq!(registry().entry(&deps.storage, 1).unwrap(), expected);
+        }
+    }
 }
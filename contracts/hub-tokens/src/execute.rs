@@ -1,36 +1,9 @@
-use std::ops::Add;
+use cosmwasm_std::Uint64;
+use cosmwasm_std::{DepsMut, MessageInfo, Response};
 
-use cosmwasm_std::{attr, Env, Uint64};
-use cosmwasm_std::{DepsMut, MessageInfo, Response, StdResult};
-
-use crate::error::ContractError;
-use crate::state::{CONFIG, Entry, ENTRY_SEQ, LIST};
-use crate::validating::{validate_by_basic_rule, validate_by_basic_uppercase_rule, validate_ipfs_cid};
-
-pub fn execute_update_owner(
-    deps: DepsMut,
-    _env: Env,
-    info: MessageInfo,
-    new_owner: Option<String>,
-) -> Result<Response, ContractError> {
-    let cfg = CONFIG.load(deps.storage)?;
-    let owner = cfg.owner.ok_or(ContractError::Unauthorized {})?;
-    if info.sender != owner {
-        return Err(ContractError::Unauthorized {});
-    }
-
-    let mut tmp_owner = None;
-    if let Some(addr) = new_owner {
-        tmp_owner = Some(deps.api.addr_validate(&addr)?)
-    }
-
-    CONFIG.update(deps.storage, |mut exists| -> StdResult<_> {
-        exists.owner = tmp_owner;
-        Ok(exists)
-    })?;
-
-    Ok(Response::new().add_attributes(vec![attr("action", "update_owner")]))
-}
+use hub_base::validating::{provided_particle, update_field, validate_by_basic_rule, validate_by_basic_uppercase_rule, validate_ipfs_cid, validate_particle};
+use hub_base::ContractError;
+use crate::state::{Entry, registry};
 
 pub fn execute_create_entry(
     deps: DepsMut,
@@ -43,42 +16,8 @@ pub fn execute_create_entry(
     logo: String,
     particle: Option<String>,
 ) -> Result<Response, ContractError> {
-    let owner = CONFIG.load(deps.storage)?.owner;
-
-    if owner.is_none() {
-        return Err(ContractError::Unauthorized {});
-    } else if info.sender != owner.unwrap() {
-        return Err(ContractError::Unauthorized {});
-    }
-
-    let validate_logo = validate_ipfs_cid(logo.clone(),"logo".to_string());
-    if validate_logo.is_err() {
-        return validate_logo;
-    }
-
-    if !particle.as_ref().is_none()  {
-        let validate_particle = validate_ipfs_cid(particle.clone().unwrap(),"particle".to_string());
-        if validate_particle.is_err() {
-            return validate_particle;
-        }
-    }
-
-    let validate_ticker = validate_by_basic_uppercase_rule(ticker.clone(), "ticker".to_string());
-    let validate_chain_id = validate_by_basic_rule(chain_id.clone(), "chain_id".to_string());
-
-    if validate_ticker.is_err() {
-        return validate_ticker;
-    }
-    if validate_chain_id.is_err() {
-        return validate_chain_id;
-    }
-
-    // TODO add denom validation
-    // TODO add channel validation
-
-    let id = ENTRY_SEQ.update::<_, cosmwasm_std::StdError>(deps.storage, |id| Ok(id.add(1)))?;
-
-    let new_entry = Entry {
+    let particle = provided_particle(particle)?;
+    registry().create(deps, &info.sender, |id| Entry {
         id,
         ticker,
         chain_id,
@@ -86,14 +25,8 @@ pub fn execute_create_entry(
         decimals,
         channel,
         logo,
-        particle: particle.unwrap_or("".to_string())
-    };
-
-    LIST.save(deps.storage, id, &new_entry)?;
-
-    Ok(Response::new()
-        .add_attribute("method", "execute_create_entry")
-        .add_attribute("new_entry_id", id.to_string()))
+        particle,
+    })
 }
 
 pub fn execute_update_entry(
@@ -108,55 +41,16 @@ pub fn execute_update_entry(
     logo: Option<String>,
     particle: Option<String>,
 ) -> Result<Response, ContractError> {
-    let owner = CONFIG.load(deps.storage)?.owner;
-
-    if owner.is_none() {
-        return Err(ContractError::Unauthorized {});
-    } else if info.sender != owner.unwrap() {
-        return Err(ContractError::Unauthorized {});
-    }
-
-    let validate_logo = validate_ipfs_cid(logo.clone().unwrap(),"logo".to_string());
-    if validate_logo.is_err() {
-        return validate_logo;
-    }
-
-    if !particle.as_ref().is_none()  {
-        let validate_particle = validate_ipfs_cid(particle.clone().unwrap(),"particle".to_string());
-        if validate_particle.is_err() {
-            return validate_particle;
-        }
-    }
-
-    let validate_ticker = validate_by_basic_uppercase_rule(ticker.clone().unwrap(), "ticker".to_string());
-    let validate_chain_id = validate_by_basic_rule(chain_id.clone().unwrap(), "chain_id".to_string());
-
-    // TODO add denom validation
-    // TODO add channel validation
-
-    if validate_ticker.is_err() {
-        return validate_ticker;
-    }
-    if validate_chain_id.is_err() {
-        return validate_chain_id;
-    }
-
-    let entry = LIST.load(deps.storage, id)?;
-    let updated_entry = Entry {
-        id,
-        ticker: ticker.unwrap_or(entry.ticker),
-        contract: contract.unwrap_or(entry.contract),
-        decimals: decimals.unwrap_or(entry.decimals),
-        chain_id: chain_id.unwrap_or(entry.chain_id),
-        channel: channel.unwrap_or(entry.channel),
-        logo: logo.unwrap_or(entry.logo),
-        particle: particle.unwrap_or("".to_string()),
-    };
-    LIST.save(deps.storage, id, &updated_entry)?;
-
-    Ok(Response::new()
-        .add_attribute("method", "execute_update_entry")
-        .add_attribute("updated_entry_id", id.to_string()))
+    registry().update(deps, &info.sender, id, |mut entry| {
+        update_field(&mut entry.ticker, ticker, |v| validate_by_basic_uppercase_rule(v, "ticker"))?;
+        update_field(&mut entry.chain_id, chain_id, |v| validate_by_basic_rule(v, "chain_id"))?;
+        entry.contract = contract.unwrap_or(entry.contract);
+        entry.decimals = decimals.unwrap_or(entry.decimals);
+        entry.channel = channel.unwrap_or(entry.channel);
+        update_field(&mut entry.logo, logo, |v| validate_ipfs_cid(v, "logo"))?;
+        update_field(&mut entry.particle, particle, |v| validate_particle(v))?;
+        Ok(entry)
+    })
 }
 
 pub fn execute_delete_entry(
@@ -164,17 +58,5 @@ pub fn execute_delete_entry(
     info: MessageInfo,
     id: u64,
 ) -> Result<Response, ContractError> {
-    let owner = CONFIG.load(deps.storage)?.owner;
-
-    if owner.is_none() {
-        return Err(ContractError::Unauthorized {});
-    } else if info.sender != owner.unwrap() {
-        return Err(ContractError::Unauthorized {});
-    }
-
-    LIST.remove(deps.storage, id);
-
-    Ok(Response::new()
-        .add_attribute("method", "execute_delete_entry")
-        .add_attribute("deleted_entry_id", id.to_string()))
+    registry().delete(deps, &info.sender, id)
 }
@@ -44,6 +44,16 @@ pub enum QueryMsg {
         start_after: Option<u64>,
         limit: Option<u32>,
     },
+    GetEntriesChainId {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        chain_id: String,
+    },
+    GetEntriesTicker {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+        ticker: String,
+    },
     GetEntry { id: u64 }
 }
 
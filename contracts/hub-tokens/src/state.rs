@@ -2,13 +2,12 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use cosmwasm_std::{Uint64};
 
-use cosmwasm_std::Addr;
-use cw_storage_plus::{Item, Map};
+use cw_storage_plus::{Index, IndexList, IndexedMap, MultiIndex};
+use hub_base::validating::{validate_by_basic_rule, validate_by_basic_uppercase_rule, validate_ipfs_cid, validate_particle};
+use hub_base::state::ENTRIES_NAMESPACE;
+use hub_base::{ContractError, HubEntry, Registry};
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct Config {
-    pub owner: Option<Addr>,
-}
+pub use hub_base::state::{Config, CONFIG, ENTRY_SEQ};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Entry {
@@ -22,6 +21,42 @@ pub struct Entry {
     pub particle: String,
 }
 
-pub const CONFIG: Item<Config> = Item::new("config");
-pub const ENTRY_SEQ: Item<u64> = Item::new("entry_seq");
-pub const LIST: Map<u64, Entry> = Map::new("list");
+impl HubEntry for Entry {
+    fn validate(&self) -> Result<(), ContractError> {
+        validate_ipfs_cid(&self.logo, "logo")?;
+        validate_particle(&self.particle)?;
+        validate_by_basic_uppercase_rule(&self.ticker, "ticker")?;
+        validate_by_basic_rule(&self.chain_id, "chain_id")?;
+        // TODO add denom validation
+        // TODO add channel validation
+        Ok(())
+    }
+}
+
+pub struct EntryIndexes<'a> {
+    pub chain_id: MultiIndex<'a, String, Entry, u64>,
+    pub ticker: MultiIndex<'a, String, Entry, u64>,
+}
+
+impl<'a> IndexList<Entry> for EntryIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Entry>> + '_> {
+        let v: Vec<&dyn Index<Entry>> = vec![&self.chain_id, &self.ticker];
+        Box::new(v.into_iter())
+    }
+}
+
+pub fn registry<'a>() -> Registry<'a, Entry, EntryIndexes<'a>> {
+    let indexes = EntryIndexes {
+        chain_id: MultiIndex::new(
+            |d| d.chain_id.clone(),
+            ENTRIES_NAMESPACE,
+            "list_chain_id"
+        ),
+        ticker: MultiIndex::new(
+            |d| d.ticker.clone(),
+            ENTRIES_NAMESPACE,
+            "list_ticker"
+        ),
+    };
+    Registry::new(IndexedMap::new(ENTRIES_NAMESPACE, indexes))
+}
@@ -6,9 +6,12 @@ mod tests {
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
     use cosmwasm_std::{attr, from_binary, Addr};
     use std::vec::Vec;
-    use crate::state::{Entry, CONFIG, Config};
-    use crate::msg::{ ListResponse, QueryMsg, InstantiateMsg, ExecuteMsg};
-    use crate::contract::{query, execute, instantiate};
+    use crate::state::{Entry, CONFIG, Config, registry};
+    use crate::ContractError;
+    use crate::msg::{ ListResponse, QueryMsg, InstantiateMsg, ExecuteMsg, MigrateMsg};
+    use crate::contract::{query, execute, instantiate, migrate};
+    use cw_storage_plus::Map;
+    use hub_base::state::ENTRIES_NAMESPACE;
 
     #[test]
     fn proper_initialization() {
@@ -123,7 +126,7 @@ mod tests {
                     ticker: "TST2".to_string(),
                     chain_id: "testchain-1".to_string(),
                     contract: "cyb".to_string(),
-                    decimals: Uint64::new(8),
+                    decimals: Uint64::new(6),
                     channel: Uint64::new(1),
                     logo: "QmYpTB36duejmy1szbdL1D2EzC5fgRL4dyhSFsHkMYPtny".to_string(),
                     particle: "QmYpTB36duejmy1szbdL1D2EzC5fgRL4dyhSFsHkMYPtny".to_string(),
@@ -173,7 +176,7 @@ mod tests {
                     ticker: "TSTUPDATE".to_string(),
                     chain_id: "testchain-1".to_string(),
                     contract: "cyb".to_string(),
-                    decimals: Uint64::new(6),
+                    decimals: Uint64::new(7),
                     channel: Uint64::new(1),
                     logo: "QmYpTB36duejmy1szbdL1D2EzC5fgRL4dyhSFsHkMYPtny".to_string(),
                     particle: "QmYpTB36duejmy1szbdL1D2EzC5fgRL4dyhSFsHkMYPtny".to_string(),
@@ -228,4 +231,139 @@ mod tests {
             list.entries
         );
     }
+
+    #[test]
+    fn query_entries_by_chain_id() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), env.clone(), info.clone(), InstantiateMsg { owner: None }).unwrap();
+        for chain_id in ["bostrom", "osmosis-1", "bostrom"] {
+            let msg = ExecuteMsg::CreateEntry {
+                ticker: "TST".to_string(),
+                chain_id: chain_id.to_string(),
+                contract: "cyb".to_string(),
+                decimals: Uint64::new(6),
+                channel: Uint64::new(1),
+                logo: "QmYpTB36duejmy1szbdL1D2EzC5fgRL4dyhSFsHkMYPtny".to_string(),
+                particle: None,
+            };
+            execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        }
+
+        let query_ids = |chain_id: &str, start_after: Option<u64>, limit: Option<u32>| -> Vec<u64> {
+            let msg = QueryMsg::GetEntriesChainId { start_after, limit, chain_id: chain_id.to_string() };
+            let list: ListResponse = from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+            list.entries.iter().map(|entry| entry.id).collect()
+        };
+        assert_eq!(query_ids("bostrom", None, None), vec![1, 3]);
+        assert_eq!(query_ids("bostrom", None, Some(1)), vec![1]);
+        assert_eq!(query_ids("bostrom", Some(1), Some(1)), vec![3]);
+        assert_eq!(query_ids("osmosis-1", None, None), vec![2]);
+        assert_eq!(query_ids("bostrom", Some(3), None), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn migrate_indexes_list_entries() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        instantiate(deps.as_mut(), env.clone(), mock_info("creator", &[]), InstantiateMsg { owner: None }).unwrap();
+
+        // entries saved by the former LIST map have no index entries
+        let entry = Entry {
+            id: 1,
+            ticker: "TST".to_string(),
+            chain_id: "bostrom".to_string(),
+            contract: "cyb".to_string(),
+            decimals: Uint64::new(6),
+            channel: Uint64::new(1),
+            logo: "QmYpTB36duejmy1szbdL1D2EzC5fgRL4dyhSFsHkMYPtny".to_string(),
+            particle: "".to_string(),
+        };
+        let list: Map<u64, Entry> = Map::new(ENTRIES_NAMESPACE);
+        list.save(deps.as_mut().storage, 1, &entry).unwrap();
+        let by_ticker = QueryMsg::GetEntriesTicker { start_after: None, limit: None, ticker: "TST".to_string() };
+        let res: ListResponse = from_binary(&query(deps.as_ref(), env.clone(), by_ticker.clone()).unwrap()).unwrap();
+        assert!(res.entries.is_empty());
+
+        migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+        let res: ListResponse = from_binary(&query(deps.as_ref(), env, by_ticker).unwrap()).unwrap();
+        assert_eq!(res.entries, vec![entry]);
+    }
+
+    fn update_msg(field: &str, value: &str) -> ExecuteMsg {
+        let value_of = |name: &str| if name == field { Some(value.to_string()) } else { None };
+        let number_of = |name: &str| if name == field { Some(Uint64::new(value.parse().unwrap())) } else { None };
+        ExecuteMsg::UpdateEntry {
+            id: 1,
+            ticker: value_of("ticker"),
+            chain_id: value_of("chain_id"),
+            contract: value_of("contract"),
+            decimals: number_of("decimals"),
+            channel: number_of("channel"),
+            logo: value_of("logo"),
+            particle: value_of("particle"),
+        }
+    }
+
+    #[test]
+    fn update_entry_fields() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), env.clone(), info.clone(), InstantiateMsg { owner: None }).unwrap();
+
+        let msg = ExecuteMsg::CreateEntry {
+            ticker: "TST".to_string(),
+            chain_id: "testchain-1".to_string(),
+            contract: "cyb".to_string(),
+            decimals: Uint64::new(6),
+            channel: Uint64::new(1),
+            logo: "QmYpTB36duejmy1szbdL1D2EzC5fgRL4dyhSFsHkMYPtny".to_string(),
+            particle: Some("QmYpTB36duejmy1szbdL1D2EzC5fgRL4dyhSFsHkMYPtny".to_string()),
+        };
+        execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
+        let mut expected = registry().entry(&deps.storage, 1).unwrap();
+
+        // nothing provided, nothing updated
+        execute(deps.as_mut(), env.clone(), info.clone(), update_msg("", "")).unwrap();
+        assert_eq!(registry().entry(&deps.storage, 1).unwrap(), expected);
+
+        // every field is updated alone and the other fields are kept, empty particle clears it
+        for (field, value) in [
+            ("ticker", "TSTUPDATE"),
+            ("chain_id", "testchain-2"),
+            ("contract", "boot"),
+            ("decimals", "18"),
+            ("channel", "42"),
+            ("logo", "QmRX8qYgeZoYM3M5zzQaWEpVFdpin6FvVXvp6RPQK3oufV"),
+            ("particle", "QmRX8qYgeZoYM3M5zzQaWEpVFdpin6FvVXvp6RPQK3oufV"),
+            ("particle", ""),
+        ] {
+            execute(deps.as_mut(), env.clone(), info.clone(), update_msg(field, value)).unwrap();
+            match field {
+                "ticker" => expected.ticker = value.to_string(),
+                "chain_id" => expected.chain_id = value.to_string(),
+                "contract" => expected.contract = value.to_string(),
+                "decimals" => expected.decimals = Uint64::new(value.parse().unwrap()),
+                "channel" => expected.channel = Uint64::new(value.parse().unwrap()),
+                "logo" => expected.logo = value.to_string(),
+                "particle" => expected.particle = value.to_string(),
+                _ => unreachable!(),
+            }
+            assert_eq!(registry().entry(&deps.storage, 1).unwrap(), expected);
+        }
+
+        // invalid values are rejected with the error of their field
+        for (field, value, err) in [
+            ("ticker", "tst", ContractError::InvalidUppercaseValue { field: "ticker".to_string() }),
+            ("chain_id", "Testchain_1", ContractError::InvalidBasicValue { field: "chain_id".to_string() }),
+            ("logo", "logo", ContractError::InvalidCid { field: "logo".to_string() }),
+            ("particle", "particle", ContractError::InvalidCid { field: "particle".to_string() }),
+        ] {
+            let res = execute(deps.as_mut(), env.clone(), info.clone(), update_msg(field, value));
+            assert_eq!(res.unwrap_err(), err);
+            assert_eq!(registry().entry(&deps.storage, 1).unwrap(), expected);
+        }
+    }
 }
@@ -0,0 +1,239 @@
+use std::cmp::{max, min};
+
+use anyhow::Result as AnyResult;
+use proptest::collection::vec;
+use proptest::prelude::*;
+use proptest::test_runner::TestCaseError;
+
+use crate::multitest::CyberApp;
+
+pub const MAX_BOND: u128 = 1_000_000_000_000;
+pub const MAX_REWARD: u128 = 1_000_000_000_000;
+pub const MAX_PERIOD_DELAY: u64 = 200;
+pub const MAX_PERIOD_LENGTH: u64 = 1_000;
+pub const MAX_ADVANCE_BLOCKS: u64 = 300;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FarmAction {
+    Bond { staker: usize, amount: u128 },
+    /// Unbond a percent of the staker's bond, above 100 the unbond has to fail
+    Unbond { staker: usize, percent: u8 },
+    Withdraw { staker: usize },
+    /// Add the period (clock + delay, clock + delay + length, amount),
+    /// the period overlapping the schedule has to fail
+    AddDistributionPeriod { delay: u64, length: u64, amount: u128 },
+    AdvanceBlocks { blocks: u64 },
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StakerPosition {
+    pub bond_amount: u128,
+    pub pending_reward: u128,
+}
+
+/// FarmHarness drives one farm deployed in CyberApp. Stakers are referenced by index,
+/// the harness is responsible to fund stakers and the distribution account.
+pub trait FarmHarness {
+    fn stakers(&self) -> usize;
+    fn app(&mut self) -> &mut CyberApp;
+    /// Current value of the clock of the farm distribution schedule, block height by default
+    fn clock(&mut self) -> u64 {
+        self.app().block_info().height
+    }
+    fn bond(&mut self, staker: usize, amount: u128) -> AnyResult<()>;
+    fn unbond(&mut self, staker: usize, amount: u128) -> AnyResult<()>;
+    /// Withdraws pending rewards and returns the amount received by the staker
+    fn withdraw(&mut self, staker: usize) -> AnyResult<u128>;
+    /// Adds the period funded with its reward
+    fn add_distribution_period(&mut self, period: (u64, u64, u128)) -> AnyResult<()>;
+    /// Staker position with rewards computed up to the current clock
+    fn staker_position(&mut self, staker: usize) -> AnyResult<StakerPosition>;
+}
+
+pub fn distribution_period() -> impl Strategy<Value = FarmAction> {
+    (0..MAX_PERIOD_DELAY, 1..MAX_PERIOD_LENGTH, 1..MAX_REWARD)
+        .prop_map(|(delay, length, amount)| FarmAction::AddDistributionPeriod { delay, length, amount })
+}
+
+pub fn farm_action(stakers: usize) -> impl Strategy<Value = FarmAction> {
+    prop_oneof![
+        3 => (0..stakers, 1..MAX_BOND).prop_map(|(staker, amount)| FarmAction::Bond { staker, amount }),
+        2 => (0..stakers, 1..120u8).prop_map(|(staker, percent)| FarmAction::Unbond { staker, percent }),
+        2 => (0..stakers).prop_map(|staker| FarmAction::Withdraw { staker }),
+        1 => distribution_period(),
+        3 => (1..MAX_ADVANCE_BLOCKS).prop_map(|blocks| FarmAction::AdvanceBlocks { blocks }),
+    ]
+}
+
+/// Random scenario which always starts with a distribution period
+pub fn farm_scenario(stakers: usize, max_actions: usize) -> impl Strategy<Value = Vec<FarmAction>> {
+    (distribution_period(), vec(farm_action(stakers), 1..max_actions)).prop_map(|(period, mut actions)| {
+        actions.insert(0, period);
+        actions
+    })
+}
+
+/// Runs the actions against the harness and checks after each of them, up to rounding, that:
+/// - rewards of stakers (withdrawn and pending) never exceed the schedule up to the current block,
+/// - their sum equals the distributed amount,
+/// - no staker gets more than accrued for its bond.
+pub fn run_farm_scenario<H: FarmHarness>(harness: &mut H, actions: &[FarmAction]) -> Result<(), TestCaseError> {
+    let clock = harness.clock();
+    let mut model = FarmModel::new(harness.stakers(), clock);
+
+    for action in actions {
+        let clock = harness.clock();
+        match *action {
+            FarmAction::Bond { staker, amount } => {
+                harness.bond(staker, amount).map_err(fail)?;
+                model.distribute(clock);
+                model.bonds[staker] += amount;
+            }
+            FarmAction::Unbond { staker, percent } => {
+                let bond = model.bonds[staker];
+                let amount = bond * percent as u128 / 100;
+                if amount == 0 {
+                    continue;
+                }
+                let res = harness.unbond(staker, amount);
+                if amount > bond {
+                    prop_assert!(res.is_err(), "unbond of {} over bond {} succeeded", amount, bond);
+                } else {
+                    res.map_err(fail)?;
+                    model.distribute(clock);
+                    model.bonds[staker] -= amount;
+                }
+            }
+            FarmAction::Withdraw { staker } => {
+                let pending = harness.staker_position(staker).map_err(fail)?.pending_reward;
+                match harness.withdraw(staker) {
+                    Ok(paid) => {
+                        prop_assert_eq!(paid, pending);
+                        model.distribute(clock);
+                        model.withdrawn[staker] += paid;
+                    }
+                    Err(err) => prop_assert!(pending == 0, "withdraw of {} failed: {}", pending, err),
+                }
+            }
+            FarmAction::AddDistributionPeriod { delay, length, amount } => {
+                let period = (clock + delay, clock + delay + length, amount);
+                let res = harness.add_distribution_period(period);
+                if model.overlaps(period) {
+                    prop_assert!(res.is_err(), "period {:?} overlapping the schedule added", period);
+                } else {
+                    res.map_err(fail)?;
+                    model.schedule.push(period);
+                }
+            }
+            FarmAction::AdvanceBlocks { blocks } => harness.app().advance_blocks(blocks),
+        }
+        model.check(harness)?;
+    }
+
+    Ok(())
+}
+
+fn fail(err: anyhow::Error) -> TestCaseError {
+    TestCaseError::fail(err.to_string())
+}
+
+/// Reference model of the reward distribution with exact integer math
+#[derive(Clone, Debug)]
+struct FarmModel {
+    schedule: Vec<(u64, u64, u128)>,
+    last_distributed: u64,
+    bonds: Vec<u128>,
+    accrued: Vec<u128>,
+    withdrawn: Vec<u128>,
+    distributed: u128,
+    steps: u128,
+}
+
+impl FarmModel {
+    fn new(stakers: usize, clock: u64) -> Self {
+        FarmModel {
+            schedule: vec![],
+            last_distributed: clock,
+            bonds: vec![0; stakers],
+            accrued: vec![0; stakers],
+            withdrawn: vec![0; stakers],
+            distributed: 0,
+            steps: 0,
+        }
+    }
+
+    // same slots as compute_reward in the farms
+    fn distribute(&mut self, clock: u64) {
+        let total_bond: u128 = self.bonds.iter().sum();
+        if total_bond == 0 {
+            self.last_distributed = clock;
+            return;
+        }
+
+        let mut amount = 0u128;
+        for &(start, end, reward) in self.schedule.iter() {
+            if start > clock || end < self.last_distributed {
+                continue;
+            }
+            let passed = min(end, clock) - max(start, self.last_distributed);
+            amount += reward * passed as u128 / (end - start) as u128;
+        }
+
+        for (accrued, bond) in self.accrued.iter_mut().zip(self.bonds.iter()) {
+            *accrued += amount * bond / total_bond;
+        }
+        self.distributed += amount;
+        self.last_distributed = clock;
+        self.steps += 1;
+    }
+
+    fn overlaps(&self, period: (u64, u64, u128)) -> bool {
+        self.schedule.iter().any(|&(start, end, _)| start < period.1 && period.0 < end)
+    }
+
+    fn scheduled(&self, clock: u64) -> u128 {
+        self.schedule
+            .iter()
+            .map(|&(start, end, reward)| {
+                let passed = min(end, max(start, clock)) - start;
+                reward * passed as u128 / (end - start) as u128
+            })
+            .sum()
+    }
+
+    fn check<H: FarmHarness>(&self, harness: &mut H) -> Result<(), TestCaseError> {
+        let clock = harness.clock();
+        let mut now = self.clone();
+        now.distribute(clock);
+
+        // every distribution step rounds once per period and once per staker,
+        // the rounding of a staker reward index may go up a unit as well
+        let staker_tolerance = now.steps;
+        let tolerance = now.steps * (now.schedule.len() + now.bonds.len() + 1) as u128;
+
+        let mut total_rewards = 0u128;
+        for staker in 0..now.bonds.len() {
+            let position = harness.staker_position(staker).map_err(fail)?;
+            prop_assert_eq!(position.bond_amount, now.bonds[staker]);
+
+            let rewards = now.withdrawn[staker] + position.pending_reward;
+            prop_assert!(
+                rewards <= now.accrued[staker] + staker_tolerance,
+                "staker {} got {} of accrued {}", staker, rewards, now.accrued[staker]
+            );
+            total_rewards += rewards;
+        }
+
+        let scheduled = now.scheduled(clock);
+        prop_assert!(
+            total_rewards <= scheduled + now.bonds.len() as u128 * staker_tolerance,
+            "rewards {} exceed schedule {}", total_rewards, scheduled
+        );
+        prop_assert!(
+            max(total_rewards, now.distributed) - min(total_rewards, now.distributed) <= tolerance,
+            "rewards {} differ from distributed {}", total_rewards, now.distributed
+        );
+
+        Ok(())
+    }
+}
@@ -1,3 +1,12 @@
+#[cfg(feature = "farm-scenarios")]
+mod farm;
+mod metering;
 mod multitest;
 
-pub use multitest::{CyberApp, CyberAppWrapped, CyberModule};
\ No newline at end of file
+#[cfg(feature = "farm-scenarios")]
+pub use farm::{
+    distribution_period, farm_action, farm_scenario, run_farm_scenario, FarmAction, FarmHarness,
+    StakerPosition,
+};
+pub use metering::{ExecutionMetering, MeteredStorage, MeteringReport, StorageMeter, StorageUsage};
+pub use multitest::{CyberApp, CyberAppWrapped, CyberModule};
@@ -0,0 +1,187 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use cosmwasm_std::{Event, Order, Record, Storage};
+use cosmwasm_std::testing::MockStorage;
+
+// KVStore gas costs of the cosmos-sdk, used to approximate the gas of an execution
+pub const READ_COST_FLAT: u64 = 1000;
+pub const READ_COST_PER_BYTE: u64 = 3;
+pub const WRITE_COST_FLAT: u64 = 2000;
+pub const WRITE_COST_PER_BYTE: u64 = 30;
+pub const DELETE_COST: u64 = 1000;
+pub const ITER_NEXT_COST_FLAT: u64 = 30;
+// wasmd charges this for every contract instance loaded to handle a call
+pub const INSTANCE_COST: u64 = 60_000;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StorageUsage {
+    pub reads: u64,
+    pub bytes_read: u64,
+    pub writes: u64,
+    pub bytes_written: u64,
+    pub removes: u64,
+    pub iterated: u64,
+}
+
+impl StorageUsage {
+    pub fn gas(&self) -> u64 {
+        self.reads * READ_COST_FLAT
+            + self.bytes_read * READ_COST_PER_BYTE
+            + self.writes * WRITE_COST_FLAT
+            + self.bytes_written * WRITE_COST_PER_BYTE
+            + self.removes * DELETE_COST
+            + self.iterated * ITER_NEXT_COST_FLAT
+    }
+
+    pub fn add(&mut self, other: &StorageUsage) {
+        self.reads += other.reads;
+        self.bytes_read += other.bytes_read;
+        self.writes += other.writes;
+        self.bytes_written += other.bytes_written;
+        self.removes += other.removes;
+        self.iterated += other.iterated;
+    }
+}
+
+#[derive(Default)]
+pub struct StorageMeter {
+    enabled: bool,
+    usage: StorageUsage,
+}
+
+impl StorageMeter {
+    pub fn start(&mut self) {
+        self.enabled = true;
+        self.usage = StorageUsage::default();
+    }
+
+    pub fn stop(&mut self) -> StorageUsage {
+        self.enabled = false;
+        std::mem::take(&mut self.usage)
+    }
+
+    fn record<F: FnOnce(&mut StorageUsage)>(&mut self, action: F) {
+        if self.enabled {
+            action(&mut self.usage)
+        }
+    }
+}
+
+/// MeteredStorage is the app storage of CyberApp. Contracts work on top of transactional caches,
+/// so writes are counted once they are committed to it, reads whenever they miss the cache.
+pub struct MeteredStorage {
+    storage: MockStorage,
+    meter: Rc<RefCell<StorageMeter>>,
+}
+
+impl MeteredStorage {
+    pub fn new(meter: Rc<RefCell<StorageMeter>>) -> Self {
+        MeteredStorage {
+            storage: MockStorage::new(),
+            meter,
+        }
+    }
+}
+
+impl Storage for MeteredStorage {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        let value = self.storage.get(key);
+        self.meter.borrow_mut().record(|usage| {
+            usage.reads += 1;
+            usage.bytes_read += value.as_ref().map_or(0, |v| v.len() as u64);
+        });
+        value
+    }
+
+    fn range<'a>(
+        &'a self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = Record> + 'a> {
+        let meter = self.meter.clone();
+        Box::new(self.storage.range(start, end, order).inspect(move |(key, value)| {
+            meter.borrow_mut().record(|usage| {
+                usage.iterated += 1;
+                usage.bytes_read += (key.len() + value.len()) as u64;
+            });
+        }))
+    }
+
+    fn set(&mut self, key: &[u8], value: &[u8]) {
+        self.meter.borrow_mut().record(|usage| {
+            usage.writes += 1;
+            usage.bytes_written += (key.len() + value.len()) as u64;
+        });
+        self.storage.set(key, value)
+    }
+
+    fn remove(&mut self, key: &[u8]) {
+        self.meter.borrow_mut().record(|usage| usage.removes += 1);
+        self.storage.remove(key)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExecutionMetering {
+    pub label: String,
+    pub height: u64,
+    pub success: bool,
+    pub storage: StorageUsage,
+    /// execute, instantiate, migrate and transfer events of the execution, the top-level call included,
+    /// counted for the wasm and bank messages it dispatched
+    pub message_events: u64,
+    /// contract entry points (execute, instantiate, migrate, sudo, reply) called
+    pub contract_calls: u64,
+    /// approximation of the gas used, see the KVStore costs above
+    pub gas: u64,
+}
+
+impl ExecutionMetering {
+    pub fn new(label: String, height: u64, storage: StorageUsage, events: Option<&[Event]>) -> Self {
+        let (message_events, contract_calls) = events.map_or((0, 0), count_events);
+        ExecutionMetering {
+            label,
+            height,
+            success: events.is_some(),
+            storage,
+            message_events,
+            contract_calls,
+            gas: storage.gas() + contract_calls * INSTANCE_COST,
+        }
+    }
+}
+
+fn count_events(events: &[Event]) -> (u64, u64) {
+    let count = |types: &[&str]| events.iter().filter(|e| types.contains(&e.ty.as_str())).count() as u64;
+    (
+        count(&["execute", "instantiate", "migrate", "transfer"]),
+        count(&["execute", "instantiate", "migrate", "sudo", "reply"]),
+    )
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MeteringReport {
+    pub executions: Vec<ExecutionMetering>,
+}
+
+impl MeteringReport {
+    pub fn last(&self) -> Option<&ExecutionMetering> {
+        self.executions.last()
+    }
+
+    pub fn total_gas(&self) -> u64 {
+        self.executions.iter().map(|e| e.gas).sum()
+    }
+
+    pub fn total_storage(&self) -> StorageUsage {
+        let mut total = StorageUsage::default();
+        self.executions.iter().for_each(|e| total.add(&e.storage));
+        total
+    }
+
+    pub fn total_message_events(&self) -> u64 {
+        self.executions.iter().map(|e| e.message_events).sum()
+    }
+}
@@ -1,16 +1,21 @@
+use std::cell::RefCell;
 use std::cmp::max;
 use std::fmt::Debug;
 use std::ops::{Deref, DerefMut};
+use std::rc::Rc;
 
 use anyhow::{bail, Result as AnyResult};
-use cosmwasm_std::{Addr, Api, Binary, BlockInfo, CustomQuery, Empty, Querier, QuerierResult, Storage};
-use cosmwasm_std::testing::{MockApi, MockStorage};
-use cw_multi_test::{App, AppResponse, BankKeeper, BasicAppBuilder, CosmosRouter, Module, WasmKeeper};
+use cosmwasm_std::{Addr, Api, BankMsg, Binary, BlockInfo, CosmosMsg, CustomQuery, Empty, Order, Querier, QuerierResult, Record, Storage, WasmMsg};
+use cosmwasm_std::testing::MockApi;
+use cw_multi_test::{App, AppResponse, BankKeeper, BasicAppBuilder, CosmosRouter, Executor, Module, WasmKeeper};
 use schemars::JsonSchema;
 use serde::de::DeserializeOwned;
+use serde::Serialize;
 
 use cyber_std::CyberMsgWrapper;
 
+use crate::metering::{ExecutionMetering, MeteredStorage, MeteringReport, StorageMeter};
+
 pub struct CyberModule {}
 
 pub const BLOCK_TIME: u64 = 5;
@@ -64,27 +69,44 @@ impl Module for CyberModule {
 }
 
 pub type CyberAppWrapped =
-    App<BankKeeper, MockApi, MockStorage, CyberModule, WasmKeeper<CyberMsgWrapper, Empty>>;
+    App<BankKeeper, MockApi, MeteredStorage, CyberModule, WasmKeeper<CyberMsgWrapper, Empty>>;
 
-pub struct CyberApp(CyberAppWrapped);
+pub struct CyberApp {
+    app: CyberAppWrapped,
+    meter: Rc<RefCell<StorageMeter>>,
+    report: Option<MeteringReport>,
+    snapshots: Vec<Snapshot>,
+}
+
+struct Snapshot {
+    block: BlockInfo,
+    records: Vec<Record>,
+}
 
 impl Deref for CyberApp {
     type Target = CyberAppWrapped;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.app
     }
 }
 
 impl DerefMut for CyberApp {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+        &mut self.app
     }
 }
 
 impl Querier for CyberApp {
     fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
-        self.0.raw_query(bin_request)
+        self.app.raw_query(bin_request)
+    }
+}
+
+impl Executor<CyberMsgWrapper> for CyberApp {
+    fn execute(&mut self, sender: Addr, msg: CosmosMsg<CyberMsgWrapper>) -> AnyResult<AppResponse> {
+        let label = msg_label(&msg);
+        self.metered(label, |app| app.execute(sender, msg))
     }
 }
 
@@ -96,15 +118,68 @@ impl Default for CyberApp {
 
 impl CyberApp {
     pub fn new() -> Self {
-        Self(
-            BasicAppBuilder::<CyberMsgWrapper, Empty>::new_custom()
+        let meter = Rc::new(RefCell::new(StorageMeter::default()));
+        Self {
+            app: BasicAppBuilder::<CyberMsgWrapper, Empty>::new_custom()
                 .with_custom(CyberModule {})
+                .with_storage(MeteredStorage::new(meter.clone()))
                 .build(|_router, _, _storage| {}),
-        )
+            meter,
+            report: None,
+            snapshots: vec![],
+        }
     }
 
     pub fn block_info(&self) -> BlockInfo {
-        self.0.block_info()
+        self.app.block_info()
+    }
+
+    /// Starts collecting a metering report for every execution and sudo call made through the app.
+    pub fn enable_metering(&mut self) {
+        self.report.get_or_insert_with(MeteringReport::default);
+    }
+
+    /// Stops metering and returns the collected report.
+    pub fn disable_metering(&mut self) -> Option<MeteringReport> {
+        self.report.take()
+    }
+
+    pub fn metering_report(&self) -> Option<&MeteringReport> {
+        self.report.as_ref()
+    }
+
+    pub fn wasm_sudo<T: Serialize, U: Into<Addr>>(
+        &mut self,
+        contract_addr: U,
+        msg: &T,
+    ) -> AnyResult<AppResponse> {
+        let contract_addr = contract_addr.into();
+        let label = format!("wasm_sudo {}", contract_addr);
+        self.metered(label, |app| app.wasm_sudo(contract_addr, msg))
+    }
+
+    fn metered<F>(&mut self, label: String, action: F) -> AnyResult<AppResponse>
+    where
+        F: FnOnce(&mut CyberAppWrapped) -> AnyResult<AppResponse>,
+    {
+        if self.report.is_none() {
+            return action(&mut self.app);
+        }
+
+        self.meter.borrow_mut().start();
+        let res = action(&mut self.app);
+        let usage = self.meter.borrow_mut().stop();
+
+        let metering = ExecutionMetering::new(
+            label,
+            self.app.block_info().height,
+            usage,
+            res.as_ref().ok().map(|r| r.events.as_slice()),
+        );
+        if let Some(report) = self.report.as_mut() {
+            report.executions.push(metering);
+        }
+        res
     }
 
     pub fn advance_blocks(&mut self, blocks: u64) {
@@ -124,4 +199,176 @@ impl CyberApp {
     pub fn next_block(&mut self) {
         self.advance_blocks(1)
     }
-}
\ No newline at end of file
+
+    /// Saves the block and the whole app storage (bank, contracts and the custom module state
+    /// all live there) and returns the snapshot id to restore it later.
+    /// Codes stored after the snapshot stay registered on restore.
+    pub fn snapshot(&mut self) -> usize {
+        let records = self.app.read_module(|_router, _, storage| {
+            storage.range(None, None, Order::Ascending).collect()
+        });
+        self.snapshots.push(Snapshot {
+            block: self.app.block_info(),
+            records,
+        });
+        self.snapshots.len() - 1
+    }
+
+    /// Brings the app back to the snapshot, which can be restored again to fork other scenarios.
+    pub fn restore(&mut self, id: usize) -> AnyResult<()> {
+        let snapshot = match self.snapshots.get(id) {
+            Some(snapshot) => snapshot,
+            None => bail!("snapshot {} not found", id),
+        };
+
+        self.app.init_modules(|_router, _, storage| {
+            let keys: Vec<Vec<u8>> = storage
+                .range(None, None, Order::Ascending)
+                .map(|(key, _)| key)
+                .collect();
+            keys.iter().for_each(|key| storage.remove(key));
+            snapshot.records.iter().for_each(|(key, value)| storage.set(key, value));
+        });
+        self.app.set_block(snapshot.block.clone());
+
+        Ok(())
+    }
+}
+
+fn msg_label(msg: &CosmosMsg<CyberMsgWrapper>) -> String {
+    match msg {
+        CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) => format!("wasm_execute {}", contract_addr),
+        CosmosMsg::Wasm(WasmMsg::Instantiate { code_id, .. }) => format!("wasm_instantiate {}", code_id),
+        CosmosMsg::Wasm(WasmMsg::Migrate { contract_addr, .. }) => format!("wasm_migrate {}", contract_addr),
+        CosmosMsg::Bank(BankMsg::Send { to_address, .. }) => format!("bank_send {}", to_address),
+        CosmosMsg::Custom(msg) => format!("cyber {:?}", msg.route),
+        _ => "other".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::{coins, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult};
+    use cw_multi_test::{ContractWrapper, Executor};
+
+    use super::*;
+
+    fn instantiate(deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response<CyberMsgWrapper>> {
+        deps.storage.set(b"counter", &0u64.to_be_bytes());
+        Ok(Response::new())
+    }
+
+    // writes `count` entries, fails on zero
+    fn execute(deps: DepsMut, _env: Env, _info: MessageInfo, count: u64) -> StdResult<Response<CyberMsgWrapper>> {
+        if count == 0 {
+            return Err(StdError::generic_err("nothing to write"));
+        }
+        for i in 0..count {
+            deps.storage.set(&i.to_be_bytes(), &[1u8; 32]);
+        }
+        Ok(Response::new())
+    }
+
+    fn query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+        to_binary(&Empty {})
+    }
+
+    fn setup() -> (CyberApp, Addr) {
+        let mut app = CyberApp::new();
+        let code_id = app.store_code(Box::new(ContractWrapper::new(execute, instantiate, query)));
+        let contract = app
+            .instantiate_contract(code_id, Addr::unchecked("owner"), &Empty {}, &[], "writer", None)
+            .unwrap();
+        (app, contract)
+    }
+
+    #[test]
+    fn metering_is_optional() {
+        let (mut app, contract) = setup();
+        app.execute_contract(Addr::unchecked("owner"), contract, &3u64, &[]).unwrap();
+        assert!(app.metering_report().is_none());
+    }
+
+    #[test]
+    fn metering_reports_executions() {
+        let (mut app, contract) = setup();
+        app.enable_metering();
+
+        app.execute_contract(Addr::unchecked("owner"), contract.clone(), &1u64, &[]).unwrap();
+        app.execute_contract(Addr::unchecked("owner"), contract.clone(), &3u64, &[]).unwrap();
+        app.execute_contract(Addr::unchecked("owner"), contract.clone(), &0u64, &[]).unwrap_err();
+
+        let report = app.disable_metering().unwrap();
+        assert_eq!(report.executions.len(), 3);
+
+        let small = &report.executions[0];
+        let large = &report.executions[1];
+        assert_eq!(small.label, format!("wasm_execute {}", contract));
+        assert!(small.success);
+        assert_eq!(small.message_events, 1);
+        assert_eq!(small.contract_calls, 1);
+        assert_eq!(large.storage.writes - small.storage.writes, 2);
+        // keys are namespaced by the contract address
+        assert!(large.storage.bytes_written - small.storage.bytes_written > 2 * (8 + 32));
+        assert!(large.gas > small.gas);
+
+        // failed executions are reported, but nothing they wrote is committed
+        let failed = &report.executions[2];
+        assert!(!failed.success);
+        assert_eq!(failed.storage.writes, 0);
+        assert_eq!(failed.message_events, 0);
+
+        assert_eq!(report.total_gas(), small.gas + large.gas + failed.gas);
+        assert_eq!(report.total_message_events(), 2);
+        assert!(app.metering_report().is_none());
+    }
+
+    #[test]
+    fn metering_counts_bank_transfers() {
+        let mut app = CyberApp::new();
+        app.init_modules(|router, _, storage| {
+            router.bank.init_balance(storage, &Addr::unchecked("owner"), coins(100, "boot")).unwrap();
+        });
+        app.enable_metering();
+
+        app.send_tokens(Addr::unchecked("owner"), Addr::unchecked("receiver"), &coins(10, "boot")).unwrap();
+
+        let metering = app.metering_report().unwrap().last().unwrap();
+        assert_eq!(metering.label, "bank_send receiver");
+        assert_eq!(metering.message_events, 1);
+        assert_eq!(metering.contract_calls, 0);
+        assert_eq!(metering.storage.writes, 2);
+    }
+
+    #[test]
+    fn snapshot_and_restore() {
+        let (mut app, contract) = setup();
+        app.init_modules(|router, _, storage| {
+            router.bank.init_balance(storage, &Addr::unchecked("owner"), coins(100, "boot")).unwrap();
+        });
+        app.advance_blocks(1000);
+        let block = app.block_info();
+        let id = app.snapshot();
+
+        // scenario A
+        app.send_tokens(Addr::unchecked("owner"), Addr::unchecked("alice"), &coins(30, "boot")).unwrap();
+        app.execute_contract(Addr::unchecked("owner"), contract.clone(), &2u64, &[]).unwrap();
+        app.advance_blocks(10);
+        assert_eq!(app.wrap().query_balance("owner", "boot").unwrap().amount.u128(), 70);
+        assert_eq!(app.wrap().query_wasm_raw(contract.clone(), 1u64.to_be_bytes().to_vec()).unwrap(), Some(vec![1u8; 32]));
+
+        // scenario B forks from the same point
+        app.restore(id).unwrap();
+        assert_eq!(app.block_info(), block);
+        assert_eq!(app.wrap().query_balance("owner", "boot").unwrap().amount.u128(), 100);
+        assert_eq!(app.wrap().query_balance("alice", "boot").unwrap().amount.u128(), 0);
+        assert_eq!(app.wrap().query_wasm_raw(contract.clone(), 1u64.to_be_bytes().to_vec()).unwrap(), None);
+        assert_eq!(app.wrap().query_wasm_raw(contract.clone(), b"counter".to_vec()).unwrap(), Some(0u64.to_be_bytes().to_vec()));
+
+        app.send_tokens(Addr::unchecked("owner"), Addr::unchecked("bob"), &coins(50, "boot")).unwrap();
+        app.restore(id).unwrap();
+        assert_eq!(app.wrap().query_balance("bob", "boot").unwrap().amount.u128(), 0);
+
+        assert!(app.restore(id + 1).is_err());
+    }
+}
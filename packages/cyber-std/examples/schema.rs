@@ -5,7 +5,7 @@ use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
 use cyber_std::{
     CyberMsg, CyberMsgWrapper, CyberQuery, CyberQueryWrapper, CyberRoute,
-    ParticleRankResponse, ParticlesAmountResponse, CyberlinksAmountResponse,
+    ParticleRankResponse, ParticlesAmountResponse, CyberlinksAmountResponse, CyberlinkExistResponse,
     ThoughtResponse, ThoughtStatsResponse, ThoughtLowestFeeResponse,
     RoutesResponse, RoutedEnergyResponse, RouteResponse,
     BandwidthPriceResponse, BandwidthLoadResponse, BandwidthTotalResponse,
@@ -27,6 +27,7 @@ fn main() {
     export_schema(&schema_for!(ParticleRankResponse), &out_dir);
     export_schema(&schema_for!(ParticlesAmountResponse), &out_dir);
     export_schema(&schema_for!(CyberlinksAmountResponse), &out_dir);
+    export_schema(&schema_for!(CyberlinkExistResponse), &out_dir);
     export_schema(&schema_for!(ThoughtResponse), &out_dir);
     export_schema(&schema_for!(ThoughtStatsResponse), &out_dir);
     export_schema(&schema_for!(ThoughtLowestFeeResponse), &out_dir);
@@ -12,7 +12,7 @@ pub use msg::{
 pub use querier::CyberQuerier;
 pub use query::{
     BandwidthLoadResponse, BandwidthPriceResponse,
-    BandwidthTotalResponse, CyberlinksAmountResponse, CyberQuery,
+    BandwidthTotalResponse, CyberlinksAmountResponse, CyberlinkExistResponse, CyberQuery,
     CyberQueryWrapper, ThoughtLowestFeeResponse, NeuronBandwidthResponse,
     ParticleRankResponse, ParticlesAmountResponse, RoutedEnergyResponse,
     RouteResponse, RoutesResponse, ThoughtResponse, ThoughtStatsResponse,
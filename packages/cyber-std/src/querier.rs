@@ -1,6 +1,6 @@
 use cosmwasm_std::{QuerierWrapper, StdResult};
 
-use crate::query::{BandwidthLoadResponse, BandwidthPriceResponse, BandwidthTotalResponse, CyberQuery, CyberQueryWrapper, CyberlinksAmountResponse, ThoughtLowestFeeResponse, NeuronBandwidthResponse, ParticleRankResponse, ParticlesAmountResponse, RouteResponse, RoutedEnergyResponse, RoutesResponse, ThoughtResponse, ThoughtStatsResponse, PoolParamsResponse, PoolLiquidityResponse, PoolSupplyResponse, PoolPriceResponse, PoolAddressResponse};
+use crate::query::{BandwidthLoadResponse, BandwidthPriceResponse, BandwidthTotalResponse, CyberQuery, CyberQueryWrapper, CyberlinksAmountResponse, CyberlinkExistResponse, ThoughtLowestFeeResponse, NeuronBandwidthResponse, ParticleRankResponse, ParticlesAmountResponse, RouteResponse, RoutedEnergyResponse, RoutesResponse, ThoughtResponse, ThoughtStatsResponse, PoolParamsResponse, PoolLiquidityResponse, PoolSupplyResponse, PoolPriceResponse, PoolAddressResponse};
 use crate::route::CyberRoute;
 
 pub struct CyberQuerier<'a> {
@@ -45,6 +45,24 @@ impl<'a> CyberQuerier<'a> {
         Ok(res)
     }
 
+    pub fn query_cyberlink_exist<T: Into<String>>(
+        &self,
+        from: T,
+        to: T,
+        address: T,
+    ) -> StdResult<CyberlinkExistResponse> {
+        let request = CyberQueryWrapper {
+            route: CyberRoute::Graph,
+            query_data: CyberQuery::CyberlinkExist {
+                from: from.into(),
+                to: to.into(),
+                address: address.into(),
+            },
+        };
+        let res: CyberlinkExistResponse = self.querier.query(&request.into())?;
+        Ok(res)
+    }
+
     pub fn query_thought<T: Into<String>>(
         &self,
         program: T,
@@ -20,6 +20,11 @@ pub enum CyberQuery {
     },
     ParticlesAmount {},
     CyberlinksAmount {},
+    CyberlinkExist {
+        from: String,
+        to: String,
+        address: String,
+    },
     Thought {
         program: String,
         name: String,
@@ -82,6 +87,11 @@ pub struct CyberlinksAmountResponse {
     pub cyberlinks_amount: u64,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CyberlinkExistResponse {
+    pub exist: bool,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ThoughtResponse {
     pub program: String,
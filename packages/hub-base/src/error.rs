@@ -0,0 +1,32 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Incorrect value for field {field}. Allowed expression /[a-z0-9-]/")]
+    InvalidBasicValue { field: String },
+
+    #[error("Incorrect value for field {field}. Allowed expression /[A-Z0-9]/")]
+    InvalidUppercaseValue { field: String },
+
+    #[error("Incorrect value for field {field}. Allowed expression /[0-9]/")]
+    InvalidPeriod { field: String },
+
+    #[error("Incorrect value for field {field}. Allowed only url")]
+    InvalidUrl { field: String },
+
+    #[error("Incorrect value for field {field}. Allowed only Ipfs hash")]
+    InvalidCid { field: String },
+
+    #[error("Custom Error val: {val:?}")]
+    CustomError { val: String },
+
+    #[error("Cannot migrate from different contract type: {previous_contract}")]
+    CannotMigrate { previous_contract: String },
+}
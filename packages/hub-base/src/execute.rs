@@ -0,0 +1,58 @@
+use cosmwasm_std::{attr, Addr, DepsMut, MessageInfo, Response, StdResult, Storage};
+use cw2::set_contract_version;
+
+use crate::error::ContractError;
+use crate::state::{Config, CONFIG, ENTRY_SEQ};
+
+pub fn instantiate(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<String>,
+    contract_name: &str,
+    contract_version: &str,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, contract_name, contract_version)?;
+
+    let owner = owner
+        .and_then(|addr_string| deps.api.addr_validate(addr_string.as_str()).ok())
+        .unwrap_or(info.sender);
+
+    let config = Config {
+        owner: Some(owner.clone()),
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    ENTRY_SEQ.save(deps.storage, &0u64)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "instantiate")
+        .add_attribute("owner", owner))
+}
+
+pub fn ensure_owner(storage: &dyn Storage, sender: &Addr) -> Result<(), ContractError> {
+    let owner = CONFIG.load(storage)?.owner;
+    if owner.as_ref() != Some(sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(())
+}
+
+pub fn execute_update_owner(
+    deps: DepsMut,
+    info: MessageInfo,
+    new_owner: Option<String>,
+) -> Result<Response, ContractError> {
+    ensure_owner(deps.storage, &info.sender)?;
+
+    let mut tmp_owner = None;
+    if let Some(addr) = new_owner {
+        tmp_owner = Some(deps.api.addr_validate(&addr)?)
+    }
+
+    CONFIG.update(deps.storage, |mut exists| -> StdResult<_> {
+        exists.owner = tmp_owner;
+        Ok(exists)
+    })?;
+
+    Ok(Response::new().add_attributes(vec![attr("action", "update_owner")]))
+}
@@ -0,0 +1,10 @@
+pub mod error;
+pub mod execute;
+pub mod registry;
+pub mod state;
+pub mod validating;
+#[cfg(test)]
+mod tests;
+
+pub use crate::error::ContractError;
+pub use crate::registry::{HubEntry, Registry};
@@ -0,0 +1,159 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use cosmwasm_std::{Addr, DepsMut, Order, Response, StdResult, Storage};
+use cw2::{get_contract_version, set_contract_version};
+use cw_storage_plus::{Bound, IndexList, IndexedMap, MultiIndex};
+
+use crate::error::ContractError;
+use crate::execute::ensure_owner;
+use crate::state::ENTRY_SEQ;
+
+pub const MAX_LIMIT: u32 = 30;
+pub const DEFAULT_LIMIT: u32 = 20;
+
+pub trait HubEntry: Serialize + DeserializeOwned + Clone {
+    /// Checks all fields of a created entry, updates validate only the provided fields
+    fn validate(&self) -> Result<(), ContractError>;
+
+    /// Address managing the entry, entries without one are managed by the hub owner
+    fn owner(&self) -> Option<&Addr> {
+        None
+    }
+}
+
+pub struct Registry<'a, T, I>
+where
+    T: HubEntry,
+    I: IndexList<T>,
+{
+    pub entries: IndexedMap<'a, u64, T, I>,
+}
+
+impl<'a, T, I> Registry<'a, T, I>
+where
+    T: HubEntry,
+    I: IndexList<T>,
+{
+    pub fn new(entries: IndexedMap<'a, u64, T, I>) -> Self {
+        Registry { entries }
+    }
+
+    pub fn authorize(&self, storage: &dyn Storage, sender: &Addr, entry: &T) -> Result<(), ContractError> {
+        match entry.owner() {
+            Some(owner) if owner == sender => Ok(()),
+            Some(_) => Err(ContractError::Unauthorized {}),
+            None => ensure_owner(storage, sender),
+        }
+    }
+
+    pub fn load_authorized(&self, storage: &dyn Storage, sender: &Addr, id: u64) -> Result<T, ContractError> {
+        let entry = self.entries.load(storage, id)?;
+        self.authorize(storage, sender, &entry)?;
+        Ok(entry)
+    }
+
+    /// Creates the entry built with the next id of the sequence
+    pub fn create<F>(&self, deps: DepsMut, sender: &Addr, build: F) -> Result<Response, ContractError>
+    where
+        F: FnOnce(u64) -> T,
+    {
+        let id = ENTRY_SEQ.load(deps.storage)? + 1;
+        let entry = build(id);
+        self.authorize(deps.storage, sender, &entry)?;
+        entry.validate()?;
+        self.entries.save(deps.storage, id, &entry)?;
+        ENTRY_SEQ.save(deps.storage, &id)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "execute_create_entry")
+            .add_attribute("new_entry_id", id.to_string()))
+    }
+
+    /// Updates the entry with the fields provided and validated by the update
+    pub fn update<F>(&self, deps: DepsMut, sender: &Addr, id: u64, update: F) -> Result<Response, ContractError>
+    where
+        F: FnOnce(T) -> Result<T, ContractError>,
+    {
+        let entry = self.load_authorized(deps.storage, sender, id)?;
+        self.entries.save(deps.storage, id, &update(entry)?)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "execute_update_entry")
+            .add_attribute("updated_entry_id", id.to_string()))
+    }
+
+    pub fn delete(&self, deps: DepsMut, sender: &Addr, id: u64) -> Result<Response, ContractError> {
+        self.load_authorized(deps.storage, sender, id)?;
+        self.entries.remove(deps.storage, id)?;
+
+        Ok(Response::new()
+            .add_attribute("method", "execute_delete_entry")
+            .add_attribute("deleted_entry_id", id.to_string()))
+    }
+
+    pub fn entry(&self, storage: &dyn Storage, id: u64) -> StdResult<T> {
+        self.entries.load(storage, id)
+    }
+
+    pub fn list(&self, storage: &dyn Storage, start_after: Option<u64>, limit: Option<u32>) -> StdResult<Vec<T>> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+        self.entries
+            .range(storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(_, entry)| entry))
+            .collect()
+    }
+
+    /// Lists entries with the given value of the index selected from the indexes of the registry
+    pub fn list_by<F>(
+        &self,
+        storage: &dyn Storage,
+        index: F,
+        value: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<T>>
+    where
+        F: FnOnce(&I) -> &MultiIndex<'a, String, T, u64>,
+    {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+        index(&self.entries.idx)
+            .prefix(value)
+            .range(storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(_, entry)| entry))
+            .collect()
+    }
+
+    /// Checks the migrated contract, saves all entries again to (re)build their indexes
+    /// and sets the version of the contract. Entries are re-saved within the single migrate
+    /// transaction, the gas of the migration grows with the number of entries
+    pub fn migrate(
+        &self,
+        storage: &mut dyn Storage,
+        contract_name: &str,
+        contract_version: &str,
+    ) -> Result<(), ContractError> {
+        let version = get_contract_version(storage)?;
+        if version.contract != contract_name {
+            return Err(ContractError::CannotMigrate {
+                previous_contract: version.contract,
+            });
+        }
+
+        let ids = self
+            .entries
+            .keys(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<u64>>>()?;
+        for id in ids {
+            let entry = self.entries.load(storage, id)?;
+            self.entries.save(storage, id, &entry)?;
+        }
+        set_contract_version(storage, contract_name, contract_version)?;
+
+        Ok(())
+    }
+}
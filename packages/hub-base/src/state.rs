@@ -0,0 +1,17 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::Addr;
+use cw_storage_plus::Item;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub owner: Option<Addr>,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const ENTRY_SEQ: Item<u64> = Item::new("entry_seq");
+
+/// Entries of the registries are kept in the namespace of the former LIST map of the hubs,
+/// indexes are built for them on migration
+pub const ENTRIES_NAMESPACE: &str = "list";
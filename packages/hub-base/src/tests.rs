@@ -0,0 +1,243 @@
+use cosmwasm_std::testing::{mock_dependencies, mock_info};
+use cosmwasm_std::{attr, Addr};
+use cw2::get_contract_version;
+use cw_storage_plus::{Index, IndexList, IndexedMap, MultiIndex};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::error::ContractError;
+use crate::execute::{execute_update_owner, instantiate};
+use crate::registry::{HubEntry, Registry};
+use crate::state::{Config, CONFIG, ENTRIES_NAMESPACE};
+use crate::validating::{
+    provided_particle, update_field, validate_by_basic_rule, validate_particle, validate_url, validate_url_symbols,
+};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+struct Entry {
+    id: u64,
+    name: String,
+    owner: Option<Addr>,
+    particle: String,
+}
+
+impl HubEntry for Entry {
+    fn validate(&self) -> Result<(), ContractError> {
+        validate_by_basic_rule(&self.name, "name")?;
+        validate_particle(&self.particle)
+    }
+
+    fn owner(&self) -> Option<&Addr> {
+        self.owner.as_ref()
+    }
+}
+
+struct EntryIndexes<'a> {
+    name: MultiIndex<'a, String, Entry, u64>,
+}
+
+impl<'a> IndexList<Entry> for EntryIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Entry>> + '_> {
+        let v: Vec<&dyn Index<Entry>> = vec![&self.name];
+        Box::new(v.into_iter())
+    }
+}
+
+fn registry<'a>() -> Registry<'a, Entry, EntryIndexes<'a>> {
+    let indexes = EntryIndexes {
+        name: MultiIndex::new(|d| d.name.clone(), ENTRIES_NAMESPACE, "list_name"),
+    };
+    Registry::new(IndexedMap::new(ENTRIES_NAMESPACE, indexes))
+}
+
+fn entry(id: u64, name: &str, owner: Option<&str>) -> Entry {
+    Entry {
+        id,
+        name: name.to_string(),
+        owner: owner.map(Addr::unchecked),
+        particle: "".to_string(),
+    }
+}
+
+#[test]
+fn instantiate_and_update_owner() {
+    let mut deps = mock_dependencies();
+    instantiate(deps.as_mut(), mock_info("creator", &[]), None, "hub-test", "0.1.0").unwrap();
+    assert_eq!(
+        CONFIG.load(&deps.storage).unwrap(),
+        Config { owner: Some(Addr::unchecked("creator")) }
+    );
+
+    let err = execute_update_owner(deps.as_mut(), mock_info("anyone", &[]), Some("anyone".to_string())).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    let res = execute_update_owner(deps.as_mut(), mock_info("creator", &[]), Some("new_owner".to_string())).unwrap();
+    assert_eq!(res.attributes, vec![attr("action", "update_owner")]);
+    assert_eq!(
+        CONFIG.load(&deps.storage).unwrap(),
+        Config { owner: Some(Addr::unchecked("new_owner")) }
+    );
+}
+
+#[test]
+fn entries_managed_by_hub_owner() {
+    let mut deps = mock_dependencies();
+    instantiate(deps.as_mut(), mock_info("creator", &[]), None, "hub-test", "0.1.0").unwrap();
+    let creator = Addr::unchecked("creator");
+    let anyone = Addr::unchecked("anyone");
+
+    let err = registry().create(deps.as_mut(), &anyone, |id| entry(id, "first", None)).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    let res = registry().create(deps.as_mut(), &creator, |id| entry(id, "first", None)).unwrap();
+    assert_eq!(res.attributes, vec![attr("method", "execute_create_entry"), attr("new_entry_id", "1")]);
+
+    let err = registry().create(deps.as_mut(), &creator, |id| entry(id, "Invalid Name", None)).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidBasicValue { field } if field == "name"));
+
+    let err = registry()
+        .update(deps.as_mut(), &anyone, 1, |mut e| { e.name = "second".to_string(); Ok(e) })
+        .unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    let err = registry()
+        .update(deps.as_mut(), &creator, 1, |mut e| {
+            update_field(&mut e.particle, Some("particle".to_string()), |v| validate_particle(v))?;
+            Ok(e)
+        })
+        .unwrap_err();
+    assert!(matches!(err, ContractError::InvalidCid { field } if field == "particle"));
+
+    let res = registry()
+        .update(deps.as_mut(), &creator, 1, |mut e| { e.name = "second".to_string(); Ok(e) })
+        .unwrap();
+    assert_eq!(res.attributes, vec![attr("method", "execute_update_entry"), attr("updated_entry_id", "1")]);
+    assert_eq!(registry().entry(&deps.storage, 1).unwrap(), entry(1, "second", None));
+
+    let err = registry().delete(deps.as_mut(), &anyone, 1).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    let res = registry().delete(deps.as_mut(), &creator, 1).unwrap();
+    assert_eq!(res.attributes, vec![attr("method", "execute_delete_entry"), attr("deleted_entry_id", "1")]);
+    assert!(registry().entry(&deps.storage, 1).is_err());
+}
+
+#[test]
+fn entries_managed_by_entry_owner() {
+    let mut deps = mock_dependencies();
+    instantiate(deps.as_mut(), mock_info("creator", &[]), None, "hub-test", "0.1.0").unwrap();
+    let creator = Addr::unchecked("creator");
+    let neuron = Addr::unchecked("neuron");
+
+    registry().create(deps.as_mut(), &neuron, |id| entry(id, "first", Some("neuron"))).unwrap();
+
+    let err = registry().create(deps.as_mut(), &neuron, |id| entry(id, "second", Some("creator"))).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    let err = registry()
+        .update(deps.as_mut(), &creator, 1, |mut e| { e.name = "second".to_string(); Ok(e) })
+        .unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+
+    registry()
+        .update(deps.as_mut(), &neuron, 1, |mut e| { e.name = "second".to_string(); Ok(e) })
+        .unwrap();
+
+    let err = registry().delete(deps.as_mut(), &creator, 1).unwrap_err();
+    assert!(matches!(err, ContractError::Unauthorized {}));
+    registry().delete(deps.as_mut(), &neuron, 1).unwrap();
+}
+
+#[test]
+fn update_validates_provided_fields() {
+    let mut deps = mock_dependencies();
+    instantiate(deps.as_mut(), mock_info("creator", &[]), None, "hub-test", "0.1.0").unwrap();
+    let creator = Addr::unchecked("creator");
+
+    // entry saved before the rule of its name
+    registry().entries.save(&mut deps.storage, 1, &entry(1, "Legacy Name", None)).unwrap();
+
+    let particle = "QmYpTB36duejmy1szbdL1D2EzC5fgRL4dyhSFsHkMYPtny".to_string();
+    registry()
+        .update(deps.as_mut(), &creator, 1, |mut e| {
+            update_field(&mut e.name, None, |v| validate_by_basic_rule(v, "name"))?;
+            update_field(&mut e.particle, Some(particle.clone()), |v| validate_particle(v))?;
+            Ok(e)
+        })
+        .unwrap();
+
+    let updated = registry().entry(&deps.storage, 1).unwrap();
+    assert_eq!(updated.name, "Legacy Name");
+    assert_eq!(updated.particle, particle);
+}
+
+#[test]
+fn validate_urls_and_particles() {
+    validate_url("https://explorer.com/txs/hash={hash}&page=1", "url").unwrap();
+    assert_eq!(
+        validate_url_symbols("https://explorer.com/txs/hash={hash}", "url", b"}{:/.-_").unwrap_err(),
+        ContractError::InvalidUrl { field: "url".to_string() }
+    );
+    validate_url_symbols("https://explorer.com/{hash}", "url", b"}{:/.-_").unwrap();
+
+    // empty particle is stored when none is provided and clears it on update, a provided one is a cid
+    assert_eq!(provided_particle(None).unwrap(), "");
+    assert_eq!(
+        provided_particle(Some("".to_string())).unwrap_err(),
+        ContractError::InvalidCid { field: "particle".to_string() }
+    );
+    let particle = "QmYpTB36duejmy1szbdL1D2EzC5fgRL4dyhSFsHkMYPtny".to_string();
+    assert_eq!(provided_particle(Some(particle.clone())).unwrap(), particle);
+    validate_particle("").unwrap();
+}
+
+#[test]
+fn list_entries() {
+    let mut deps = mock_dependencies();
+    instantiate(deps.as_mut(), mock_info("creator", &[]), None, "hub-test", "0.1.0").unwrap();
+    let creator = Addr::unchecked("creator");
+
+    for i in 0..35 {
+        let name = if i % 2 == 0 { "even" } else { "odd" };
+        registry().create(deps.as_mut(), &creator, |id| entry(id, name, None)).unwrap();
+    }
+
+    let entries = registry().list(&deps.storage, None, None).unwrap();
+    assert_eq!(entries.len(), 20);
+    assert_eq!(entries[0].id, 1);
+
+    let entries = registry().list(&deps.storage, Some(30), Some(100)).unwrap();
+    assert_eq!(entries.iter().map(|e| e.id).collect::<Vec<_>>(), vec![31, 32, 33, 34, 35]);
+
+    let entries = registry().list(&deps.storage, None, Some(100)).unwrap();
+    assert_eq!(entries.len(), 30);
+
+    let entries = registry()
+        .list_by(&deps.storage, |idx| &idx.name, "odd".to_string(), Some(10), Some(3))
+        .unwrap();
+    assert_eq!(entries.iter().map(|e| e.id).collect::<Vec<_>>(), vec![12, 14, 16]);
+}
+
+#[test]
+fn migrate_rebuilds_indexes() {
+    let mut deps = mock_dependencies();
+    instantiate(deps.as_mut(), mock_info("creator", &[]), None, "hub-test", "0.1.0").unwrap();
+
+    // entries saved without indexes
+    let plain: IndexedMap<u64, Entry, EntryIndexes> = IndexedMap::new(
+        "list",
+        EntryIndexes { name: MultiIndex::new(|d| d.name.clone(), "list", "list_other") },
+    );
+    plain.save(&mut deps.storage, 1, &entry(1, "first", None)).unwrap();
+    assert!(registry().list_by(&deps.storage, |idx| &idx.name, "first".to_string(), None, None).unwrap().is_empty());
+
+    let err = registry().migrate(&mut deps.storage, "hub-other", "0.2.0").unwrap_err();
+    assert!(matches!(err, ContractError::CannotMigrate { .. }));
+
+    registry().migrate(&mut deps.storage, "hub-test", "0.2.0").unwrap();
+    assert_eq!(get_contract_version(&deps.storage).unwrap().version, "0.2.0");
+    assert_eq!(
+        registry().list_by(&deps.storage, |idx| &idx.name, "first".to_string(), None, None).unwrap(),
+        vec![entry(1, "first", None)]
+    );
+}
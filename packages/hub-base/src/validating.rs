@@ -0,0 +1,95 @@
+use cid::{Cid, Version};
+use std::str::FromStr;
+
+use crate::error::ContractError;
+
+/*
+ * Basic rule /[a-z0-9-]/
+ */
+pub fn validate_by_basic_rule(val: &str, field_name: &str) -> Result<(), ContractError> {
+    // - && 0-9 && a-z
+    if !val.bytes().all(|byte| byte == b'-' || byte.is_ascii_digit() || byte.is_ascii_lowercase()) {
+        return Err(ContractError::InvalidBasicValue { field: field_name.to_string() });
+    }
+
+    Ok(())
+}
+
+/*
+ * Basic rule /[A-Z0-9]/
+ */
+pub fn validate_by_basic_uppercase_rule(val: &str, field_name: &str) -> Result<(), ContractError> {
+    // 0-9 && A-Z
+    if !val.bytes().all(|byte| byte.is_ascii_digit() || byte.is_ascii_uppercase()) {
+        return Err(ContractError::InvalidUppercaseValue { field: field_name.to_string() });
+    }
+
+    Ok(())
+}
+
+/*
+ * Basic rule /[0-9]/
+ */
+pub fn validate_period(val: &str, field_name: &str) -> Result<(), ContractError> {
+    // 0-9
+    if !val.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(ContractError::InvalidPeriod { field: field_name.to_string() });
+    }
+
+    Ok(())
+}
+
+/// Symbols allowed in urls besides 0-9 a-z A-Z
+pub const URL_SYMBOLS: &[u8] = b"=&}{:/.-_";
+
+pub fn validate_url(val: &str, field_name: &str) -> Result<(), ContractError> {
+    validate_url_symbols(val, field_name, URL_SYMBOLS)
+}
+
+/// Url with the own set of symbols allowed by the hub besides 0-9 a-z A-Z
+pub fn validate_url_symbols(val: &str, field_name: &str, symbols: &[u8]) -> Result<(), ContractError> {
+    if !val.bytes().all(|byte| symbols.contains(&byte) || byte.is_ascii_alphanumeric()) {
+        return Err(ContractError::InvalidUrl { field: field_name.to_string() });
+    }
+
+    Ok(())
+}
+
+pub fn validate_ipfs_cid(particle: &str, field_name: &str) -> Result<(), ContractError> {
+    match Cid::from_str(particle) {
+        Ok(cid) if cid.version() == Version::V0 => Ok(()),
+        _ => Err(ContractError::InvalidCid { field: field_name.to_string() }),
+    }
+}
+
+// particle of an entry is optional and kept empty when not set, an update clears it with an empty particle
+pub fn validate_particle(particle: &str) -> Result<(), ContractError> {
+    if particle.is_empty() {
+        return Ok(());
+    }
+    validate_ipfs_cid(particle, "particle")
+}
+
+/// Particle of a created entry, the provided one is a CID, empty when none is provided
+pub fn provided_particle(particle: Option<String>) -> Result<String, ContractError> {
+    match particle {
+        Some(particle) => {
+            validate_ipfs_cid(&particle, "particle")?;
+            Ok(particle)
+        }
+        None => Ok(String::new()),
+    }
+}
+
+/// Sets the field of an updated entry to the provided value once it is validated,
+/// the field is kept as is when no value is provided
+pub fn update_field<V, F>(field: &mut V, value: Option<V>, validate: F) -> Result<(), ContractError>
+where
+    F: FnOnce(&V) -> Result<(), ContractError>,
+{
+    if let Some(value) = value {
+        validate(&value)?;
+        *field = value;
+    }
+    Ok(())
+}